@@ -0,0 +1,45 @@
+//! 识别性能基准：模拟 1080p 和 4K 屏幕截图，分别测量有码和无码（所有策略都会执行）时的耗时
+//!
+//! 运行：`cargo bench -p glimpse-core --bench scan`
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use glimpse_core::debug_log::{self, LogTarget};
+use glimpse_core::{scan_qr_codes, ScanPipeline};
+
+/// 测试的屏幕分辨率
+const RESOLUTIONS: [(&str, u32, u32); 2] = [("1080p", 1920, 1080), ("4k", 3840, 2160)];
+
+fn bench_scan(c: &mut Criterion) {
+    debug_log::set_target(LogTarget::Off);
+    let pipeline = ScanPipeline::default();
+
+    let mut group = c.benchmark_group("scan_qr_codes");
+    group.sample_size(10);
+
+    for (label, width, height) in RESOLUTIONS {
+        // 屏幕上有 3 个码：找到后其余策略提前取消
+        let (screen, contents) = common::screen_with_codes(width, height, 3, 42);
+        let report = scan_qr_codes(&screen, &pipeline).expect("扫描失败");
+        for content in &contents {
+            // 识别率回退时直接报错，避免测得的是未识别时的耗时
+            assert!(report.results.iter().any(|r| &r.content == content), "{} 截图中未识别: {}", label, content);
+        }
+        group.bench_with_input(BenchmarkId::new("codes", label), &screen, |b, screen| {
+            b.iter(|| scan_qr_codes(screen, &pipeline).expect("扫描失败"))
+        });
+
+        // 没有码：所有策略都会执行，是最慢的情况
+        let (empty, _) = common::screen_with_codes(width, height, 0, 42);
+        group.bench_with_input(BenchmarkId::new("empty", label), &empty, |b, screen| {
+            b.iter(|| scan_qr_codes(screen, &pipeline).expect("扫描失败"))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan);
+criterion_main!(benches);
@@ -0,0 +1,448 @@
+//! glimpse-cli 命令行工具
+//! 不启动图形界面，直接识别图像文件（或标准输入）中的二维码和文字，输出 JSON / NDJSON / 纯文本
+
+use serde::Serialize;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use glimpse_core::debug_log::{self, LogTarget};
+use glimpse_core::image_file::{self, ImageFrame};
+use glimpse_core::ocr::{self, OcrResult};
+use glimpse_core::qr_scanner::{self, QrCodeResult};
+use glimpse_core::scan_pipeline::ScanPipeline;
+
+const USAGE: &str = "\
+用法: glimpse-cli [选项] <输入>...
+
+输入可以是图像文件、目录、通配符（如 \"shots/*.png\"），或 - 表示从标准输入读取图像
+支持 PNG、JPEG、WebP、BMP、GIF、TIFF，动画 GIF 和多页 TIFF 逐帧识别
+
+选项:
+  -m, --mode <qr|ocr|all>           识别内容（默认 qr）
+  -f, --format <json|ndjson|text>   输出格式（默认 json）
+  -r, --recursive                   递归扫描子目录
+  -p, --pipeline <文件>             使用 JSON 格式的识别流水线配置
+  -a, --all-occurrences             相同内容的码分别输出每一处（默认合并并记录次数）
+  -v, --verbose                     将调试日志输出到标准错误
+  -h, --help                        显示帮助
+  -V, --version                     显示版本
+
+退出码: 0 识别到内容，1 未识别到内容，2 参数错误或有文件读取失败";
+
+/// 识别内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Qr,
+    Ocr,
+    All,
+}
+
+impl Mode {
+    fn scans_qr(self) -> bool {
+        matches!(self, Mode::Qr | Mode::All)
+    }
+
+    fn runs_ocr(self) -> bool {
+        matches!(self, Mode::Ocr | Mode::All)
+    }
+}
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// 所有文件的结果组成一个 JSON 数组
+    Json,
+    /// 每个文件一行 JSON，便于流式处理
+    Ndjson,
+    /// 纯文本，每行一个识别结果
+    Text,
+}
+
+/// 命令行参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CliArgs {
+    inputs: Vec<String>,
+    mode: Mode,
+    format: OutputFormat,
+    recursive: bool,
+    verbose: bool,
+    pipeline: Option<PathBuf>,
+    all_occurrences: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Run(CliArgs),
+    Help,
+    Version,
+}
+
+/// 解析命令行参数（不含程序名），支持 `--name value` 和 `--name=value` 两种写法
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut parsed = CliArgs {
+        inputs: Vec::new(),
+        mode: Mode::Qr,
+        format: OutputFormat::Json,
+        recursive: false,
+        verbose: false,
+        pipeline: None,
+        all_occurrences: false,
+    };
+
+    let mut args = args.into_iter();
+    let mut only_inputs = false;
+    while let Some(arg) = args.next() {
+        if only_inputs || arg == "-" || !arg.starts_with('-') {
+            parsed.inputs.push(arg);
+            continue;
+        }
+
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("选项 {} 缺少参数值", name))
+        };
+
+        match name.as_str() {
+            "--" => only_inputs = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-r" | "--recursive" => parsed.recursive = true,
+            "-v" | "--verbose" => parsed.verbose = true,
+            "-a" | "--all-occurrences" => parsed.all_occurrences = true,
+            "-m" | "--mode" => {
+                parsed.mode = match value(&name)?.as_str() {
+                    "qr" => Mode::Qr,
+                    "ocr" => Mode::Ocr,
+                    "all" => Mode::All,
+                    other => return Err(format!("未知的识别模式: {}", other)),
+                }
+            }
+            "-f" | "--format" => {
+                parsed.format = match value(&name)?.as_str() {
+                    "json" => OutputFormat::Json,
+                    "ndjson" => OutputFormat::Ndjson,
+                    "text" => OutputFormat::Text,
+                    other => return Err(format!("未知的输出格式: {}", other)),
+                }
+            }
+            "-p" | "--pipeline" => parsed.pipeline = Some(PathBuf::from(value(&name)?)),
+            _ => return Err(format!("未知选项: {}", arg)),
+        }
+    }
+
+    if parsed.inputs.is_empty() {
+        return Err("缺少输入文件".to_string());
+    }
+    Ok(Command::Run(parsed))
+}
+
+/// 待识别的输入
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match self {
+            Input::Stdin => "-".to_string(),
+            Input::File(path) => path.display().to_string(),
+        }
+    }
+
+    /// 读取所有帧（动画 GIF、多页 TIFF 为多帧）
+    fn load(&self) -> Result<Vec<ImageFrame>, String> {
+        match self {
+            Input::Stdin => {
+                let mut bytes = Vec::new();
+                io::stdin()
+                    .read_to_end(&mut bytes)
+                    .map_err(|e| format!("读取标准输入失败: {}", e))?;
+                Ok(image_file::decode_frames(&bytes)?)
+            }
+            Input::File(path) => Ok(image_file::load_frames(path)?),
+        }
+    }
+}
+
+/// 是否为可解码的图像文件（按扩展名判断）
+fn is_image_file(path: &Path) -> bool {
+    path.is_file() && image_file::is_supported_file(path)
+}
+
+/// 收集目录下的图像文件
+fn collect_dir(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("读取目录 {} 失败: {}", dir.display(), e))?;
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            if recursive {
+                collect_dir(&path, recursive, files)?;
+            }
+        } else if is_image_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 将命令行输入展开为文件列表（目录、通配符）
+fn expand_inputs(inputs: &[String], recursive: bool) -> Result<Vec<Input>, String> {
+    let mut expanded = Vec::new();
+    for input in inputs {
+        if input == "-" {
+            expanded.push(Input::Stdin);
+            continue;
+        }
+
+        let path = Path::new(input);
+        if path.is_dir() {
+            let mut files = Vec::new();
+            collect_dir(path, recursive, &mut files)?;
+            expanded.extend(files.into_iter().map(Input::File));
+        } else if input.contains(['*', '?', '[']) {
+            // Windows 命令行不会展开通配符，这里自行处理
+            let matches = glob::glob(input).map_err(|e| format!("通配符 {} 无效: {}", input, e))?;
+            let before = expanded.len();
+            for path in matches.filter_map(Result::ok) {
+                if is_image_file(&path) {
+                    expanded.push(Input::File(path));
+                }
+            }
+            if expanded.len() == before {
+                eprintln!("警告: 通配符 {} 没有匹配到图像文件", input);
+            }
+        } else {
+            expanded.push(Input::File(path.to_path_buf()));
+        }
+    }
+    Ok(expanded)
+}
+
+/// 单个输入的识别结果
+#[derive(Debug, Serialize)]
+struct FileReport {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    codes: Option<Vec<QrCodeResult>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ocr: Option<OcrResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl FileReport {
+    /// 是否识别到任何内容
+    fn has_content(&self) -> bool {
+        self.codes.as_ref().is_some_and(|codes| !codes.is_empty())
+            || self.ocr.as_ref().is_some_and(|ocr| !ocr.text.trim().is_empty())
+    }
+}
+
+/// 识别单个输入，出错时记录在报告中而不是中止
+fn process(input: &Input, mode: Mode, pipeline: &ScanPipeline) -> FileReport {
+    let mut report = FileReport { path: input.name(), codes: None, ocr: None, error: None };
+
+    let frames = match input.load() {
+        Ok(frames) => frames,
+        Err(e) => {
+            report.error = Some(e);
+            return report;
+        }
+    };
+
+    let mut errors = Vec::new();
+    if mode.scans_qr() {
+        match qr_scanner::scan_frames(&frames, pipeline) {
+            Ok(scan) => report.codes = Some(scan.results),
+            Err(e) => errors.push(format!("二维码识别失败: {}", e)),
+        }
+    }
+    if mode.runs_ocr() {
+        // 多帧时各帧文本以空行分隔
+        match ocr::recognize_frames(&frames).map(ocr::join_frames) {
+            Ok(result) => report.ocr = Some(result),
+            Err(e) => errors.push(format!("文字识别失败: {}", e)),
+        }
+    }
+    if !errors.is_empty() {
+        report.error = Some(errors.join("; "));
+    }
+    report
+}
+
+/// 纯文本输出：多个文件时每行以文件名开头
+fn write_text(out: &mut impl Write, report: &FileReport, with_path: bool) -> io::Result<()> {
+    let prefix = if with_path { format!("{}: ", report.path) } else { String::new() };
+    if let Some(error) = &report.error {
+        eprintln!("{}错误: {}", prefix, error);
+    }
+    for code in report.codes.iter().flatten() {
+        writeln!(out, "{}{}", prefix, code.content)?;
+    }
+    if let Some(ocr) = &report.ocr {
+        for line in ocr.text.lines() {
+            writeln!(out, "{}{}", prefix, line)?;
+        }
+    }
+    Ok(())
+}
+
+/// 读取流水线配置文件
+fn load_pipeline(path: Option<&Path>) -> Result<ScanPipeline, String> {
+    let Some(path) = path else {
+        return Ok(ScanPipeline::default());
+    };
+    let json = std::fs::read_to_string(path).map_err(|e| format!("读取流水线配置失败: {}", e))?;
+    let pipeline: ScanPipeline = serde_json::from_str(&json).map_err(|e| format!("解析流水线配置失败: {}", e))?;
+    pipeline.validate()?;
+    Ok(pipeline)
+}
+
+/// 命令行入口
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Version) => {
+            println!("glimpse-cli {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("错误: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    debug_log::set_target(if args.verbose { LogTarget::Stderr } else { LogTarget::Off });
+
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("错误: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &CliArgs) -> Result<ExitCode, String> {
+    let mut pipeline = load_pipeline(args.pipeline.as_deref())?;
+    pipeline.keep_duplicates |= args.all_occurrences;
+    let inputs = expand_inputs(&args.inputs, args.recursive)?;
+    if inputs.is_empty() {
+        return Err("没有找到可识别的图像文件".to_string());
+    }
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let write_err = |e: io::Error| format!("写入输出失败: {}", e);
+
+    let mut reports = Vec::new();
+    let mut found = false;
+    let mut failed = false;
+    for input in &inputs {
+        let report = process(input, args.mode, &pipeline);
+        found |= report.has_content();
+        failed |= report.error.is_some();
+
+        // NDJSON 和纯文本逐个输出，JSON 数组在最后统一输出
+        match args.format {
+            OutputFormat::Json => reports.push(report),
+            OutputFormat::Ndjson => {
+                let line = serde_json::to_string(&report).map_err(|e| format!("序列化失败: {}", e))?;
+                writeln!(out, "{}", line).map_err(write_err)?;
+            }
+            OutputFormat::Text => write_text(&mut out, &report, inputs.len() > 1).map_err(write_err)?,
+        }
+    }
+
+    if args.format == OutputFormat::Json {
+        let json = serde_json::to_string_pretty(&reports).map_err(|e| format!("序列化失败: {}", e))?;
+        writeln!(out, "{}", json).map_err(write_err)?;
+    }
+
+    Ok(if failed {
+        ExitCode::from(2)
+    } else if found {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_defaults() {
+        let Command::Run(args) = parse(&["a.png"]).unwrap() else { panic!() };
+        assert_eq!(args.inputs, vec!["a.png"]);
+        assert_eq!(args.mode, Mode::Qr);
+        assert_eq!(args.format, OutputFormat::Json);
+        assert!(!args.recursive);
+        assert!(!args.all_occurrences);
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let Command::Run(args) =
+            parse(&["-m", "all", "--format=ndjson", "-r", "-a", "--pipeline", "p.json", "-", "dir"]).unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!(args.mode, Mode::All);
+        assert_eq!(args.format, OutputFormat::Ndjson);
+        assert!(args.recursive);
+        assert!(args.all_occurrences);
+        assert_eq!(args.pipeline, Some(PathBuf::from("p.json")));
+        assert_eq!(args.inputs, vec!["-", "dir"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--mode"]).is_err());
+        assert!(parse(&["--mode", "barcode", "a.png"]).is_err());
+        assert!(parse(&["--unknown", "a.png"]).is_err());
+        assert_eq!(parse(&["-h"]).unwrap(), Command::Help);
+        // -- 之后的参数都视为输入
+        let Command::Run(args) = parse(&["--", "-r"]).unwrap() else { panic!() };
+        assert_eq!(args.inputs, vec!["-r"]);
+    }
+
+    #[test]
+    fn test_expand_directory_filters_images() {
+        let dir = std::env::temp_dir().join(format!("glimpse-cli-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        for name in ["b.png", "a.png", "notes.txt", "nested/c.png"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let inputs = vec![dir.display().to_string()];
+        let flat = expand_inputs(&inputs, false).unwrap();
+        assert_eq!(flat, vec![Input::File(dir.join("a.png")), Input::File(dir.join("b.png"))]);
+        assert_eq!(expand_inputs(&inputs, true).unwrap().len(), 3);
+
+        let pattern = vec![format!("{}/b*", dir.display())];
+        assert_eq!(expand_inputs(&pattern, false).unwrap(), vec![Input::File(dir.join("b.png"))]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+//! 二维码文本字符集解码模块
+//! 按 ECI 指示符解码字节段；没有 ECI 时在 UTF-8、GB18030、Shift-JIS、ISO-8859-1 之间推断
+
+use encoding_rs::Encoding;
+
+use crate::qr_symbol::{Segment, SegmentMode};
+
+/// 字符集
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    /// ISO-8859-1（每个字节直接映射为 U+0000~U+00FF）
+    Latin1,
+    /// encoding_rs 支持的编码
+    Encoding(&'static Encoding),
+}
+
+impl Charset {
+    /// 字符集名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Charset::Latin1 => "ISO-8859-1",
+            Charset::Encoding(encoding) => encoding.name(),
+        }
+    }
+
+    /// 解码字节，无法映射的字节替换为 U+FFFD
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Charset::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+            Charset::Encoding(encoding) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+        }
+    }
+
+    /// 根据 ECI 指示符确定字符集（AIM ECI 规范），不支持时返回 None
+    pub fn from_eci(eci: u32) -> Option<Self> {
+        use encoding_rs::*;
+        let encoding = match eci {
+            1 | 3 | 27 => return Some(Charset::Latin1),
+            4 => ISO_8859_2,
+            5 => ISO_8859_3,
+            6 => ISO_8859_4,
+            7 => ISO_8859_5,
+            8 => ISO_8859_6,
+            9 => ISO_8859_7,
+            10 => ISO_8859_8,
+            11 => WINDOWS_1254,
+            12 => ISO_8859_10,
+            13 => WINDOWS_874,
+            15 => ISO_8859_13,
+            16 => ISO_8859_14,
+            17 => ISO_8859_15,
+            18 => ISO_8859_16,
+            20 => SHIFT_JIS,
+            21 => WINDOWS_1250,
+            22 => WINDOWS_1251,
+            23 => WINDOWS_1252,
+            24 => WINDOWS_1256,
+            25 => UTF_16BE,
+            26 => UTF_8,
+            28 => BIG5,
+            29 | 32 => GB18030,
+            30 => EUC_KR,
+            _ => return None,
+        };
+        Some(Charset::Encoding(encoding))
+    }
+}
+
+/// 解码结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedText {
+    pub text: String,
+    /// 实际使用的字符集名称
+    pub charset: &'static str,
+}
+
+/// 推断无 ECI 字节流的字符集
+///
+/// 合法 UTF-8 优先；否则比较 GB18030 与 Shift-JIS 的字符分布得分；都不合法时使用 ISO-8859-1
+pub fn detect_charset(bytes: &[u8]) -> Charset {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    if std::str::from_utf8(bytes).is_ok() {
+        return Charset::Encoding(encoding_rs::UTF_8);
+    }
+
+    let gb_score = score_gb18030(bytes).filter(|_| decodes_cleanly(encoding_rs::GB18030, bytes));
+    let sjis_score = score_shift_jis(bytes).filter(|_| decodes_cleanly(encoding_rs::SHIFT_JIS, bytes));
+
+    match (gb_score, sjis_score) {
+        // 分数相同时优先 GB18030
+        (Some(gb), Some(sjis)) if sjis > gb => Charset::Encoding(encoding_rs::SHIFT_JIS),
+        (Some(_), _) => Charset::Encoding(encoding_rs::GB18030),
+        (None, Some(_)) => Charset::Encoding(encoding_rs::SHIFT_JIS),
+        (None, None) => Charset::Latin1,
+    }
+}
+
+/// 检查字节能否在不替换字符的情况下完整解码
+fn decodes_cleanly(encoding: &'static Encoding, bytes: &[u8]) -> bool {
+    encoding
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .is_some()
+}
+
+/// GB18030 字符分布得分，结构不合法时返回 None
+///
+/// GB2312 一二级汉字（B0~F7 区）得分最高，符号区次之，GBK 扩展区和四字节序列较少见
+fn score_gb18030(bytes: &[u8]) -> Option<i32> {
+    let mut score = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let lead = bytes[i];
+        if lead < 0x80 {
+            i += 1;
+            continue;
+        }
+        if !(0x81..=0xFE).contains(&lead) {
+            return None;
+        }
+        let trail = *bytes.get(i + 1)?;
+        match trail {
+            0x30..=0x39 => {
+                // 四字节序列
+                let third = *bytes.get(i + 2)?;
+                let fourth = *bytes.get(i + 3)?;
+                if !(0x81..=0xFE).contains(&third) || !(0x30..=0x39).contains(&fourth) {
+                    return None;
+                }
+                score -= 1;
+                i += 4;
+            }
+            0x40..=0x7E | 0x80..=0xFE => {
+                if (0xB0..=0xF7).contains(&lead) && trail >= 0xA1 {
+                    score += 2;
+                } else if (0xA1..=0xA9).contains(&lead) && trail >= 0xA1 {
+                    score += 1;
+                }
+                i += 2;
+            }
+            _ => return None,
+        }
+    }
+    Some(score)
+}
+
+/// Shift-JIS 字符分布得分，结构不合法时返回 None
+///
+/// 假名和第一水准汉字得分最高，半角片假名在实际文本中少见（多为 GBK 字节被误读）
+fn score_shift_jis(bytes: &[u8]) -> Option<i32> {
+    let mut score = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let lead = bytes[i];
+        match lead {
+            0x00..=0x7F => i += 1,
+            0xA1..=0xDF => {
+                score -= 1;
+                i += 1;
+            }
+            0x81..=0x9F | 0xE0..=0xFC => {
+                let trail = *bytes.get(i + 1)?;
+                if !matches!(trail, 0x40..=0x7E | 0x80..=0xFC) {
+                    return None;
+                }
+                score += match lead {
+                    0x82 | 0x83 | 0x88..=0x98 => 2,
+                    0x81 => 1,
+                    _ => 0,
+                };
+                i += 2;
+            }
+            _ => return None,
+        }
+    }
+    Some(score)
+}
+
+/// 解码二维码数据段
+///
+/// 数字/字母数字段按 ASCII，汉字模式段按 Shift-JIS / GB18030，
+/// 字节段按其 ECI 指示的字符集；无 ECI 的字节段合并后统一推断字符集
+pub fn decode_segments(segments: &[Segment]) -> DecodedText {
+    let unlabeled: Vec<u8> = segments
+        .iter()
+        .filter(|s| s.mode == SegmentMode::Byte && s.eci.and_then(Charset::from_eci).is_none())
+        .flat_map(|s| s.data.iter().copied())
+        .collect();
+    let detected = detect_charset(&unlabeled);
+
+    // 相邻且字符集相同的段合并解码，避免多字节字符被拆开
+    let mut runs: Vec<(Charset, Vec<u8>)> = Vec::new();
+    let mut reported: Option<Charset> = None;
+    for segment in segments {
+        let charset = match segment.mode {
+            SegmentMode::Numeric | SegmentMode::Alphanumeric => Charset::Latin1,
+            SegmentMode::Kanji => Charset::Encoding(encoding_rs::SHIFT_JIS),
+            SegmentMode::Hanzi => Charset::Encoding(encoding_rs::GB18030),
+            SegmentMode::Byte => segment.eci.and_then(Charset::from_eci).unwrap_or(detected),
+        };
+        if segment.mode != SegmentMode::Numeric && segment.mode != SegmentMode::Alphanumeric {
+            reported.get_or_insert(charset);
+        }
+        match runs.last_mut() {
+            Some((last, data)) if *last == charset => data.extend_from_slice(&segment.data),
+            _ => runs.push((charset, segment.data.clone())),
+        }
+    }
+
+    DecodedText {
+        text: runs.iter().map(|(charset, data)| charset.decode(data)).collect(),
+        charset: reported.map_or("US-ASCII", Charset::name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn byte_segment(data: &[u8], eci: Option<u32>) -> Segment {
+        Segment { mode: SegmentMode::Byte, data: data.to_vec(), eci }
+    }
+
+    fn encode(encoding: &'static Encoding, text: &str) -> Vec<u8> {
+        encoding.encode(text).0.into_owned()
+    }
+
+    #[test]
+    fn test_detect_utf8() {
+        let bytes = "你好，世界 hello".as_bytes();
+        assert_eq!(detect_charset(bytes).name(), "UTF-8");
+    }
+
+    #[test]
+    fn test_detect_gb18030() {
+        let bytes = encode(encoding_rs::GB18030, "微信支付收款码：张三");
+        assert_eq!(detect_charset(&bytes), Charset::Encoding(encoding_rs::GB18030));
+    }
+
+    #[test]
+    fn test_detect_shift_jis() {
+        let bytes = encode(encoding_rs::SHIFT_JIS, "東京駅から新宿へ、ありがとうございます");
+        assert_eq!(detect_charset(&bytes), Charset::Encoding(encoding_rs::SHIFT_JIS));
+    }
+
+    #[test]
+    fn test_detect_shift_jis_kanji_only() {
+        let bytes = encode(encoding_rs::SHIFT_JIS, "日本語");
+        assert_eq!(detect_charset(&bytes), Charset::Encoding(encoding_rs::SHIFT_JIS));
+    }
+
+    #[test]
+    fn test_detect_latin1_fallback() {
+        // 0x80 在 GB18030 和 Shift-JIS 中都不是合法的首字节
+        let bytes = b"caf\xE9 \x80";
+        assert_eq!(detect_charset(bytes), Charset::Latin1);
+        assert_eq!(Charset::Latin1.decode(b"caf\xE9"), "café");
+    }
+
+    #[test]
+    fn test_decode_honours_eci() {
+        // 同样的字节在 ECI 20 下按 Shift-JIS 解码，而不是推断为 GB18030
+        let bytes = encode(encoding_rs::SHIFT_JIS, "テスト");
+        let decoded = decode_segments(&[byte_segment(&bytes, Some(20))]);
+        assert_eq!(decoded.text, "テスト");
+        assert_eq!(decoded.charset, "Shift_JIS");
+
+        let decoded = decode_segments(&[byte_segment(b"\xC0\xE9", Some(3))]);
+        assert_eq!(decoded.text, "Àé");
+        assert_eq!(decoded.charset, "ISO-8859-1");
+    }
+
+    #[test]
+    fn test_decode_mixed_segments() {
+        let gbk = encode(encoding_rs::GB18030, "金额");
+        let segments = vec![
+            byte_segment(&gbk, None),
+            Segment { mode: SegmentMode::Numeric, data: b"100".to_vec(), eci: None },
+            Segment { mode: SegmentMode::Kanji, data: vec![0x93, 0x5F], eci: None },
+        ];
+        let decoded = decode_segments(&segments);
+        assert_eq!(decoded.text, "金额100点");
+        assert_eq!(decoded.charset, "gb18030");
+    }
+
+    #[test]
+    fn test_decode_utf8_split_across_segments() {
+        let bytes = "汉字".as_bytes();
+        let segments = vec![byte_segment(&bytes[..2], None), byte_segment(&bytes[2..], None)];
+        assert_eq!(decode_segments(&segments).text, "汉字");
+    }
+
+    #[test]
+    fn test_decode_ascii_only() {
+        let segments = vec![Segment { mode: SegmentMode::Alphanumeric, data: b"HTTP://A.B".to_vec(), eci: None }];
+        let decoded = decode_segments(&segments);
+        assert_eq!(decoded.text, "HTTP://A.B");
+        assert_eq!(decoded.charset, "US-ASCII");
+    }
+}
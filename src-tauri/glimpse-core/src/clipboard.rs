@@ -0,0 +1,56 @@
+//! 剪贴板图像读取
+//! 从系统剪贴板读取图像（如聊天软件中复制的截图），无需先显示在屏幕上即可识别
+
+use arboard::Clipboard;
+use image::{DynamicImage, RgbaImage};
+
+use crate::error::{Error, Result};
+
+/// 读取系统剪贴板中的图像
+pub fn read_clipboard_image() -> Result<DynamicImage> {
+    let mut clipboard = Clipboard::new().map_err(|e| Error::Clipboard(format!("打开剪贴板失败: {}", e)))?;
+    let data = clipboard.get_image().map_err(|e| match e {
+        arboard::Error::ContentNotAvailable => Error::Clipboard("剪贴板中没有图像".to_string()),
+        e => Error::Clipboard(format!("读取剪贴板图像失败: {}", e)),
+    })?;
+
+    image_from_rgba(data.width, data.height, data.bytes.into_owned())
+}
+
+/// 将剪贴板返回的 RGBA 像素数据转换为图像
+fn image_from_rgba(width: usize, height: usize, bytes: Vec<u8>) -> Result<DynamicImage> {
+    if width == 0 || height == 0 {
+        return Err(Error::Clipboard("剪贴板中的图像为空".to_string()));
+    }
+    let expected = width * height * 4;
+    let actual = bytes.len();
+    RgbaImage::from_raw(width as u32, height as u32, bytes)
+        .map(DynamicImage::ImageRgba8)
+        .ok_or_else(|| {
+            Error::Clipboard(format!(
+                "剪贴板图像数据长度不匹配: {}x{} 需要 {} 字节，实际 {} 字节",
+                width, height, expected, actual
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GenericImageView, Rgba};
+
+    #[test]
+    fn test_image_from_rgba() {
+        let bytes = vec![255, 0, 0, 255, 0, 0, 255, 255];
+        let image = image_from_rgba(2, 1, bytes).unwrap();
+        assert_eq!(image.dimensions(), (2, 1));
+        assert_eq!(image.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(image.get_pixel(1, 0), Rgba([0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn test_image_from_rgba_invalid() {
+        assert!(image_from_rgba(0, 0, Vec::new()).is_err());
+        assert!(image_from_rgba(2, 2, vec![0; 12]).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! 调试日志输出
+//! 桌面端输出到标准输出；命令行工具默认关闭（`--verbose` 时输出到标准错误），避免混入 JSON 结果
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 日志输出位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogTarget {
+    Stdout,
+    Stderr,
+    Off,
+}
+
+static TARGET: AtomicU8 = AtomicU8::new(0);
+
+/// 设置日志输出位置
+pub fn set_target(target: LogTarget) {
+    let value = match target {
+        LogTarget::Stdout => 0,
+        LogTarget::Stderr => 1,
+        LogTarget::Off => 2,
+    };
+    TARGET.store(value, Ordering::Relaxed);
+}
+
+/// 当前日志输出位置
+pub fn target() -> LogTarget {
+    match TARGET.load(Ordering::Relaxed) {
+        0 => LogTarget::Stdout,
+        1 => LogTarget::Stderr,
+        _ => LogTarget::Off,
+    }
+}
+
+/// 输出一行调试日志，用法同 `println!`
+#[macro_export]
+macro_rules! debug_println {
+    ($($arg:tt)*) => {
+        match $crate::debug_log::target() {
+            $crate::debug_log::LogTarget::Stdout => println!($($arg)*),
+            $crate::debug_log::LogTarget::Stderr => eprintln!($($arg)*),
+            $crate::debug_log::LogTarget::Off => {}
+        }
+    };
+}
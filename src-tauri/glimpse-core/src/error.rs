@@ -0,0 +1,54 @@
+//! 错误类型
+
+use std::fmt;
+
+/// glimpse-core 的错误类型，错误信息为中文，可直接展示给用户
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// 识别流水线配置无效
+    InvalidPipeline(String),
+    /// 屏幕截图失败
+    Capture(String),
+    /// 剪贴板不可用或其中没有图像
+    Clipboard(String),
+    /// 图像解码、编码或转换失败
+    Image(String),
+    /// OCR 引擎不可用或识别失败
+    Ocr(String),
+    /// 二维码生成失败（内容过长、参数无效等）
+    Encode(String),
+    /// 链接打开规则无效
+    InvalidRule(String),
+    /// 历史记录读写失败
+    History(String),
+    /// OCR 模型包清单无效或找不到模型包
+    ModelPack(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidPipeline(message) => write!(f, "识别流水线配置无效: {}", message),
+            Error::InvalidRule(message) => write!(f, "链接打开规则无效: {}", message),
+            Error::ModelPack(message) => write!(f, "OCR 模型包无效: {}", message),
+            Error::Capture(message)
+            | Error::Clipboard(message)
+            | Error::Image(message)
+            | Error::Ocr(message)
+            | Error::Encode(message)
+            | Error::History(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// 便于 Tauri 命令等以 `String` 作为错误类型的调用方直接使用 `?`
+impl From<Error> for String {
+    fn from(error: Error) -> Self {
+        error.to_string()
+    }
+}
+
+/// glimpse-core 的结果类型
+pub type Result<T> = std::result::Result<T, Error>;
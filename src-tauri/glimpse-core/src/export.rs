@@ -0,0 +1,61 @@
+//! 导出文本的格式化
+//! 历史记录与表格导出共用的 CSV、TSV 字段和 Markdown 表格单元格转义
+
+use std::borrow::Cow;
+
+/// CSV 字段：包含逗号、引号或换行时加引号，引号写两次；可能被当作公式的内容先加 ' 前缀
+pub fn csv_field(value: &str) -> String {
+    let value = neutralize_formula(value);
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// TSV 字段：制表符和换行改为空格；可能被当作公式的内容先加 ' 前缀
+pub fn tsv_field(value: &str) -> String {
+    neutralize_formula(value).replace(['\t', '\r', '\n'], " ")
+}
+
+/// Markdown 表格单元格：转义竖线，换行改为 <br>
+pub fn markdown_cell(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|").replace("\r\n", "<br>").replace('\n', "<br>")
+}
+
+/// 以 `=`、`+`、`-`、`@`、制表符或回车开头的单元格会被电子表格当作公式执行，加 ' 前缀作为纯文本
+fn neutralize_formula(value: &str) -> Cow<'_, str> {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        Cow::Owned(format!("'{}", value))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_neutralizes_formulas() {
+        assert_eq!(csv_field("=HYPERLINK(\"http://x\")"), "\"'=HYPERLINK(\"\"http://x\"\")\"");
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("-1"), "'-1");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("\tx"), "'\tx");
+        assert_eq!(csv_field("\rx"), "\"'\rx\"");
+        assert_eq!(csv_field("a=1"), "a=1");
+    }
+
+    #[test]
+    fn test_tsv_field() {
+        assert_eq!(tsv_field("a\tb\nc"), "a b c");
+        assert_eq!(tsv_field("=1+1"), "'=1+1");
+        assert_eq!(tsv_field("\tx"), "' x");
+    }
+
+    #[test]
+    fn test_markdown_cell() {
+        assert_eq!(markdown_cell("a|b\\c\r\nd\ne"), "a\\|b\\\\c<br>d<br>e");
+    }
+}
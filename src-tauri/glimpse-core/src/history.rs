@@ -0,0 +1,605 @@
+//! 识别历史记录
+//! 以追加写入的 JSON Lines 文件保存，每行一条操作（新增、置顶、删除），打开时按顺序重放；
+//! 清空或无效操作过多时重写文件压缩体积。支持按文本、类型、来源、日期查询，导出为 JSON、CSV、Markdown
+
+use base64::Engine;
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::export::{csv_field, markdown_cell};
+use crate::qr_scanner::{BarcodeFormat, Point, QrCodeResult, QrCodeType};
+use crate::screenshot::image_to_png_bytes;
+
+/// 最多保留的记录数，超出时删除最早的未置顶记录
+pub const MAX_ENTRIES: usize = 5000;
+/// 缩略图最大边长（像素）
+pub const THUMBNAIL_SIZE: u32 = 96;
+
+/// 文件中的操作数超过有效记录数的倍数时重写文件
+const COMPACT_RATIO: usize = 2;
+
+/// 识别来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistorySource {
+    FullScreen,
+    Region,
+    Ocr,
+    Clipboard,
+    File,
+    Watch,
+}
+
+impl HistorySource {
+    /// 导出时使用的中文名称
+    pub fn label(self) -> &'static str {
+        match self {
+            HistorySource::FullScreen => "全屏识别",
+            HistorySource::Region => "区域识别",
+            HistorySource::Ocr => "文字识别",
+            HistorySource::Clipboard => "剪贴板",
+            HistorySource::File => "图片文件",
+            HistorySource::Watch => "监视模式",
+        }
+    }
+}
+
+/// 一条历史记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,
+    /// 识别时间（Unix 毫秒）
+    pub timestamp: u64,
+    pub source: HistorySource,
+    pub content: String,
+    /// 内容类型（OCR 文字为 None）
+    pub qr_type: Option<QrCodeType>,
+    /// 码制（OCR 文字为 None）
+    pub format: Option<BarcodeFormat>,
+    /// 缩略图（PNG data URL）
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// 待写入的记录（编号和时间由 [`History::add`] 填写）
+#[derive(Debug, Clone)]
+pub struct NewEntry {
+    pub source: HistorySource,
+    pub content: String,
+    pub qr_type: Option<QrCodeType>,
+    pub format: Option<BarcodeFormat>,
+    pub thumbnail: Option<String>,
+}
+
+impl NewEntry {
+    /// 由二维码识别结果创建，`image` 为码所在的图像（用于截取缩略图）
+    pub fn from_code(source: HistorySource, result: &QrCodeResult, image: Option<&DynamicImage>) -> Self {
+        Self {
+            source,
+            content: result.content.clone(),
+            qr_type: Some(result.qr_type.clone()),
+            format: Some(result.format),
+            thumbnail: image.and_then(|image| thumbnail(image, &result.points)),
+        }
+    }
+
+    /// 由识别出的文字创建，缩略图为整张图像
+    pub fn from_text(source: HistorySource, text: String, image: Option<&DynamicImage>) -> Self {
+        Self {
+            source,
+            content: text,
+            qr_type: None,
+            format: None,
+            thumbnail: image.and_then(|image| thumbnail(image, &[])),
+        }
+    }
+}
+
+/// 查询条件，未指定的条件不参与过滤
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryQuery {
+    /// 内容包含的文本（不区分大小写）
+    pub text: Option<String>,
+    pub qr_type: Option<QrCodeType>,
+    pub source: Option<HistorySource>,
+    /// 起始时间（Unix 毫秒，含）
+    pub since: Option<u64>,
+    /// 截止时间（Unix 毫秒，不含）
+    pub until: Option<u64>,
+    pub pinned_only: bool,
+    /// 最多返回的条数
+    pub limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        let text_matches = match self.text.as_deref().map(str::trim) {
+            Some(text) if !text.is_empty() => entry.content.to_lowercase().contains(&text.to_lowercase()),
+            _ => true,
+        };
+        text_matches
+            && self.qr_type.as_ref().is_none_or(|t| entry.qr_type.as_ref() == Some(t))
+            && self.source.is_none_or(|s| entry.source == s)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp < until)
+            && (!self.pinned_only || entry.pinned)
+    }
+}
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+/// 文件中的一行操作
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum LogRecord {
+    Add(HistoryEntry),
+    Pin { id: u64, pinned: bool },
+    Delete { id: u64 },
+}
+
+/// 历史记录存储，记录按时间从早到晚排列
+pub struct History {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+    next_id: u64,
+    /// 文件中的操作数
+    records: usize,
+}
+
+impl History {
+    /// 打开历史记录文件，不存在时创建；无法解析的行（例如写入中断的最后一行）会被跳过
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::History(format!("创建历史记录目录失败: {}", e)))?;
+        }
+
+        let mut history = Self { path, entries: Vec::new(), next_id: 1, records: 0 };
+        if history.path.exists() {
+            let file = fs::File::open(&history.path)
+                .map_err(|e| Error::History(format!("读取历史记录失败: {}", e)))?;
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(|e| Error::History(format!("读取历史记录失败: {}", e)))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<LogRecord>(&line) {
+                    Ok(record) => history.replay(record),
+                    Err(e) => debug_println!("[DEBUG] 跳过无法解析的历史记录: {}", e),
+                }
+                history.records += 1;
+            }
+        }
+
+        debug_println!("[DEBUG] 已加载 {} 条历史记录", history.entries.len());
+        history.compact_if_needed()?;
+        Ok(history)
+    }
+
+    /// 历史记录文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 记录条数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 新增一条记录，超出 [`MAX_ENTRIES`] 时删除最早的未置顶记录
+    pub fn add(&mut self, entry: NewEntry) -> Result<HistoryEntry> {
+        let entry = HistoryEntry {
+            id: self.next_id,
+            timestamp: now_millis(),
+            source: entry.source,
+            content: entry.content,
+            qr_type: entry.qr_type,
+            format: entry.format,
+            thumbnail: entry.thumbnail,
+            pinned: false,
+        };
+        self.append(&LogRecord::Add(entry.clone()))?;
+        self.next_id += 1;
+        self.entries.push(entry.clone());
+
+        while self.entries.len() > MAX_ENTRIES {
+            let Some(oldest) = self.entries.iter().find(|e| !e.pinned).map(|e| e.id) else {
+                break;
+            };
+            self.delete(oldest)?;
+        }
+        Ok(entry)
+    }
+
+    /// 按条件查询，置顶的记录在前，其余按时间从新到旧排列
+    pub fn query(&self, query: &HistoryQuery) -> Vec<HistoryEntry> {
+        let mut entries: Vec<HistoryEntry> = self.entries.iter().rev().filter(|e| query.matches(e)).cloned().collect();
+        entries.sort_by_key(|e| !e.pinned);
+        if let Some(limit) = query.limit {
+            entries.truncate(limit);
+        }
+        entries
+    }
+
+    /// 置顶或取消置顶（置顶的记录不会因数量上限或清空被删除）
+    pub fn set_pinned(&mut self, id: u64, pinned: bool) -> Result<()> {
+        let index = self.index_of(id)?;
+        self.append(&LogRecord::Pin { id, pinned })?;
+        self.entries[index].pinned = pinned;
+        Ok(())
+    }
+
+    /// 删除一条记录
+    pub fn delete(&mut self, id: u64) -> Result<()> {
+        let index = self.index_of(id)?;
+        self.append(&LogRecord::Delete { id })?;
+        self.entries.remove(index);
+        self.compact_if_needed()
+    }
+
+    /// 清空历史记录，返回删除的条数；`keep_pinned` 为 true 时保留置顶的记录
+    pub fn clear(&mut self, keep_pinned: bool) -> Result<usize> {
+        let before = self.entries.len();
+        self.entries.retain(|e| keep_pinned && e.pinned);
+        self.compact()?;
+        Ok(before - self.entries.len())
+    }
+
+    /// 按条件导出
+    pub fn export(&self, query: &HistoryQuery, format: ExportFormat) -> Result<String> {
+        export(&self.query(query), format)
+    }
+
+    fn index_of(&self, id: u64) -> Result<usize> {
+        self.entries
+            .iter()
+            .position(|e| e.id == id)
+            .ok_or_else(|| Error::History(format!("历史记录 {} 不存在", id)))
+    }
+
+    /// 重放文件中的一条操作
+    fn replay(&mut self, record: LogRecord) {
+        match record {
+            LogRecord::Add(entry) => {
+                self.next_id = self.next_id.max(entry.id + 1);
+                self.entries.push(entry);
+            }
+            LogRecord::Pin { id, pinned } => {
+                if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+                    entry.pinned = pinned;
+                }
+            }
+            LogRecord::Delete { id } => self.entries.retain(|e| e.id != id),
+        }
+    }
+
+    /// 在文件末尾追加一条操作
+    fn append(&mut self, record: &LogRecord) -> Result<()> {
+        let line = serde_json::to_string(record).map_err(|e| Error::History(format!("序列化历史记录失败: {}", e)))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| Error::History(format!("写入历史记录失败: {}", e)))?;
+        writeln!(file, "{}", line).map_err(|e| Error::History(format!("写入历史记录失败: {}", e)))?;
+        self.records += 1;
+        Ok(())
+    }
+
+    fn compact_if_needed(&mut self) -> Result<()> {
+        if self.records > self.entries.len() * COMPACT_RATIO + 100 {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// 只保留有效记录重写文件（先写临时文件再替换，避免中断时丢失数据）
+    fn compact(&mut self) -> Result<()> {
+        let mut content = String::new();
+        for entry in &self.entries {
+            let record = LogRecord::Add(entry.clone());
+            let line = serde_json::to_string(&record).map_err(|e| Error::History(format!("序列化历史记录失败: {}", e)))?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+
+        let temp_path = self.path.with_extension("tmp");
+        fs::write(&temp_path, content).map_err(|e| Error::History(format!("写入历史记录失败: {}", e)))?;
+        fs::rename(&temp_path, &self.path).map_err(|e| Error::History(format!("替换历史记录文件失败: {}", e)))?;
+
+        debug_println!("[DEBUG] 历史记录已压缩: {} 条操作 -> {} 条", self.records, self.entries.len());
+        self.records = self.entries.len();
+        Ok(())
+    }
+}
+
+/// 将记录导出为指定格式的文本
+pub fn export(entries: &[HistoryEntry], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(entries).map_err(|e| Error::History(format!("导出 JSON 失败: {}", e)))
+        }
+        ExportFormat::Csv => {
+            let mut out = String::from("时间,来源,码制,类型,内容,置顶\n");
+            for entry in entries {
+                let row = export_row(entry).map(|field| csv_field(&field)).collect::<Vec<_>>().join(",");
+                out.push_str(&row);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+        ExportFormat::Markdown => {
+            let mut out = String::from("| 时间 | 来源 | 码制 | 类型 | 内容 | 置顶 |\n| --- | --- | --- | --- | --- | --- |\n");
+            for entry in entries {
+                let row = export_row(entry).map(|field| markdown_cell(&field)).collect::<Vec<_>>().join(" | ");
+                out.push_str(&format!("| {} |\n", row));
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// 导出表格中的一行（时间为 UTC）
+fn export_row(entry: &HistoryEntry) -> impl Iterator<Item = String> {
+    [
+        format_timestamp(entry.timestamp),
+        entry.source.label().to_string(),
+        entry.format.map(|f| format!("{:?}", f)).unwrap_or_default(),
+        entry.qr_type.as_ref().map(|t| format!("{:?}", t)).unwrap_or_default(),
+        entry.content.clone(),
+        if entry.pinned { "是" } else { "" }.to_string(),
+    ]
+    .into_iter()
+}
+
+/// 将 Unix 毫秒时间格式化为 `YYYY-MM-DD HH:MM:SS`（UTC）
+fn format_timestamp(millis: u64) -> String {
+    let seconds = millis / 1000;
+    let (days, time) = (seconds / 86_400, seconds % 86_400);
+
+    // 按公历从 1970-01-01 起的天数换算年月日（以 3 月为一年的开始，闰日在年末）
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// 当前时间（Unix 毫秒）
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+/// 截取缩略图：`points` 为码的角点（为空时使用整张图像），四周留出少量边距
+pub fn thumbnail(image: &DynamicImage, points: &[Point]) -> Option<String> {
+    let (width, height) = (image.width(), image.height());
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let region = if points.is_empty() {
+        image.clone()
+    } else {
+        let min_x = points.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+        let min_y = points.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        let max_x = points.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+        let max_y = points.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        let margin = ((max_x - min_x).max(max_y - min_y) * 0.1).max(4.0);
+
+        let left = (min_x - margin).max(0.0) as u32;
+        let top = (min_y - margin).max(0.0) as u32;
+        let right = ((max_x + margin).max(0.0) as u32).min(width);
+        let bottom = ((max_y + margin).max(0.0) as u32).min(height);
+        if left >= right || top >= bottom {
+            return None;
+        }
+        image.crop_imm(left, top, right - left, bottom - top)
+    };
+
+    let png = image_to_png_bytes(&region.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)).ok()?;
+    Some(format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每个测试使用独立的临时文件
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("glimpse-history-{}", std::process::id()));
+        let path = dir.join(format!("{}.jsonl", name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn text(source: HistorySource, content: &str) -> NewEntry {
+        NewEntry {
+            source,
+            content: content.to_string(),
+            qr_type: Some(QrCodeType::from_content(content)),
+            format: Some(BarcodeFormat::QrCode),
+            thumbnail: None,
+        }
+    }
+
+    #[test]
+    fn test_reopen_replays_operations() {
+        let path = temp_path("replay");
+        let mut history = History::open(&path).unwrap();
+        let first = history.add(text(HistorySource::FullScreen, "https://example.com")).unwrap();
+        let second = history.add(text(HistorySource::Region, "hello")).unwrap();
+        let third = history.add(text(HistorySource::Clipboard, "WIFI:S:office;P:secret;;")).unwrap();
+        history.set_pinned(first.id, true).unwrap();
+        history.delete(second.id).unwrap();
+        assert!(history.delete(second.id).is_err());
+
+        let reopened = History::open(&path).unwrap();
+        let entries = reopened.query(&HistoryQuery::default());
+        assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![first.id, third.id]);
+        assert!(entries[0].pinned);
+
+        // 编号不会与已删除的记录重复
+        let mut reopened = reopened;
+        assert_eq!(reopened.add(text(HistorySource::File, "new")).unwrap().id, third.id + 1);
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let mut history = History::open(temp_path("query")).unwrap();
+        for (source, content) in [
+            (HistorySource::FullScreen, "https://Example.com/a"),
+            (HistorySource::Region, "plain text"),
+            (HistorySource::FullScreen, "https://other.org"),
+        ] {
+            history.add(text(source, content)).unwrap();
+        }
+        history.add(NewEntry::from_text(HistorySource::Ocr, "识别出的文字".to_string(), None)).unwrap();
+        for (i, entry) in history.entries.iter_mut().enumerate() {
+            entry.timestamp = 1000 * (i as u64 + 1);
+        }
+
+        fn contents(history: &History, query: HistoryQuery) -> Vec<String> {
+            history.query(&query).into_iter().map(|e| e.content).collect()
+        }
+        assert_eq!(
+            contents(&history, HistoryQuery { text: Some("example".to_string()), ..Default::default() }),
+            vec!["https://Example.com/a"]
+        );
+        assert_eq!(
+            contents(&history, HistoryQuery { qr_type: Some(QrCodeType::Url), ..Default::default() }),
+            vec!["https://other.org", "https://Example.com/a"]
+        );
+        assert_eq!(
+            contents(&history, HistoryQuery { source: Some(HistorySource::Ocr), ..Default::default() }),
+            vec!["识别出的文字"]
+        );
+        assert_eq!(
+            contents(&history, HistoryQuery { since: Some(2000), until: Some(4000), ..Default::default() }),
+            vec!["https://other.org", "plain text"]
+        );
+        assert_eq!(contents(&history, HistoryQuery { limit: Some(1), ..Default::default() }), vec!["识别出的文字"]);
+
+        // 置顶的记录排在最前
+        let id = history.entries[0].id;
+        history.set_pinned(id, true).unwrap();
+        assert_eq!(history.query(&HistoryQuery::default())[0].id, id);
+        assert_eq!(contents(&history, HistoryQuery { pinned_only: true, ..Default::default() }), vec!["https://Example.com/a"]);
+    }
+
+    #[test]
+    fn test_clear_keeps_pinned_and_compacts() {
+        let path = temp_path("clear");
+        let mut history = History::open(&path).unwrap();
+        let pinned = history.add(text(HistorySource::FullScreen, "keep")).unwrap();
+        history.add(text(HistorySource::FullScreen, "drop")).unwrap();
+        history.set_pinned(pinned.id, true).unwrap();
+
+        assert_eq!(history.clear(true).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert_eq!(History::open(&path).unwrap().query(&HistoryQuery::default())[0].content, "keep");
+
+        assert_eq!(history.clear(false).unwrap(), 1);
+        assert!(History::open(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_truncated_line_is_skipped() {
+        let path = temp_path("truncated");
+        let mut history = History::open(&path).unwrap();
+        history.add(text(HistorySource::FullScreen, "intact")).unwrap();
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"op\":\"add\",\"id\":2,\"time").unwrap();
+
+        let reopened = History::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(reopened.query(&HistoryQuery::default())[0].content, "intact");
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let entry = HistoryEntry {
+            id: 1,
+            timestamp: 1_700_000_000_000,
+            source: HistorySource::Region,
+            content: "a,\"b\"|c\nd".to_string(),
+            qr_type: Some(QrCodeType::Text),
+            format: Some(BarcodeFormat::QrCode),
+            thumbnail: None,
+            pinned: true,
+        };
+
+        let csv = export(std::slice::from_ref(&entry), ExportFormat::Csv).unwrap();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "2023-11-14 22:13:20,区域识别,QrCode,Text,\"a,\"\"b\"\"|c"
+        );
+        assert!(csv.ends_with("d\",是\n"));
+
+        let markdown = export(std::slice::from_ref(&entry), ExportFormat::Markdown).unwrap();
+        assert_eq!(
+            markdown.lines().nth(2).unwrap(),
+            "| 2023-11-14 22:13:20 | 区域识别 | QrCode | Text | a,\"b\"\\|c<br>d | 是 |"
+        );
+
+        let json = export(std::slice::from_ref(&entry), ExportFormat::Json).unwrap();
+        let parsed: Vec<HistoryEntry> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, vec![entry]);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(951_782_400_000), "2000-02-29 00:00:00");
+        assert_eq!(format_timestamp(1_700_000_000_999), "2023-11-14 22:13:20");
+    }
+
+    #[test]
+    fn test_thumbnail_crops_code() {
+        use image::{Rgb, RgbImage};
+
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(800, 600, Rgb([255, 255, 255])));
+        let points = [Point { x: 100.0, y: 100.0 }, Point { x: 300.0, y: 100.0 }, Point { x: 300.0, y: 300.0 }];
+        let decode = |data_url: String| {
+            let base64 = data_url.strip_prefix("data:image/png;base64,").unwrap().to_string();
+            let png = base64::engine::general_purpose::STANDARD.decode(base64).unwrap();
+            image::load_from_memory(&png).unwrap()
+        };
+
+        let code = decode(thumbnail(&image, &points).unwrap());
+        assert_eq!((code.width(), code.height()), (THUMBNAIL_SIZE, THUMBNAIL_SIZE));
+        let whole = decode(thumbnail(&image, &[]).unwrap());
+        assert_eq!((whole.width(), whole.height()), (THUMBNAIL_SIZE, 72));
+
+        let outside = [Point { x: 900.0, y: 700.0 }, Point { x: 1000.0, y: 800.0 }];
+        assert!(thumbnail(&image, &outside).is_none());
+    }
+}
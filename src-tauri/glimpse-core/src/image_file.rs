@@ -0,0 +1,190 @@
+//! 图像文件读取
+//! 支持 PNG、JPEG、WebP、BMP、GIF、TIFF；动画 GIF 和多页 TIFF 拆分为多帧，逐帧识别
+
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, DynamicImage, GrayAlphaImage, GrayImage, ImageBuffer, ImageFormat, RgbImage, RgbaImage};
+use std::io::Cursor;
+use std::path::Path;
+use tiff::decoder::{Decoder as TiffDecoder, DecodingResult};
+use tiff::ColorType as TiffColorType;
+
+use crate::error::{Error, Result};
+
+/// 单个文件最多读取的帧数（避免超长动画占用大量内存和识别时间）
+pub const MAX_FRAMES: usize = 100;
+
+/// 支持打开的图像文件扩展名（用于文件选择对话框和拖放过滤）
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "bmp", "gif", "tif", "tiff"];
+
+/// 图像中的一帧
+#[derive(Debug, Clone)]
+pub struct ImageFrame {
+    /// 帧序号（从 0 开始，单帧图像为 0）
+    pub index: usize,
+    /// 帧图像（动画 GIF 为合成后的完整画面）
+    pub image: DynamicImage,
+}
+
+/// 读取图像文件的所有帧
+pub fn load_frames(path: &Path) -> Result<Vec<ImageFrame>> {
+    let bytes = std::fs::read(path).map_err(|e| Error::Image(format!("读取文件失败: {}", e)))?;
+    decode_frames(&bytes)
+}
+
+/// 从内存数据解码所有帧，格式按文件头自动识别
+pub fn decode_frames(bytes: &[u8]) -> Result<Vec<ImageFrame>> {
+    let format = image::guess_format(bytes).map_err(|e| Error::Image(format!("无法识别图像格式: {}", e)))?;
+
+    let images = match format {
+        ImageFormat::Gif => decode_gif(bytes)?,
+        ImageFormat::Tiff => decode_tiff(bytes)?,
+        _ => vec![image::load_from_memory_with_format(bytes, format)
+            .map_err(|e| Error::Image(format!("解码图像失败: {}", e)))?],
+    };
+
+    debug_println!("[DEBUG] 图像格式: {:?}，共 {} 帧", format, images.len());
+
+    Ok(images
+        .into_iter()
+        .enumerate()
+        .map(|(index, image)| ImageFrame { index, image })
+        .collect())
+}
+
+/// 解码 GIF 的所有帧（按画布合成，透明区域保留上一帧内容）
+fn decode_gif(bytes: &[u8]) -> Result<Vec<DynamicImage>> {
+    let decoder = GifDecoder::new(Cursor::new(bytes)).map_err(|e| Error::Image(format!("GIF 解码失败: {}", e)))?;
+    decoder
+        .into_frames()
+        .take(MAX_FRAMES)
+        .map(|frame| {
+            frame
+                .map(|frame| DynamicImage::ImageRgba8(frame.into_buffer()))
+                .map_err(|e| Error::Image(format!("GIF 帧解码失败: {}", e)))
+        })
+        .collect()
+}
+
+/// 解码 TIFF 的所有页
+fn decode_tiff(bytes: &[u8]) -> Result<Vec<DynamicImage>> {
+    let tiff_error = |e: tiff::TiffError| Error::Image(format!("TIFF 解码失败: {}", e));
+    let mut decoder = TiffDecoder::new(Cursor::new(bytes)).map_err(tiff_error)?;
+
+    let mut pages = Vec::new();
+    loop {
+        pages.push(decode_tiff_page(&mut decoder)?);
+        if pages.len() >= MAX_FRAMES || !decoder.more_images() {
+            break;
+        }
+        decoder.next_image().map_err(tiff_error)?;
+    }
+    Ok(pages)
+}
+
+/// 解码 TIFF 当前页，支持 8/16 位灰度、灰度+透明、RGB、RGBA
+fn decode_tiff_page(decoder: &mut TiffDecoder<Cursor<&[u8]>>) -> Result<DynamicImage> {
+    let tiff_error = |e: tiff::TiffError| Error::Image(format!("TIFF 解码失败: {}", e));
+    let (width, height) = decoder.dimensions().map_err(tiff_error)?;
+    let color = decoder.colortype().map_err(tiff_error)?;
+    let data = decoder.read_image().map_err(tiff_error)?;
+
+    let image = match (color, data) {
+        (TiffColorType::Gray(8), DecodingResult::U8(data)) => {
+            GrayImage::from_raw(width, height, data).map(DynamicImage::ImageLuma8)
+        }
+        (TiffColorType::GrayA(8), DecodingResult::U8(data)) => {
+            GrayAlphaImage::from_raw(width, height, data).map(DynamicImage::ImageLumaA8)
+        }
+        (TiffColorType::RGB(8), DecodingResult::U8(data)) => {
+            RgbImage::from_raw(width, height, data).map(DynamicImage::ImageRgb8)
+        }
+        (TiffColorType::RGBA(8), DecodingResult::U8(data)) => {
+            RgbaImage::from_raw(width, height, data).map(DynamicImage::ImageRgba8)
+        }
+        (TiffColorType::Gray(16), DecodingResult::U16(data)) => {
+            ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageLuma16)
+        }
+        (TiffColorType::RGB(16), DecodingResult::U16(data)) => {
+            ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageRgb16)
+        }
+        (TiffColorType::RGBA(16), DecodingResult::U16(data)) => {
+            ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageRgba16)
+        }
+        (color, _) => return Err(Error::Image(format!("不支持的 TIFF 颜色类型: {:?}", color))),
+    };
+
+    image.ok_or_else(|| Error::Image("TIFF 数据长度与图像尺寸不符".to_string()))
+}
+
+/// 根据扩展名判断是否为支持的图像文件
+pub fn is_supported_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SUPPORTED_EXTENSIONS.iter().any(|s| s.eq_ignore_ascii_case(ext)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Luma, Rgba};
+    use tiff::encoder::{colortype, TiffEncoder};
+
+    fn solid_rgba(value: u8) -> RgbaImage {
+        RgbaImage::from_pixel(8, 8, Rgba([value, value, value, 255]))
+    }
+
+    #[test]
+    fn test_decode_animated_gif() {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut bytes);
+            let frames = [0u8, 128, 255]
+                .map(|value| Frame::from_parts(solid_rgba(value), 0, 0, Delay::from_numer_denom_ms(100, 1)));
+            encoder.encode_frames(frames).unwrap();
+        }
+
+        let frames = decode_frames(&bytes).unwrap();
+        assert_eq!(frames.len(), 3);
+        for (i, (frame, value)) in frames.iter().zip([0u8, 128, 255]).enumerate() {
+            assert_eq!(frame.index, i);
+            assert_eq!(frame.image.to_luma8().get_pixel(4, 4), &Luma([value]));
+        }
+    }
+
+    #[test]
+    fn test_decode_multipage_tiff() {
+        let mut bytes = Cursor::new(Vec::new());
+        {
+            let mut encoder = TiffEncoder::new(&mut bytes).unwrap();
+            encoder.write_image::<colortype::Gray8>(4, 2, &[10; 8]).unwrap();
+            encoder.write_image::<colortype::RGB8>(2, 2, &[200; 12]).unwrap();
+        }
+
+        let frames = decode_frames(bytes.get_ref()).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[0].image.width(), frames[0].image.height()), (4, 2));
+        assert_eq!(frames[0].image.to_luma8().get_pixel(0, 0), &Luma([10]));
+        assert_eq!((frames[1].image.width(), frames[1].image.height()), (2, 2));
+        assert_eq!(frames[1].index, 1);
+    }
+
+    #[test]
+    fn test_decode_single_frame() {
+        let mut bytes = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(solid_rgba(50)).write_to(&mut bytes, ImageFormat::Png).unwrap();
+        let frames = decode_frames(bytes.get_ref()).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].index, 0);
+
+        assert!(decode_frames(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_is_supported_file() {
+        assert!(is_supported_file(Path::new("a/scan.TIFF")));
+        assert!(is_supported_file(Path::new("anim.gif")));
+        assert!(!is_supported_file(Path::new("notes.txt")));
+        assert!(!is_supported_file(Path::new("no_extension")));
+    }
+}
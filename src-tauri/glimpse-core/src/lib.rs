@@ -0,0 +1,73 @@
+//! 灵视 Glimpse 识别核心库
+//!
+//! 提供屏幕截图、二维码/条码识别和 OCR 文字识别，不依赖 Tauri，
+//! 桌面端、命令行工具和其他内部工具共用同一套识别逻辑。
+//!
+//! - 截图：[`capture_full_screen`]、[`capture_region`]；剪贴板图像：[`read_clipboard_image`]
+//! - 图像文件：[`load_frames`]，动画 GIF 和多页 TIFF 拆分为多帧
+//! - 识别：[`scan_qr_codes`]、[`scan_multiple_images`]、[`scan_frames`]，预处理策略由 [`ScanPipeline`] 配置
+//! - 文字识别：[`recognize_text`]，按阅读顺序重建段落，多帧图像用 [`recognize_frames`]；表格识别：[`recognize_table`]，导出 TSV/CSV/Markdown；
+//!   其他语言的模型从外部模型包加载，见 [`ocr::models`]
+//! - 生成：[`generate_qr`]，输出 PNG 和 SVG
+//! - 链接风险：[`analyze_url`]，识别结果中的链接自动分析钓鱼风险
+//! - 链接打开策略：[`OpenPolicy`]，按域名、协议、正则规则决定自动打开、确认或禁止
+//! - 历史记录：[`History`]，保存识别结果，可按文本、类型、日期查询、置顶，导出 JSON/CSV/Markdown
+//! - 监视模式：[`Watcher`]，定时截取屏幕或区域，画面变化时识别并只报告新出现的码
+//!
+//! ```no_run
+//! use glimpse_core::{scan_qr_codes, ScanPipeline};
+//!
+//! let image = glimpse_core::image::open("screenshot.png")?;
+//! let report = scan_qr_codes(&image, &ScanPipeline::default())?;
+//! for code in &report.results {
+//!     println!("{:?}: {}", code.format, code.content);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! 调试日志默认输出到标准输出，可通过 [`debug_log::set_target`] 重定向或关闭。
+
+#[macro_use]
+pub mod debug_log;
+pub mod charset;
+pub mod clipboard;
+pub mod error;
+pub mod export;
+pub mod history;
+pub mod image_file;
+pub mod ocr;
+pub mod open_policy;
+pub mod payload;
+pub mod preprocess;
+pub mod qr_generator;
+pub mod qr_scanner;
+pub mod qr_symbol;
+pub mod scan_pipeline;
+pub mod screenshot;
+pub mod structured_append;
+pub mod url_risk;
+pub mod watch;
+
+/// 重新导出 image 库，调用方无需单独依赖相同版本
+pub use image;
+
+pub use clipboard::read_clipboard_image;
+pub use error::{Error, Result};
+pub use history::{ExportFormat, History, HistoryEntry, HistoryQuery, HistorySource, NewEntry};
+pub use image_file::{decode_frames, load_frames, ImageFrame};
+pub use ocr::models::ModelPack;
+pub use ocr::table::{Table, TableFormat};
+pub use ocr::{recognize_frames, recognize_table, recognize_text, FrameText, OcrLine, OcrResult};
+pub use open_policy::{CompiledPolicy, OpenAction, OpenDecision, OpenPolicy, OpenRule, RulePattern};
+pub use payload::QrPayload;
+pub use qr_generator::{generate_qr, GeneratedQr, QrGenerateOptions};
+pub use qr_scanner::{
+    scan_frames, scan_multiple_images, scan_qr_codes, BarcodeFormat, Point, PreprocessMethod, QrCodeResult, QrCodeType,
+    QrSymbolInfo, ScanReport, ScreenOrigin, SymbolLocation,
+};
+pub use qr_symbol::EccLevel;
+pub use scan_pipeline::{ScanDiagnostics, ScanPipeline, ScanStrategy, StrategyDiagnostics};
+pub use screenshot::{capture_full_screen, capture_region, image_to_png_bytes, ScreenCapture};
+pub use structured_append::StructuredAppendInfo;
+pub use url_risk::{analyze_url, RiskLevel, UrlRisk};
+pub use watch::{WatchOptions, WatchTarget, Watcher};
@@ -0,0 +1,532 @@
+//! OCR 文字识别模块
+//! 使用 paddle-ocr-rs (基于 ONNX Runtime 的 PaddleOCR)
+//! 内置中文模型嵌入到二进制中，支持离线使用；其他语言可从外部模型包加载（见 [`models`]）
+//! Windows OCR API 作为备选方案
+
+use image::DynamicImage;
+use paddle_ocr_rs::ocr_lite::OcrLite;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::error::Error;
+use crate::image_file::ImageFrame;
+use crate::qr_scanner::Point;
+
+pub mod layout;
+pub mod models;
+pub mod table;
+
+use models::ModelPack;
+
+/// OCR 识别结果
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OcrResult {
+    /// 识别出的文本（按阅读顺序重建段落）
+    pub text: String,
+    /// 识别的语言/引擎
+    pub language: String,
+    /// 文本行（按阅读顺序），用于在原图上叠加可选中的文字
+    #[serde(default)]
+    pub lines: Vec<OcrLine>,
+}
+
+/// 一行文字（一个文本检测框）
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OcrLine {
+    pub text: String,
+    /// 文本框的四个角点（左上、右上、右下、左下），原图像素坐标
+    pub points: Vec<Point>,
+    /// 识别置信度（0~1，引擎不提供时为 None）
+    pub confidence: Option<f32>,
+    /// 文字方向（度，0 为正向，180 为倒置）
+    pub angle: f32,
+}
+
+/// 单帧的文字识别结果
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FrameText {
+    /// 帧序号（单帧图像为 None）
+    pub frame: Option<usize>,
+    #[serde(flatten)]
+    pub result: OcrResult,
+}
+
+/// 已加载的 OCR 引擎及其模型包
+struct LoadedEngine {
+    pack: ModelPack,
+    engine: OcrLite,
+}
+
+/// 全局 OCR 引擎（懒加载），记录为哪个模型包加载；加载失败时为 None，切换模型包前不再重试
+static OCR_ENGINE: Mutex<Option<(String, Option<LoadedEngine>)>> = Mutex::new(None);
+
+/// 加载模型包，失败时回退到内置模型包
+fn load_engine(id: &str) -> Option<LoadedEngine> {
+    match init_engine(id) {
+        Ok(engine) => Some(engine),
+        Err(e) if id != models::BUILTIN_PACK => {
+            debug_println!("[WARN] 加载模型包 {} 失败: {}，回退到内置模型包", id, e);
+            init_engine(models::BUILTIN_PACK)
+                .map_err(|e| debug_println!("[ERROR] {}", e))
+                .ok()
+        }
+        Err(e) => {
+            debug_println!("[ERROR] {}", e);
+            None
+        }
+    }
+}
+
+/// 初始化指定模型包的 PaddleOCR 引擎
+fn init_engine(id: &str) -> Result<LoadedEngine, String> {
+    debug_println!("[DEBUG] 正在初始化 PaddleOCR (ONNX) 引擎，模型包: {}", id);
+
+    // 确保模型已解压（内置模型）或存在（外部模型包）
+    let files = models::resolve(id)?;
+    let path = |path: &Path| path.to_str().map(str::to_string).ok_or_else(|| format!("模型路径包含无效字符: {}", path.display()));
+    let (det, cls, rec) = (path(files.det.as_path())?, path(files.cls.as_path())?, path(files.rec.as_path())?);
+
+    // 创建 OCR 引擎并初始化模型（线程数 2）
+    let mut engine = OcrLite::new();
+    match &files.dict {
+        Some(dict) => engine.init_models_with_dict(&det, &cls, &rec, &path(dict.as_path())?, 2),
+        None => engine.init_models(&det, &cls, &rec, 2),
+    }
+    .map_err(|e| format!("PaddleOCR 引擎初始化失败: {:?}", e))?;
+
+    debug_println!("[DEBUG] PaddleOCR 引擎初始化成功: {}", files.pack.name);
+    Ok(LoadedEngine { pack: files.pack, engine })
+}
+
+/// 使用 PaddleOCR 识别图像中的文字
+pub fn recognize_text(image: &DynamicImage) -> Result<OcrResult, Error> {
+    debug_println!("[DEBUG] 开始 OCR 识别，图像尺寸: {}x{}", image.width(), image.height());
+
+    // 尝试使用 PaddleOCR（选中的模型包变化时重新加载）
+    let selected = models::selected_pack();
+    let mut slot = OCR_ENGINE.lock().map_err(|e| Error::Ocr(format!("锁定 OCR 引擎失败: {}", e)))?;
+    if slot.as_ref().map(|(id, _)| id) != Some(&selected) {
+        *slot = Some((selected.clone(), load_engine(&selected)));
+    }
+    if let Some((_, Some(loaded))) = slot.as_mut() {
+        match recognize_with_paddle_ocr(&mut loaded.engine, &loaded.pack, image) {
+            Ok(result) if !result.text.trim().is_empty() => {
+                debug_println!("[DEBUG] PaddleOCR 识别成功，文本长度: {}", result.text.len());
+                return Ok(result);
+            }
+            Ok(_) => {
+                debug_println!("[DEBUG] PaddleOCR 未识别到文字，尝试 Windows OCR");
+            }
+            Err(e) => {
+                debug_println!("[DEBUG] PaddleOCR 识别失败: {}，尝试 Windows OCR", e);
+            }
+        }
+    } else {
+        debug_println!("[DEBUG] PaddleOCR 引擎不可用，使用 Windows OCR");
+    }
+    drop(slot);
+
+    // 回退到 Windows OCR
+    #[cfg(windows)]
+    {
+        recognize_with_windows_ocr(image).map_err(Error::Ocr)
+    }
+
+    #[cfg(not(windows))]
+    {
+        Err(Error::Ocr("OCR 引擎初始化失败".to_string()))
+    }
+}
+
+/// 识别图像中的表格：OCR 文本框结合图像中的表格线推断行列
+pub fn recognize_table(image: &DynamicImage) -> Result<table::Table, Error> {
+    let result = recognize_text(image)?;
+    let rulings = table::detect_rulings(image);
+    debug_println!("[DEBUG] 表格线: {} 条横线，{} 条竖线", rulings.horizontal.len(), rulings.vertical.len());
+
+    let table = table::reconstruct(&result.lines, &rulings);
+    debug_println!("[DEBUG] 表格识别完成: {} 行 x {} 列", table.rows.len(), table.column_count());
+    Ok(table)
+}
+
+/// 逐帧识别文字（动画 GIF、多页 TIFF），跳过空白帧和与上一帧文本相同的帧
+///
+/// 单帧识别失败时跳过该帧；所有帧都识别失败时返回最后一个错误
+pub fn recognize_frames(frames: &[ImageFrame]) -> Result<Vec<FrameText>, Error> {
+    collect_frames(frames.len() > 1, frames.iter().map(|frame| (frame.index, recognize_text(&frame.image))))
+}
+
+/// 将各帧文本合并为一个结果，帧之间以空行分隔
+pub fn join_frames(texts: Vec<FrameText>) -> OcrResult {
+    let language = texts.first().map(|text| text.result.language.clone()).unwrap_or_default();
+    let mut paragraphs = Vec::with_capacity(texts.len());
+    let mut lines = Vec::new();
+    for text in texts {
+        paragraphs.push(text.result.text.trim().to_string());
+        lines.extend(text.result.lines);
+    }
+    OcrResult { text: paragraphs.join("\n\n"), language, lines }
+}
+
+fn collect_frames(
+    multi_frame: bool,
+    results: impl Iterator<Item = (usize, Result<OcrResult, Error>)>,
+) -> Result<Vec<FrameText>, Error> {
+    let mut texts: Vec<FrameText> = Vec::new();
+    let mut recognized = false;
+    let mut last_error = None;
+
+    for (index, result) in results {
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                debug_println!("[DEBUG] 第 {} 帧 OCR 识别失败: {}", index, e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+        recognized = true;
+        let text = result.text.trim();
+        let duplicate = texts.last().is_some_and(|last| last.result.text.trim() == text);
+        if !text.is_empty() && !duplicate {
+            texts.push(FrameText { frame: multi_frame.then_some(index), result });
+        }
+    }
+
+    match last_error {
+        Some(e) if !recognized => Err(e),
+        _ => Ok(texts),
+    }
+}
+
+/// 使用 PaddleOCR 识别
+fn recognize_with_paddle_ocr(engine: &mut OcrLite, pack: &ModelPack, image: &DynamicImage) -> Result<OcrResult, String> {
+    let img_width = image.width();
+    let img_height = image.height();
+    let img_area = img_width * img_height;
+
+    debug_println!("[DEBUG] PaddleOCR 原始图像: {}x{} (面积: {} 像素)", img_width, img_height, img_area);
+
+    // 对小图像进行放大处理，提高检测率（文本框坐标需换算回原图）
+    let processed_image = if img_area < 20000 || img_width < 100 || img_height < 50 {
+        // 计算放大倍数，确保最小边至少 200 像素
+        let scale = if img_width < img_height {
+            (200.0 / img_width as f32).clamp(2.0, 4.0)
+        } else {
+            (200.0 / img_height as f32).clamp(2.0, 4.0)
+        };
+
+        let new_width = (img_width as f32 * scale) as u32;
+        let new_height = (img_height as f32 * scale) as u32;
+
+        debug_println!("[DEBUG] 小图像放大 {:.1}x: {}x{} -> {}x{}", scale, img_width, img_height, new_width, new_height);
+
+        image.resize(new_width, new_height, image::imageops::FilterType::Lanczos3)
+    } else {
+        image.clone()
+    };
+
+    // 转换为 RGB8 格式
+    let rgb_image = processed_image.to_rgb8();
+    let processed_area = rgb_image.width() * rgb_image.height();
+
+    // 根据处理后的图像大小动态调整参数
+    // 统一使用较低的阈值以提高检测率
+    let (padding, max_side_len, box_score_thresh, box_thresh, unclip_ratio) = if processed_area < 30000 {
+        // 小区域：极低阈值
+        debug_println!("[DEBUG] 使用极低阈值参数");
+        (100, 960, 0.05_f32, 0.05_f32, 2.5_f32)
+    } else if processed_area < 80000 {
+        // 中小区域：低阈值
+        debug_println!("[DEBUG] 使用低阈值参数");
+        (80, 960, 0.1_f32, 0.08_f32, 2.0_f32)
+    } else {
+        // 正常区域：标准阈值（也适当降低）
+        debug_println!("[DEBUG] 使用标准阈值参数");
+        (50, 1024, 0.3_f32, 0.2_f32, 1.6_f32)
+    };
+
+    // 调用识别
+    let result = engine.detect(
+        &rgb_image,
+        padding,
+        max_side_len,
+        box_score_thresh,
+        box_thresh,
+        unclip_ratio,
+        true,  // do_angle
+        true,  // most_angle
+    ).map_err(|e| format!("PaddleOCR 识别错误: {:?}", e))?;
+
+    debug_println!("[DEBUG] PaddleOCR 检测到 {} 个文本块", result.text_blocks.len());
+
+    // 放大后的坐标换算回原图
+    let scale = (
+        rgb_image.width() as f32 / img_width as f32,
+        rgb_image.height() as f32 / img_height as f32,
+    );
+    let lines: Vec<OcrLine> = result.text_blocks
+        .iter()
+        .map(|block| {
+            debug_println!("[DEBUG] 文本块: '{}' (置信度 {:.2})", block.text, block.text_score);
+            OcrLine {
+                text: block.text.clone(),
+                points: block.box_points
+                    .iter()
+                    .map(|p| to_original(p.x as f32, p.y as f32, scale, (img_width, img_height)))
+                    .collect(),
+                confidence: Some(block.text_score),
+                // 方向分类器只区分正向（0）和倒置（1）
+                angle: if block.angle_index == 1 { 180.0 } else { 0.0 },
+            }
+        })
+        .collect();
+
+    // 检测顺序在多栏、聊天截图等版面下是乱序的，按版面分析的阅读顺序重排
+    let layout = layout::analyze(&lines);
+    debug_println!("[DEBUG] 版面分析: {:?}，{} 个段落", layout.direction, layout.paragraphs.len());
+    let text = layout.text();
+    let lines = layout.reading_order().into_iter().map(|i| lines[i].clone()).collect();
+
+    // 外部模型包在引擎名后注明模型包名称
+    let language = if pack.is_builtin() { "PaddleOCR".to_string() } else { format!("PaddleOCR · {}", pack.name) };
+
+    Ok(OcrResult { text, language, lines })
+}
+
+/// 将放大后图像中的坐标换算回原图，并限制在原图范围内
+fn to_original(x: f32, y: f32, scale: (f32, f32), size: (u32, u32)) -> Point {
+    Point {
+        x: (x / scale.0).clamp(0.0, size.0 as f32),
+        y: (y / scale.1).clamp(0.0, size.1 as f32),
+    }
+}
+
+// ============ Windows OCR 备选方案 ============
+
+#[cfg(windows)]
+use windows::{
+    core::HSTRING,
+    Graphics::Imaging::BitmapDecoder,
+    Media::Ocr::{OcrEngine, OcrResult as WindowsOcrResult},
+    Storage::Streams::{DataWriter, InMemoryRandomAccessStream},
+    Globalization::Language,
+};
+
+/// 使用 Windows OCR API 识别
+#[cfg(windows)]
+fn recognize_with_windows_ocr(image: &DynamicImage) -> Result<OcrResult, String> {
+    debug_println!("[DEBUG] 使用 Windows OCR 识别");
+
+    // 将图像转换为 PNG 字节
+    let png_bytes = image_to_png_bytes(image)?;
+
+    // 调用 Windows OCR
+    let mut result = recognize_from_bytes(&png_bytes)?;
+
+    // 后处理
+    result.text = postprocess_text(&result.text);
+    for line in &mut result.lines {
+        line.text = postprocess_text(&line.text);
+    }
+
+    Ok(result)
+}
+
+/// 将图像转换为 PNG 字节
+#[cfg(windows)]
+fn image_to_png_bytes(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut cursor = std::io::Cursor::new(&mut bytes);
+    image
+        .write_to(&mut cursor, image::ImageFormat::Png)
+        .map_err(|e| format!("图像编码失败: {}", e))?;
+    Ok(bytes)
+}
+
+/// 从 PNG 字节数据识别文字
+#[cfg(windows)]
+fn recognize_from_bytes(png_bytes: &[u8]) -> Result<OcrResult, String> {
+    let stream = InMemoryRandomAccessStream::new()
+        .map_err(|e| format!("创建内存流失败: {}", e))?;
+
+    let writer = DataWriter::CreateDataWriter(&stream)
+        .map_err(|e| format!("创建数据写入器失败: {}", e))?;
+
+    writer.WriteBytes(png_bytes)
+        .map_err(|e| format!("写入图像数据失败: {}", e))?;
+
+    writer.StoreAsync()
+        .map_err(|e| format!("存储数据失败: {}", e))?
+        .get()
+        .map_err(|e| format!("等待存储完成失败: {}", e))?;
+
+    writer.FlushAsync()
+        .map_err(|e| format!("刷新缓冲区失败: {}", e))?
+        .get()
+        .map_err(|e| format!("等待刷新完成失败: {}", e))?;
+
+    stream.Seek(0)
+        .map_err(|e| format!("重置流位置失败: {}", e))?;
+
+    let decoder = BitmapDecoder::CreateAsync(&stream)
+        .map_err(|e| format!("创建解码器失败: {}", e))?
+        .get()
+        .map_err(|e| format!("等待解码器创建失败: {}", e))?;
+
+    let bitmap = decoder.GetSoftwareBitmapAsync()
+        .map_err(|e| format!("获取位图失败: {}", e))?
+        .get()
+        .map_err(|e| format!("等待位图获取失败: {}", e))?;
+
+    // 尝试中文引擎
+    if let Ok(lang) = Language::CreateLanguage(&HSTRING::from("zh-Hans-CN")) {
+        if OcrEngine::IsLanguageSupported(&lang).unwrap_or(false) {
+            if let Ok(engine) = OcrEngine::TryCreateFromLanguage(&lang) {
+                if let Ok(result) = engine.RecognizeAsync(&bitmap) {
+                    if let Ok(result) = result.get() {
+                        if let Ok(result) = convert_windows_result(&result) {
+                            if !result.text.trim().is_empty() {
+                                return Ok(result);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // 尝试默认引擎
+    if let Ok(engine) = OcrEngine::TryCreateFromUserProfileLanguages() {
+        if let Ok(result) = engine.RecognizeAsync(&bitmap) {
+            if let Ok(result) = result.get() {
+                if let Ok(result) = convert_windows_result(&result) {
+                    return Ok(result);
+                }
+            }
+        }
+    }
+
+    Ok(OcrResult {
+        text: String::new(),
+        language: "none".to_string(),
+        lines: Vec::new(),
+    })
+}
+
+/// 转换 Windows OCR 结果：行的文本框取各单词外接矩形的并集（Windows OCR 不提供置信度）
+#[cfg(windows)]
+fn convert_windows_result(result: &WindowsOcrResult) -> windows::core::Result<OcrResult> {
+    let angle = result.TextAngle().and_then(|angle| angle.Value()).unwrap_or(0.0) as f32;
+
+    let mut lines = Vec::new();
+    for line in result.Lines()? {
+        let mut bounds: Option<(f32, f32, f32, f32)> = None;
+        for word in line.Words()? {
+            let rect = word.BoundingRect()?;
+            let (right, bottom) = (rect.X + rect.Width, rect.Y + rect.Height);
+            bounds = Some(match bounds {
+                Some((l, t, r, b)) => (l.min(rect.X), t.min(rect.Y), r.max(right), b.max(bottom)),
+                None => (rect.X, rect.Y, right, bottom),
+            });
+        }
+        let points = bounds
+            .map(|(l, t, r, b)| {
+                vec![Point { x: l, y: t }, Point { x: r, y: t }, Point { x: r, y: b }, Point { x: l, y: b }]
+            })
+            .unwrap_or_default();
+        lines.push(OcrLine { text: line.Text()?.to_string(), points, confidence: None, angle });
+    }
+
+    Ok(OcrResult {
+        text: result.Text()?.to_string(),
+        language: "Windows OCR".to_string(),
+        lines,
+    })
+}
+
+/// 文本后处理：清理中文字符间的多余空格
+#[cfg(windows)]
+fn postprocess_text(text: &str) -> String {
+    let mut result = String::new();
+    let chars: Vec<char> = text.chars().collect();
+
+    for i in 0..chars.len() {
+        let current = chars[i];
+
+        if current == ' ' {
+            let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+            let next = if i + 1 < chars.len() { Some(chars[i + 1]) } else { None };
+
+            let prev_is_cjk = prev.map(is_cjk_char).unwrap_or(false);
+            let next_is_cjk = next.map(is_cjk_char).unwrap_or(false);
+
+            if prev_is_cjk && next_is_cjk {
+                continue;
+            }
+        }
+
+        result.push(current);
+    }
+
+    result
+}
+
+/// 检查字符是否是 CJK 字符
+#[cfg(windows)]
+fn is_cjk_char(c: char) -> bool {
+    let code = c as u32;
+    (0x4E00..=0x9FFF).contains(&code)
+        || (0x3400..=0x4DBF).contains(&code)
+        || (0x3000..=0x303F).contains(&code)
+        || (0xFF00..=0xFFEF).contains(&code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_original_undoes_upscale() {
+        // 100x40 的小图放大 4 倍为 400x160
+        let point = to_original(200.0, 60.0, (4.0, 4.0), (100, 40));
+        assert_eq!(point, Point { x: 50.0, y: 15.0 });
+
+        // 超出原图的角点（检测框外扩）限制在图像范围内
+        let point = to_original(-8.0, 200.0, (4.0, 4.0), (100, 40));
+        assert_eq!(point, Point { x: 0.0, y: 40.0 });
+
+        // 未放大的图像坐标不变
+        assert_eq!(to_original(123.0, 45.0, (1.0, 1.0), (800, 600)), Point { x: 123.0, y: 45.0 });
+    }
+
+    fn text(text: &str) -> Result<OcrResult, Error> {
+        Ok(OcrResult { text: text.to_string(), language: "PaddleOCR".to_string(), lines: Vec::new() })
+    }
+
+    #[test]
+    fn test_collect_frames_skips_blank_and_repeated_text() {
+        let results = vec![
+            (0, text("第一页")),
+            (1, text("第一页\n")),
+            (2, text("  ")),
+            (3, Err(Error::Ocr("失败".to_string()))),
+            (4, text("第二页")),
+            (5, text("第一页")),
+        ];
+        let texts = collect_frames(true, results.into_iter()).unwrap();
+        let frames: Vec<Option<usize>> = texts.iter().map(|t| t.frame).collect();
+        assert_eq!(frames, vec![Some(0), Some(4), Some(5)]);
+        assert_eq!(join_frames(texts).text, "第一页\n\n第二页\n\n第一页");
+
+        // 单帧图像不标记帧序号；全部失败时返回错误
+        assert_eq!(collect_frames(false, vec![(0, text("a"))].into_iter()).unwrap()[0].frame, None);
+        assert!(collect_frames(false, vec![(0, Err(Error::Ocr("失败".to_string())))].into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_result_without_lines_deserializes() {
+        let result: OcrResult = serde_json::from_str(r#"{"text":"hi","language":"PaddleOCR"}"#).unwrap();
+        assert!(result.lines.is_empty());
+    }
+}
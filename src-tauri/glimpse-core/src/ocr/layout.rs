@@ -0,0 +1,619 @@
+//! OCR 版面分析
+//! 将文本框聚类为行、段落和分栏，按自然阅读顺序重建文本：
+//! 合并折行（包括连字符断词）、保留缩进，支持从右到左书写（希伯来文、阿拉伯文）
+//! 和竖排中日韩文字（列内从上到下，列间从右到左）
+
+use serde::{Deserialize, Serialize};
+
+use super::OcrLine;
+
+/// 栏间空白的最小宽度（行高的倍数）
+const COLUMN_GAP: f32 = 1.5;
+/// 段间（区块间）空白的最小高度（行高的倍数）
+const SECTION_GAP: f32 = 0.8;
+/// 两个文本框在垂直于行的方向上重叠超过该比例时视为同一行
+const SAME_LINE_OVERLAP: f32 = 0.5;
+/// 行尾距区块右边界不超过该宽度（半角字符数）时视为自动折行
+const WRAP_SLACK: f32 = 6.0;
+/// 整块文本的偏移超过该宽度（半角字符数）时视为独立排布（如右对齐的聊天气泡），不计入缩进
+const MAX_INDENT: usize = 16;
+
+/// 文字书写方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextDirection {
+    /// 横排，从左到右
+    LeftToRight,
+    /// 横排，从右到左（希伯来文、阿拉伯文）
+    RightToLeft,
+    /// 竖排，列内从上到下，列间从右到左
+    Vertical,
+}
+
+/// 段落
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Paragraph {
+    /// 段落文本（自动折行已合并）
+    pub text: String,
+    /// 首行缩进（半角字符数）
+    pub indent: usize,
+    /// 组成段落的文本行在输入中的下标（阅读顺序）
+    pub lines: Vec<usize>,
+    /// 所在区块（分栏、聊天气泡等）的序号
+    pub region: usize,
+    /// 是否为新区块的第一段（输出文本时与上一段之间空一行）
+    pub separated: bool,
+}
+
+/// 版面分析结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Layout {
+    pub direction: TextDirection,
+    /// 按阅读顺序排列的段落
+    pub paragraphs: Vec<Paragraph>,
+}
+
+impl Layout {
+    /// 重建后的文本：段落之间换行，区块之间空一行，首行缩进用空格表示
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for (i, paragraph) in self.paragraphs.iter().enumerate() {
+            if i > 0 {
+                text.push_str(if paragraph.separated { "\n\n" } else { "\n" });
+            }
+            text.push_str(&" ".repeat(paragraph.indent));
+            text.push_str(&paragraph.text);
+        }
+        text
+    }
+
+    /// 所有文本行在输入中的下标（阅读顺序）
+    pub fn reading_order(&self) -> Vec<usize> {
+        self.paragraphs.iter().flat_map(|p| p.lines.iter().copied()).collect()
+    }
+}
+
+/// 自动判断书写方向后进行版面分析
+pub fn analyze(lines: &[OcrLine]) -> Layout {
+    analyze_with(lines, detect_direction(lines))
+}
+
+/// 按指定书写方向进行版面分析
+pub fn analyze_with(lines: &[OcrLine], direction: TextDirection) -> Layout {
+    let blocks: Vec<Block> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Block::new(index, line, direction))
+        .collect();
+    let metrics = Metrics::new(&blocks);
+
+    let mut regions = Vec::new();
+    if !blocks.is_empty() {
+        let all: Vec<&Block> = blocks.iter().collect();
+        let origin = bounds(all.iter().map(|b| b.along)).start;
+        segment(all, origin, &metrics, &mut regions);
+    }
+
+    let mut paragraphs = Vec::new();
+    for (index, region) in regions.into_iter().enumerate() {
+        build_paragraphs(region, index, &metrics, &mut paragraphs);
+    }
+
+    // 没有坐标的文本行无法定位，按原顺序放在最后
+    let mut region = paragraphs.last().map_or(0, |p| p.region + 1);
+    for (index, line) in lines.iter().enumerate() {
+        if line.points.is_empty() && !line.text.trim().is_empty() {
+            paragraphs.push(Paragraph {
+                text: line.text.trim().to_string(),
+                indent: 0,
+                lines: vec![index],
+                region,
+                separated: region > 0,
+            });
+            region += 1;
+        }
+    }
+
+    Layout { direction, paragraphs }
+}
+
+/// 根据文字和文本框形状判断书写方向：
+/// 多数含两个以上全角字符的文本框为竖长条时视为竖排，多数字母为希伯来文/阿拉伯文时视为从右到左
+pub fn detect_direction(lines: &[OcrLine]) -> TextDirection {
+    let (mut letters, mut rtl) = (0usize, 0usize);
+    let (mut wide_lines, mut tall) = (0usize, 0usize);
+
+    for line in lines {
+        for c in line.text.chars().filter(|c| c.is_alphabetic()) {
+            letters += 1;
+            rtl += usize::from(is_rtl(c));
+        }
+
+        let xs = line.points.iter().map(|p| p.x);
+        let ys = line.points.iter().map(|p| p.y);
+        if line.text.chars().filter(|&c| is_wide(c)).count() >= 2 && !line.points.is_empty() {
+            wide_lines += 1;
+            tall += usize::from(bounds_of(ys).extent() > bounds_of(xs).extent() * 1.5);
+        }
+    }
+
+    if wide_lines > 0 && tall * 2 > wide_lines {
+        TextDirection::Vertical
+    } else if letters > 0 && rtl * 2 > letters {
+        TextDirection::RightToLeft
+    } else {
+        TextDirection::LeftToRight
+    }
+}
+
+/// 一维区间（表格识别也使用）
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Span {
+    pub(super) start: f32,
+    pub(super) end: f32,
+}
+
+impl Span {
+    pub(super) fn extent(self) -> f32 {
+        self.end - self.start
+    }
+
+    pub(super) fn center(self) -> f32 {
+        (self.start + self.end) / 2.0
+    }
+
+    pub(super) fn overlap(self, other: Span) -> f32 {
+        (self.end.min(other.end) - self.start.max(other.start)).max(0.0)
+    }
+
+    /// 坐标取反（用于从右到左的方向）
+    fn flipped(self) -> Span {
+        Span { start: -self.end, end: -self.start }
+    }
+}
+
+pub(super) fn bounds_of(values: impl Iterator<Item = f32>) -> Span {
+    values.fold(Span { start: f32::MAX, end: f32::MIN }, |span, v| Span {
+        start: span.start.min(v),
+        end: span.end.max(v),
+    })
+}
+
+fn bounds(spans: impl Iterator<Item = Span>) -> Span {
+    spans.fold(Span { start: f32::MAX, end: f32::MIN }, |a, b| Span {
+        start: a.start.min(b.start),
+        end: a.end.max(b.end),
+    })
+}
+
+/// 统一到阅读坐标系的文本框：along 为行内文字前进方向，cross 为换行方向
+///
+/// 从右到左时 along 取 -x；竖排时 along 为 y、cross 取 -x（列从右到左排列），
+/// 之后的聚类和排序都不再区分书写方向
+struct Block<'a> {
+    index: usize,
+    text: &'a str,
+    along: Span,
+    cross: Span,
+}
+
+impl<'a> Block<'a> {
+    fn new(index: usize, line: &'a OcrLine, direction: TextDirection) -> Option<Self> {
+        let text = line.text.trim();
+        if text.is_empty() || line.points.is_empty() {
+            return None;
+        }
+
+        let x = bounds_of(line.points.iter().map(|p| p.x));
+        let y = bounds_of(line.points.iter().map(|p| p.y));
+        let (along, cross) = match direction {
+            TextDirection::LeftToRight => (x, y),
+            TextDirection::RightToLeft => (x.flipped(), y),
+            TextDirection::Vertical => (y, x.flipped()),
+        };
+        Some(Block { index, text, along, cross })
+    }
+}
+
+/// 版面的基本尺寸
+struct Metrics {
+    /// 行高（文本框在换行方向上的中位数）
+    line_height: f32,
+    /// 半角字符宽度（全角字符按两个半角计）
+    char_width: f32,
+}
+
+impl Metrics {
+    fn new(blocks: &[Block]) -> Self {
+        let line_height = median(blocks.iter().map(|b| b.cross.extent())).unwrap_or(1.0).max(1.0);
+        let char_width = median(blocks.iter().map(|b| b.along.extent() / text_units(b.text) as f32))
+            .unwrap_or(line_height / 2.0)
+            .max(0.5);
+        Self { line_height, char_width }
+    }
+
+    /// 长度折合的半角字符数
+    fn columns(&self, length: f32) -> usize {
+        (length / self.char_width).round().max(0.0) as usize
+    }
+}
+
+pub(super) fn median(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let mut values: Vec<f32> = values.filter(|v| v.is_finite()).collect();
+    values.sort_by(f32::total_cmp);
+    values.get(values.len() / 2).copied()
+}
+
+/// 一个区块及其缩进基准
+struct Region<'a> {
+    blocks: Vec<&'a Block<'a>>,
+    origin: f32,
+}
+
+/// 递归切分区块（XY-cut）：优先按栏间空白分栏，其次按段间空白分段，结果按阅读顺序追加到 `regions`
+///
+/// 分段得到的子区块沿用上级的缩进基准，这样代码中被空行隔开的代码块仍保留缩进
+fn segment<'a>(blocks: Vec<&'a Block<'a>>, origin: f32, metrics: &Metrics, regions: &mut Vec<Region<'a>>) {
+    if let Some((before, after)) = split_columns(&blocks, metrics) {
+        for side in [before, after] {
+            let origin = bounds(side.iter().map(|b| b.along)).start;
+            segment(side, origin, metrics, regions);
+        }
+        return;
+    }
+
+    let sections = split_sections(&blocks, metrics);
+    if sections.len() > 1 {
+        for section in sections {
+            segment(section, origin, metrics, regions);
+        }
+        return;
+    }
+
+    regions.push(Region { blocks, origin });
+}
+
+/// 区间投影中不小于 `min_gap` 的空白，返回空白的中点（升序）
+pub(super) fn gaps(spans: impl Iterator<Item = Span>, min_gap: f32) -> Vec<f32> {
+    let mut spans: Vec<Span> = spans.collect();
+    spans.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut cuts = Vec::new();
+    let mut end = f32::MIN;
+    for span in spans {
+        if end > f32::MIN && span.start - end >= min_gap {
+            cuts.push((span.start + end) / 2.0);
+        }
+        end = end.max(span.end);
+    }
+    cuts
+}
+
+/// 在栏间空白处分为两栏
+///
+/// 只有两侧各有两个以上文本框、且较少一侧至少一半的文本框与另一侧并排（处于同一高度）时才分栏，
+/// 避免把左右交替出现的聊天气泡或单行的“标签  值”当作分栏
+fn split_columns<'a>(blocks: &[&'a Block<'a>], metrics: &Metrics) -> Option<(Vec<&'a Block<'a>>, Vec<&'a Block<'a>>)> {
+    gaps(blocks.iter().map(|b| b.along), COLUMN_GAP * metrics.line_height)
+        .into_iter()
+        .map(|cut| blocks.iter().partition::<Vec<_>, _>(|b| b.along.center() < cut))
+        .find(|(before, after)| side_by_side(before, after))
+}
+
+fn side_by_side(before: &[&Block], after: &[&Block]) -> bool {
+    let (smaller, other) = if before.len() <= after.len() { (before, after) } else { (after, before) };
+    if smaller.len() < 2 {
+        return false;
+    }
+    let aligned = smaller.iter().filter(|a| other.iter().any(|b| same_line(a, b))).count();
+    aligned * 2 >= smaller.len()
+}
+
+fn same_line(a: &Block, b: &Block) -> bool {
+    a.cross.overlap(b.cross) >= SAME_LINE_OVERLAP * a.cross.extent().min(b.cross.extent())
+}
+
+/// 在段间空白处分段
+fn split_sections<'a>(blocks: &[&'a Block<'a>], metrics: &Metrics) -> Vec<Vec<&'a Block<'a>>> {
+    let cuts = gaps(blocks.iter().map(|b| b.cross), SECTION_GAP * metrics.line_height);
+    let mut sections = vec![Vec::new(); cuts.len() + 1];
+    for block in blocks {
+        let section = cuts.iter().filter(|&&cut| block.cross.center() > cut).count();
+        sections[section].push(*block);
+    }
+    sections
+}
+
+/// 视觉上的一行（可能由多个文本框组成）
+struct VisualLine {
+    text: String,
+    along: Span,
+    indices: Vec<usize>,
+}
+
+/// 将区块内的文本框聚类为行，行按换行方向排序，行内文本框按文字前进方向拼接
+fn group_lines(mut blocks: Vec<&Block>, metrics: &Metrics) -> Vec<VisualLine> {
+    blocks.sort_by(|a, b| a.cross.center().total_cmp(&b.cross.center()));
+
+    let mut rows: Vec<Vec<&Block>> = Vec::new();
+    for block in blocks {
+        match rows.last_mut() {
+            Some(row) if row.iter().any(|b| same_line(b, block)) => row.push(block),
+            _ => rows.push(vec![block]),
+        }
+    }
+
+    rows.into_iter()
+        .map(|mut row| {
+            row.sort_by(|a, b| a.along.start.total_cmp(&b.along.start));
+            let mut text = String::new();
+            let mut end = f32::MIN;
+            for block in &row {
+                if !text.is_empty() {
+                    text.push_str(&segment_separator(&text, block.text, block.along.start - end, metrics));
+                }
+                text.push_str(block.text);
+                end = end.max(block.along.end);
+            }
+            VisualLine {
+                text,
+                along: bounds(row.iter().map(|b| b.along)),
+                indices: row.iter().map(|b| b.index).collect(),
+            }
+        })
+        .collect()
+}
+
+/// 同一行内两个文本框之间的分隔：较宽的空白按宽度保留空格（对齐的注释、表格），
+/// 否则西文之间加一个空格，中日韩文字之间不加
+fn segment_separator(previous: &str, next: &str, gap: f32, metrics: &Metrics) -> String {
+    let spaces = metrics.columns(gap);
+    if spaces >= 2 {
+        " ".repeat(spaces)
+    } else if joins_without_space(previous, next) {
+        String::new()
+    } else {
+        " ".to_string()
+    }
+}
+
+pub(super) fn joins_without_space(previous: &str, next: &str) -> bool {
+    previous.chars().next_back().is_some_and(is_wide) || next.chars().next().is_some_and(is_wide)
+}
+
+/// 将区块内的行合并为段落
+///
+/// 上一行写满（行尾接近区块边界）、下一行缩进不增加且上一行不像代码行结尾时视为自动折行，合并到同一段
+fn build_paragraphs(region: Region, index: usize, metrics: &Metrics, paragraphs: &mut Vec<Paragraph>) {
+    let lines = group_lines(region.blocks, metrics);
+    let span = bounds(lines.iter().map(|l| l.along));
+    let origin = if metrics.columns(span.start - region.origin) > MAX_INDENT { span.start } else { region.origin };
+
+    let mut current: Option<(Paragraph, &VisualLine, usize)> = None;
+    for line in &lines {
+        let indent = metrics.columns(line.along.start - origin);
+        match &mut current {
+            Some((paragraph, previous, previous_indent))
+                if previous.along.end >= span.end - WRAP_SLACK * metrics.char_width
+                    && indent <= *previous_indent
+                    && !ends_code_line(&previous.text, &line.text) =>
+            {
+                join_wrapped(&mut paragraph.text, &line.text);
+                paragraph.lines.extend(&line.indices);
+                *previous = line;
+                *previous_indent = indent;
+            }
+            _ => {
+                let separated = index > 0 && current.is_none();
+                if let Some((paragraph, _, _)) = current.take() {
+                    paragraphs.push(paragraph);
+                }
+                let paragraph = Paragraph {
+                    text: line.text.clone(),
+                    indent,
+                    lines: line.indices.clone(),
+                    region: index,
+                    separated,
+                };
+                current = Some((paragraph, line, indent));
+            }
+        }
+    }
+    if let Some((paragraph, _, _)) = current {
+        paragraphs.push(paragraph);
+    }
+}
+
+/// 代码行常以分号、花括号结尾，即使写满也不与下一行合并
+fn ends_code_line(previous: &str, next: &str) -> bool {
+    previous.ends_with([';', '{', '}']) || next.starts_with('}')
+}
+
+/// 合并自动折行：西文单词被连字符断开时去掉连字符直接拼接
+fn join_wrapped(text: &mut String, next: &str) {
+    let mut tail = text.chars().rev();
+    let hyphenated = tail.next() == Some('-')
+        && tail.next().is_some_and(char::is_alphabetic)
+        && next.chars().next().is_some_and(char::is_lowercase);
+
+    if hyphenated {
+        text.pop();
+    } else if !joins_without_space(text, next) {
+        text.push(' ');
+    }
+    text.push_str(next);
+}
+
+/// 文本折合的半角字符数
+fn text_units(text: &str) -> usize {
+    text.chars().map(|c| if is_wide(c) { 2 } else { 1 }).sum::<usize>().max(1)
+}
+
+/// 全角字符（中日韩文字、假名、谚文及全角标点）
+fn is_wide(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{115F}'
+        | '\u{2E80}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}')
+}
+
+/// 从右到左书写的字母（希伯来文、阿拉伯文）
+fn is_rtl(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{05FF}'
+        | '\u{0600}'..='\u{06FF}'
+        | '\u{0750}'..='\u{077F}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_scanner::Point;
+
+    /// 轴对齐的文本框
+    fn line(text: &str, x: f32, y: f32, width: f32, height: f32) -> OcrLine {
+        OcrLine {
+            text: text.to_string(),
+            points: vec![
+                Point { x, y },
+                Point { x: x + width, y },
+                Point { x: x + width, y: y + height },
+                Point { x, y: y + height },
+            ],
+            confidence: Some(0.9),
+            angle: 0.0,
+        }
+    }
+
+    /// 西文文本框：每个字符宽 10 像素，行高 20 像素，第 `row` 行（行距 30 像素）
+    fn latin(text: &str, column: usize, row: usize) -> OcrLine {
+        line(text, column as f32 * 10.0, row as f32 * 30.0, text.chars().count() as f32 * 10.0, 20.0)
+    }
+
+    /// 按给定顺序打乱输入，检查结果与输入顺序无关
+    fn shuffled(lines: &[OcrLine]) -> Vec<OcrLine> {
+        let mut shuffled: Vec<OcrLine> = lines.iter().rev().cloned().collect();
+        shuffled.rotate_left(lines.len() / 3);
+        shuffled
+    }
+
+    fn assert_golden(lines: &[OcrLine], expected: &str) {
+        assert_eq!(analyze(lines).text(), expected);
+        assert_eq!(analyze(&shuffled(lines)).text(), expected);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(analyze(&[]).text(), "");
+    }
+
+    #[test]
+    fn test_wrapped_paragraphs_and_hyphenation() {
+        let lines = [
+            latin("Reading order matters for every", 4, 0),
+            latin("screenshot that contains several lin-", 0, 1),
+            latin("es of wrapped text.", 0, 2),
+            latin("A second paragraph starts with an", 4, 3),
+            latin("indent and ends here.", 0, 4),
+        ];
+        assert_golden(
+            &lines,
+            "    Reading order matters for every screenshot that contains several lines of wrapped text.\n\
+             \x20   A second paragraph starts with an indent and ends here.",
+        );
+    }
+
+    #[test]
+    fn test_two_columns_with_title() {
+        let lines = [
+            latin("Quarterly Report", 20, 0),
+            latin("Left column starts", 0, 2),
+            latin("here and goes on.", 0, 3),
+            latin("Right column text", 30, 2),
+            latin("follows after it.", 30, 3),
+        ];
+        assert_golden(
+            &lines,
+            "Quarterly Report\n\nLeft column starts here and goes on.\n\nRight column text follows after it.",
+        );
+    }
+
+    #[test]
+    fn test_chat_bubbles_keep_chronological_order() {
+        let lines = [
+            line("在吗？", 20.0, 0.0, 60.0, 20.0),
+            line("在的，什么事", 400.0, 50.0, 120.0, 20.0),
+            line("明天的会议改到下午三点", 20.0, 100.0, 220.0, 20.0),
+            line("好的，收到", 400.0, 150.0, 100.0, 20.0),
+        ];
+        assert_golden(&lines, "在吗？\n\n在的，什么事\n\n明天的会议改到下午三点\n\n好的，收到");
+    }
+
+    #[test]
+    fn test_code_indentation_is_preserved() {
+        let lines = [
+            latin("fn main() {", 0, 0),
+            latin("let items = vec![1, 2, 3];", 4, 1),
+            latin("for item in items {", 4, 2),
+            latin("println!(\"{}\", item);", 8, 3),
+            latin("}", 4, 4),
+            latin("}", 0, 5),
+        ];
+        assert_golden(
+            &lines,
+            "fn main() {\n    let items = vec![1, 2, 3];\n    for item in items {\n        println!(\"{}\", item);\n    }\n}",
+        );
+    }
+
+    #[test]
+    fn test_segments_on_one_line() {
+        let lines = [
+            latin("let total = 0;", 0, 0),
+            latin("// running sum", 20, 0),
+            line("你好", 0.0, 60.0, 40.0, 20.0),
+            line("世界", 42.0, 60.0, 40.0, 20.0),
+        ];
+        let layout = analyze(&lines);
+        assert_eq!(layout.text(), "let total = 0;      // running sum\n\n你好世界");
+        assert_eq!(layout.reading_order(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_right_to_left() {
+        let lines = [
+            line("שלום", 300.0, 0.0, 40.0, 20.0),
+            line("עולם", 200.0, 0.0, 40.0, 20.0),
+            line("מה שלומך", 260.0, 60.0, 80.0, 20.0),
+        ];
+        let layout = analyze(&lines);
+        assert_eq!(layout.direction, TextDirection::RightToLeft);
+        assert_eq!(layout.text(), "שלום      עולם\n\nמה שלומך");
+    }
+
+    #[test]
+    fn test_vertical_columns_read_right_to_left() {
+        // 每个字 20x20 像素，前两列写满后折行，第四列缩进两字开始新段落
+        let lines = [
+            line("今天天气很好", 100.0, 0.0, 20.0, 120.0),
+            line("我们一起去公", 70.0, 0.0, 20.0, 120.0),
+            line("园散步吧", 40.0, 0.0, 20.0, 80.0),
+            line("明天见", 10.0, 40.0, 20.0, 60.0),
+        ];
+        let layout = analyze(&lines);
+        assert_eq!(layout.direction, TextDirection::Vertical);
+        assert_eq!(layout.text(), "今天天气很好我们一起去公园散步吧\n    明天见");
+        assert_eq!(analyze(&shuffled(&lines)).text(), layout.text());
+    }
+
+    #[test]
+    fn test_lines_without_points_are_appended() {
+        let mut lines = vec![latin("located", 0, 0)];
+        lines.push(OcrLine { text: "floating".to_string(), points: Vec::new(), confidence: None, angle: 0.0 });
+        assert_eq!(analyze(&lines).text(), "located\n\nfloating");
+    }
+}
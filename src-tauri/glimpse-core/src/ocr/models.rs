@@ -0,0 +1,564 @@
+//! OCR 模型包
+//! 模型包是包含清单文件 `pack.json` 和 ONNX 模型的目录，清单描述文本检测（det）、
+//! 方向分类（cls）、文字识别（rec）模型和字符字典。用户配置的模型目录本身或其每个子目录
+//! 都可以是一个模型包；嵌入二进制的中文模型包作为默认值，外部模型包加载失败时也回退到它
+//!
+//! ```json
+//! {
+//!     "id": "latin-v3",
+//!     "name": "拉丁字母 (PP-OCRv3)",
+//!     "languages": ["en", "fr", "de"],
+//!     "rec": "latin_PP-OCRv3_rec_infer.onnx",
+//!     "dict": "latin_dict.txt"
+//! }
+//! ```
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+
+/// 模型包清单文件名
+pub const MANIFEST_FILE: &str = "pack.json";
+/// 内置中文模型包的 ID
+pub const BUILTIN_PACK: &str = "builtin-ch";
+
+/// 嵌入的模型文件
+/// 编译时会将模型文件打包到二进制中
+static MODEL_DET: EmbeddedModel =
+    EmbeddedModel::new("ch_PP-OCRv4_det_infer.onnx", include_bytes!("../../../models/ch_PP-OCRv4_det_infer.onnx"));
+static MODEL_CLS: EmbeddedModel = EmbeddedModel::new(
+    "ch_ppocr_mobile_v2.0_cls_infer.onnx",
+    include_bytes!("../../../models/ch_ppocr_mobile_v2.0_cls_infer.onnx"),
+);
+static MODEL_REC: EmbeddedModel =
+    EmbeddedModel::new("ch_PP-OCRv4_rec_infer.onnx", include_bytes!("../../../models/ch_PP-OCRv4_rec_infer.onnx"));
+
+/// 嵌入的模型及其内容哈希（首次校验缓存时计算，之后复用）
+struct EmbeddedModel {
+    name: &'static str,
+    data: &'static [u8],
+    digest: OnceLock<String>,
+}
+
+impl EmbeddedModel {
+    const fn new(name: &'static str, data: &'static [u8]) -> Self {
+        Self { name, data, digest: OnceLock::new() }
+    }
+
+    fn digest(&self) -> &str {
+        self.digest.get_or_init(|| sha256(self.data))
+    }
+}
+
+/// 模型目录和选中的模型包（空字符串表示内置模型包）
+struct Config {
+    dir: Option<PathBuf>,
+    selected: String,
+}
+
+static CONFIG: Mutex<Config> = Mutex::new(Config { dir: None, selected: String::new() });
+
+/// 内置模型的缓存根目录，每个版本解压到独立的子目录，不同版本同时运行也互不覆盖
+static CACHE_ROOT: Mutex<Option<PathBuf>> = Mutex::new(None);
+const CACHE_VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
+/// 旧版本缓存每个进程只清理一次
+static CLEANUP: Once = Once::new();
+
+/// 解压时的临时文件后缀，文件名中带进程号和序号，多个实例、线程互不冲突
+const TEMP_SUFFIX: &str = ".partial";
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+/// 超过该时间的临时文件视为中断解压的残留
+const STALE_TEMP_AGE: Duration = Duration::from_secs(3600);
+
+/// 模型包清单，文件路径相对于模型包目录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// 唯一标识，切换模型包时使用
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 适用的语言（如 "en"、"ja"、"ko"），仅用于展示
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// 文本检测模型，省略时使用内置模型（检测模型基本与语言无关）
+    #[serde(default)]
+    pub det: Option<String>,
+    /// 方向分类模型，省略时使用内置模型
+    #[serde(default)]
+    pub cls: Option<String>,
+    /// 文字识别模型
+    pub rec: String,
+    /// 字符字典（每行一个字符），省略时使用识别模型中内嵌的字典
+    #[serde(default)]
+    pub dict: Option<String>,
+}
+
+impl Manifest {
+    /// 读取并校验模型包目录中的清单
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| Error::ModelPack(format!("读取 {} 失败: {}", path.display(), e)))?;
+        let manifest: Manifest = serde_json::from_str(&content)
+            .map_err(|e| Error::ModelPack(format!("{} 格式错误: {}", path.display(), e)))?;
+        manifest.validate(dir)?;
+        Ok(manifest)
+    }
+
+    fn validate(&self, dir: &Path) -> Result<()> {
+        if self.id.trim().is_empty() || self.id == BUILTIN_PACK {
+            return Err(Error::ModelPack(format!("{} 中的 ID 为空或与内置模型包重复", dir.display())));
+        }
+        let files = [Some(&self.rec), self.det.as_ref(), self.cls.as_ref(), self.dict.as_ref()];
+        for file in files.into_iter().flatten() {
+            if !dir.join(file).is_file() {
+                return Err(Error::ModelPack(format!("模型包 {} 缺少文件: {}", self.id, file)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 可供选择的模型包
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPack {
+    pub id: String,
+    pub name: String,
+    pub languages: Vec<String>,
+    /// 模型包目录，内置模型包为 None
+    pub path: Option<PathBuf>,
+}
+
+impl ModelPack {
+    /// 内置中文模型包
+    pub fn builtin() -> Self {
+        ModelPack {
+            id: BUILTIN_PACK.to_string(),
+            name: "内置中文 (PP-OCRv4)".to_string(),
+            languages: vec!["zh".to_string(), "en".to_string()],
+            path: None,
+        }
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        self.path.is_none()
+    }
+}
+
+/// 加载引擎所需的模型文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFiles {
+    pub pack: ModelPack,
+    pub det: PathBuf,
+    pub cls: PathBuf,
+    pub rec: PathBuf,
+    pub dict: Option<PathBuf>,
+}
+
+/// 扫描模型目录：目录本身或其子目录中包含清单的都是模型包，无效或 ID 重复的模型包跳过
+pub fn discover(dir: &Path) -> Vec<ModelPack> {
+    let mut candidates = vec![dir.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(dir) {
+        let mut children: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+        children.sort();
+        candidates.extend(children);
+    }
+
+    let mut packs: Vec<ModelPack> = Vec::new();
+    for candidate in candidates {
+        if !candidate.join(MANIFEST_FILE).is_file() {
+            continue;
+        }
+        match Manifest::load(&candidate) {
+            Ok(manifest) if packs.iter().any(|p| p.id == manifest.id) => {
+                debug_println!("[WARN] 跳过 ID 重复的模型包: {} ({})", manifest.id, candidate.display());
+            }
+            Ok(manifest) => packs.push(ModelPack {
+                id: manifest.id,
+                name: manifest.name,
+                languages: manifest.languages,
+                path: Some(candidate),
+            }),
+            Err(e) => debug_println!("[WARN] 跳过无效的模型包: {}", e),
+        }
+    }
+    packs
+}
+
+/// 设置外部模型目录（None 表示只使用内置模型包）
+pub fn set_models_dir(dir: Option<PathBuf>) {
+    if let Ok(mut config) = CONFIG.lock() {
+        debug_println!("[DEBUG] OCR 模型目录: {:?}", dir);
+        config.dir = dir;
+    }
+}
+
+/// 当前的外部模型目录
+pub fn models_dir() -> Option<PathBuf> {
+    CONFIG.lock().ok().and_then(|config| config.dir.clone())
+}
+
+/// 所有可用的模型包，内置模型包在最前
+pub fn list_packs() -> Vec<ModelPack> {
+    let mut packs = vec![ModelPack::builtin()];
+    if let Some(dir) = models_dir() {
+        packs.extend(discover(&dir));
+    }
+    packs
+}
+
+/// 切换模型包，下次识别时加载
+pub fn select_pack(id: &str) -> Result<()> {
+    if !list_packs().iter().any(|p| p.id == id) {
+        return Err(Error::ModelPack(format!("未找到模型包: {}", id)));
+    }
+    let mut config = CONFIG.lock().map_err(|e| Error::ModelPack(format!("锁定模型配置失败: {}", e)))?;
+    debug_println!("[DEBUG] 切换 OCR 模型包: {}", id);
+    config.selected = if id == BUILTIN_PACK { String::new() } else { id.to_string() };
+    Ok(())
+}
+
+/// 当前选中的模型包 ID
+pub fn selected_pack() -> String {
+    CONFIG
+        .lock()
+        .ok()
+        .map(|config| config.selected.clone())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| BUILTIN_PACK.to_string())
+}
+
+/// 解析模型包的模型文件；清单省略的 det/cls 以及内置模型包使用内置模型（按需解压到缓存目录）
+pub fn resolve(id: &str) -> Result<ModelFiles> {
+    if id == BUILTIN_PACK {
+        return Ok(ModelFiles {
+            pack: ModelPack::builtin(),
+            det: builtin_det()?,
+            cls: builtin_cls()?,
+            rec: extract_model(&MODEL_REC)?,
+            dict: None,
+        });
+    }
+
+    let dir = models_dir().ok_or_else(|| Error::ModelPack("未设置 OCR 模型目录".to_string()))?;
+    let pack = discover(&dir)
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| Error::ModelPack(format!("未找到模型包: {}", id)))?;
+    let pack_dir = pack.path.clone().unwrap_or_default();
+    let manifest = Manifest::load(&pack_dir)?;
+
+    Ok(ModelFiles {
+        det: manifest.det.map(|file| Ok(pack_dir.join(file))).unwrap_or_else(builtin_det)?,
+        cls: manifest.cls.map(|file| Ok(pack_dir.join(file))).unwrap_or_else(builtin_cls)?,
+        rec: pack_dir.join(&manifest.rec),
+        dict: manifest.dict.map(|file| pack_dir.join(file)),
+        pack,
+    })
+}
+
+fn builtin_det() -> Result<PathBuf> {
+    extract_model(&MODEL_DET)
+}
+
+fn builtin_cls() -> Result<PathBuf> {
+    extract_model(&MODEL_CLS)
+}
+
+/// 设置内置模型的缓存根目录（桌面端使用应用数据目录下的 `ocr_models`）
+pub fn set_cache_dir(dir: PathBuf) {
+    if let Ok(mut root) = CACHE_ROOT.lock() {
+        debug_println!("[DEBUG] OCR 模型缓存目录: {}", dir.display());
+        *root = Some(dir);
+    }
+}
+
+/// 模型缓存根目录：未设置时使用系统本地数据目录，都不可用时使用临时目录
+fn cache_root() -> PathBuf {
+    CACHE_ROOT
+        .lock()
+        .ok()
+        .and_then(|root| root.clone())
+        .or_else(|| dirs::data_local_dir().map(|dir| dir.join("glimpse").join("ocr_models")))
+        .unwrap_or_else(|| std::env::temp_dir().join("glimpse_ocr_models"))
+}
+
+/// 获取当前版本的模型缓存目录，首次使用时清理旧版本的缓存
+fn cache_dir() -> Result<PathBuf> {
+    let root = cache_root();
+    let dir = root.join(CACHE_VERSION);
+    fs::create_dir_all(&dir).map_err(|e| Error::Ocr(format!("创建模型缓存目录失败: {}", e)))?;
+
+    CLEANUP.call_once(|| remove_stale_versions(&root, CACHE_VERSION));
+
+    Ok(dir)
+}
+
+/// 删除早期版本直接解压在临时目录中的模型缓存
+///
+/// 由桌面端在设置缓存目录后调用；不在首次识别时自动执行，避免测试和命令行工具删除其他实例正在使用的目录
+pub fn remove_legacy_cache() {
+    let legacy = std::env::temp_dir().join("glimpse_ocr_models");
+    if legacy == cache_root() || !legacy.is_dir() {
+        return;
+    }
+    match fs::remove_dir_all(&legacy) {
+        Ok(()) => debug_println!("[DEBUG] 已清理旧模型缓存: {}", legacy.display()),
+        Err(e) => debug_println!("[WARN] 清理旧模型缓存失败: {}", e),
+    }
+}
+
+/// 将嵌入的模型写入缓存目录
+fn extract_model(model: &EmbeddedModel) -> Result<PathBuf> {
+    extract_into(&cache_dir()?, model.data, model.digest(), model.name)
+}
+
+/// 解压模型到指定目录；已有文件的内容哈希与 `expected`（嵌入模型的 SHA-256）一致时直接复用，
+/// 否则先写入临时文件再重命名，多个实例同时解压也不会读到写了一半的文件
+fn extract_into(dir: &Path, data: &[u8], expected: &str, name: &str) -> Result<PathBuf> {
+    let model_path = dir.join(name);
+
+    if file_sha256(&model_path).as_deref() == Some(expected) {
+        debug_println!("[DEBUG] 模型缓存校验通过: {}", name);
+        return Ok(model_path);
+    }
+    if model_path.exists() {
+        debug_println!("[WARN] 模型缓存校验失败，重新解压: {}", name);
+    }
+
+    debug_println!("[DEBUG] 解压模型到缓存: {} ({} bytes)", name, data.len());
+
+    let serial = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let temp_path = dir.join(format!("{}.{}.{}{}", name, std::process::id(), serial, TEMP_SUFFIX));
+    let written = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(data)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, &model_path));
+
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp_path);
+        // Windows 上正被其他实例加载的文件无法替换；只要其内容正确就可以直接使用
+        if file_sha256(&model_path).as_deref() != Some(expected) {
+            return Err(Error::Ocr(format!("写入模型文件失败: {}", e)));
+        }
+    }
+
+    Ok(model_path)
+}
+
+/// 删除比当前版本旧的缓存目录、旧版平铺在根目录中的模型以及中断解压留下的临时文件；
+/// 更新版本的缓存保留，可能有新版本的实例正在使用。删除失败（文件被占用）时忽略，下次启动再试
+fn remove_stale_versions(root: &Path, current: &str) {
+    let Some(current_version) = parse_version(current) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let removed = if path.is_dir() {
+            match parse_version(&name) {
+                Some(version) if version < current_version => fs::remove_dir_all(&path),
+                _ => continue,
+            }
+        } else if name.ends_with(".onnx") {
+            fs::remove_file(&path)
+        } else {
+            continue;
+        };
+        match removed {
+            Ok(()) => debug_println!("[DEBUG] 已清理旧模型缓存: {}", name),
+            Err(e) => debug_println!("[WARN] 清理旧模型缓存失败: {} ({})", name, e),
+        }
+    }
+
+    remove_stale_temp_files(&root.join(current));
+}
+
+/// 删除超过一小时未修改的临时文件（更新的可能是其他实例正在写入的）
+fn remove_stale_temp_files(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > STALE_TEMP_AGE);
+        if stale && entry.file_name().to_string_lossy().ends_with(TEMP_SUFFIX) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// 解析缓存目录名中的版本号，如 `v0.1.1` -> [0, 1, 1]
+fn parse_version(name: &str) -> Option<Vec<u64>> {
+    name.strip_prefix('v')?.split('.').map(|part| part.parse().ok()).collect()
+}
+
+fn sha256(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// 文件内容的 SHA-256，文件不存在或无法读取时返回 None
+fn file_sha256(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|data| sha256(&data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每个测试使用独立的临时目录
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("glimpse-models-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_pack(dir: &Path, manifest: &str, files: &[&str]) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+        for file in files {
+            fs::write(dir.join(file), b"onnx").unwrap();
+        }
+    }
+
+    #[test]
+    fn test_discover_packs() {
+        let root = temp_dir("discover");
+        write_pack(
+            &root.join("latin"),
+            r#"{"id": "latin", "name": "Latin", "languages": ["en", "fr"], "rec": "rec.onnx", "dict": "dict.txt"}"#,
+            &["rec.onnx", "dict.txt"],
+        );
+        write_pack(
+            &root.join("japan"),
+            r#"{"id": "japan", "name": "日本語", "det": "det.onnx", "rec": "rec.onnx"}"#,
+            &["det.onnx", "rec.onnx"],
+        );
+        // 缺少识别模型、ID 重复、与内置模型包冲突、没有清单的目录都跳过
+        write_pack(&root.join("broken"), r#"{"id": "broken", "name": "Broken", "rec": "missing.onnx"}"#, &[]);
+        write_pack(&root.join("zz-copy"), r#"{"id": "latin", "name": "Copy", "rec": "rec.onnx"}"#, &["rec.onnx"]);
+        write_pack(&root.join("builtin"), r#"{"id": "builtin-ch", "name": "Fake", "rec": "rec.onnx"}"#, &["rec.onnx"]);
+        fs::create_dir_all(root.join("empty")).unwrap();
+
+        let packs = discover(&root);
+        let ids: Vec<&str> = packs.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["japan", "latin"]);
+        assert_eq!(packs[1].languages, vec!["en", "fr"]);
+        assert_eq!(packs[1].path.as_deref(), Some(root.join("latin").as_path()));
+
+        // 模型目录本身也可以是一个模型包
+        assert_eq!(discover(&root.join("latin")).len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        let root = temp_dir("invalid");
+        fs::write(root.join(MANIFEST_FILE), "{ not json").unwrap();
+        assert!(matches!(Manifest::load(&root), Err(Error::ModelPack(_))));
+
+        let error = Manifest::load(&root.join("missing")).unwrap_err();
+        assert!(error.to_string().contains(MANIFEST_FILE));
+    }
+
+    #[test]
+    fn test_select_and_resolve_pack() {
+        let root = temp_dir("select");
+        write_pack(&root.join("korean"), r#"{"id": "korean", "name": "한국어", "rec": "rec.onnx", "dict": "dict.txt"}"#, &["rec.onnx", "dict.txt"]);
+        set_models_dir(Some(root.clone()));
+        set_cache_dir(root.join("cache"));
+
+        assert!(select_pack("unknown").is_err());
+        assert_eq!(selected_pack(), BUILTIN_PACK);
+
+        select_pack("korean").unwrap();
+        assert_eq!(selected_pack(), "korean");
+        assert_eq!(list_packs().len(), 2);
+
+        // 未提供的检测、分类模型使用内置模型
+        let files = resolve("korean").unwrap();
+        assert_eq!(files.rec, root.join("korean").join("rec.onnx"));
+        assert_eq!(files.dict, Some(root.join("korean").join("dict.txt")));
+        assert_eq!(files.det, builtin_det().unwrap());
+        assert!(!files.pack.is_builtin());
+
+        select_pack(BUILTIN_PACK).unwrap();
+        assert_eq!(selected_pack(), BUILTIN_PACK);
+        set_models_dir(None);
+    }
+
+    #[test]
+    fn test_extract_verifies_content() {
+        let dir = temp_dir("extract");
+        let data = b"embedded model";
+
+        let digest = sha256(data);
+
+        let path = extract_into(&dir, data, &digest, "det.onnx").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), data);
+
+        // 大小相同但内容被篡改的缓存重新解压
+        fs::write(&path, b"tampered model").unwrap();
+        extract_into(&dir, data, &digest, "det.onnx").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), data);
+
+        // 不留下临时文件
+        let names: Vec<String> = fs::read_dir(&dir).unwrap().flatten().map(|e| e.file_name().to_string_lossy().to_string()).collect();
+        assert_eq!(names, vec!["det.onnx"]);
+    }
+
+    #[test]
+    fn test_concurrent_extract() {
+        let dir = temp_dir("concurrent");
+        let data: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+        let digest = sha256(&data);
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| extract_into(&dir, &data, &digest, "rec.onnx").unwrap());
+            }
+        });
+        assert_eq!(fs::read(dir.join("rec.onnx")).unwrap(), data);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_remove_stale_versions() {
+        let root = temp_dir("stale");
+        for version in ["v0.0.9", "v0.1.0", "v0.1.1", "v0.2.0"] {
+            fs::create_dir_all(root.join(version)).unwrap();
+            fs::write(root.join(version).join("det.onnx"), b"onnx").unwrap();
+        }
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(root.join("ch_PP-OCRv4_det_infer.onnx"), b"legacy").unwrap();
+        fs::write(root.join("v0.1.1").join(format!("det.onnx.1.0{}", TEMP_SUFFIX)), b"fresh").unwrap();
+
+        remove_stale_versions(&root, "v0.1.1");
+
+        let mut names: Vec<String> = fs::read_dir(&root).unwrap().flatten().map(|e| e.file_name().to_string_lossy().to_string()).collect();
+        names.sort();
+        // 只删除更旧的版本和平铺的旧模型，正在写入的临时文件保留
+        assert_eq!(names, vec!["other", "v0.1.1", "v0.2.0"]);
+        assert_eq!(fs::read_dir(root.join("v0.1.1")).unwrap().count(), 2);
+        assert_eq!(parse_version("v0.10.0"), Some(vec![0, 10, 0]));
+        assert_eq!(parse_version("latest"), None);
+    }
+}
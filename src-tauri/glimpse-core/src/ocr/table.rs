@@ -0,0 +1,345 @@
+//! 表格识别
+//! 根据 OCR 文本框的位置和图像中的表格线推断行列，重建单元格，
+//! 可导出为 TSV（粘贴到电子表格）、CSV 或 Markdown 表格
+
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+
+use super::layout::{bounds_of, gaps, joins_without_space, median, Span};
+use super::OcrLine;
+use crate::export::{csv_field, markdown_cell, tsv_field};
+
+/// 检测表格线时与两侧比较的距离（像素），可识别 2 * RULING_PROBE - 1 像素以内粗细的线
+const RULING_PROBE: u32 = 3;
+/// 线上像素与两侧的最小亮度差
+const RULING_CONTRAST: i16 = 24;
+/// 表格线的最小长度（占图像宽度或高度的比例）
+const RULING_MIN_LENGTH: f32 = 0.5;
+/// 线上允许的最大中断（与另一方向的线相交、抗锯齿），像素
+const RULING_MAX_GAP: u32 = 4;
+/// 两个文本框在垂直方向上重叠超过该比例时视为同一行
+const SAME_ROW_OVERLAP: f32 = 0.5;
+/// 列间空白的最小宽度（行高的倍数）
+const COLUMN_GAP: f32 = 0.5;
+/// 横线不少于该数量时按横线划分行（同一格内的多行文字合并），否则只按文字位置分行
+const MIN_ROW_RULINGS: usize = 3;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableFormat {
+    Tsv,
+    Csv,
+    Markdown,
+}
+
+/// 图像中的表格线
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rulings {
+    /// 横线的 y 坐标（升序）
+    pub horizontal: Vec<f32>,
+    /// 竖线的 x 坐标（升序）
+    pub vertical: Vec<f32>,
+}
+
+/// 重建的表格，所有行的列数相同（缺失的单元格为空字符串）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Table {
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    /// 导出为文本，第一行作为表头（Markdown 需要表头）
+    pub fn export(&self, format: TableFormat) -> String {
+        let mut out = String::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            match format {
+                TableFormat::Tsv => {
+                    out.push_str(&row.iter().map(|cell| tsv_field(cell)).collect::<Vec<_>>().join("\t"));
+                }
+                TableFormat::Csv => {
+                    out.push_str(&row.iter().map(|cell| csv_field(cell)).collect::<Vec<_>>().join(","));
+                }
+                TableFormat::Markdown => {
+                    let cells: Vec<String> = row.iter().map(|cell| markdown_cell(cell)).collect();
+                    out.push_str(&format!("| {} |", cells.join(" | ")));
+                    if i == 0 {
+                        out.push_str(&format!("\n|{}", " --- |".repeat(row.len())));
+                    }
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// 检测图像中的横线和竖线
+pub fn detect_rulings(image: &DynamicImage) -> Rulings {
+    let gray = image.to_luma8();
+    let (width, height) = gray.dimensions();
+    Rulings {
+        horizontal: find_rulings(width, height, |along, pos| gray.get_pixel(along, pos)[0]),
+        vertical: find_rulings(height, width, |along, pos| gray.get_pixel(pos, along)[0]),
+    }
+}
+
+/// 逐条扫描候选线（检测横线时为每一行像素），返回各条线的中心坐标
+///
+/// 比两侧相距 [`RULING_PROBE`] 像素处都明显更暗（或都更亮）的像素视为线上的点，
+/// 最长连续段达到长度的 [`RULING_MIN_LENGTH`] 即为表格线；文字笔画不会形成这样长的连续段
+fn find_rulings(length: u32, count: u32, pixel: impl Fn(u32, u32) -> u8) -> Vec<f32> {
+    if count <= 2 * RULING_PROBE {
+        return Vec::new();
+    }
+    let min_run = ((length as f32 * RULING_MIN_LENGTH) as u32).max(1);
+
+    let candidates: Vec<u32> = (RULING_PROBE..count - RULING_PROBE)
+        .filter(|&pos| {
+            longest_run(length, |i| {
+                let p = i16::from(pixel(i, pos));
+                let before = p - i16::from(pixel(i, pos - RULING_PROBE));
+                let after = p - i16::from(pixel(i, pos + RULING_PROBE));
+                before.abs() >= RULING_CONTRAST && after.abs() >= RULING_CONTRAST && before.signum() == after.signum()
+            }) >= min_run
+        })
+        .collect();
+
+    // 相邻的候选线属于同一条粗线，取中点
+    let mut rulings = Vec::new();
+    let mut start = 0;
+    for i in 1..=candidates.len() {
+        if i == candidates.len() || candidates[i] != candidates[i - 1] + 1 {
+            rulings.push((candidates[start] + candidates[i - 1]) as f32 / 2.0);
+            start = i;
+        }
+    }
+    rulings
+}
+
+/// 最长连续段的长度（不超过 [`RULING_MAX_GAP`] 的中断视为连续）
+fn longest_run(length: u32, on: impl Fn(u32) -> bool) -> u32 {
+    let (mut longest, mut run_start, mut last_on) = (0, 0, None);
+    for i in 0..length {
+        if !on(i) {
+            continue;
+        }
+        match last_on {
+            Some(last) if i - last <= RULING_MAX_GAP + 1 => {}
+            _ => run_start = i,
+        }
+        last_on = Some(i);
+        longest = longest.max(i - run_start + 1);
+    }
+    longest
+}
+
+/// 表格中的文本框
+struct Cell<'a> {
+    text: &'a str,
+    x: Span,
+    y: Span,
+    /// 按文字位置聚类得到的行号
+    line: usize,
+}
+
+/// 根据文本框和表格线重建表格
+///
+/// 行：按文字的垂直位置聚类；横线足够多时，同一对横线之间的多行文字合并为一行。
+/// 列：在有多个单元格的行中，文本框水平投影的空白处和竖线处都是列边界；
+/// 只有一个文本框的行（标题、合并单元格）按其中心归入所在的列
+pub fn reconstruct(lines: &[OcrLine], rulings: &Rulings) -> Table {
+    let mut cells: Vec<Cell> = lines
+        .iter()
+        .filter(|line| !line.text.trim().is_empty() && !line.points.is_empty())
+        .map(|line| Cell {
+            text: line.text.trim(),
+            x: bounds_of(line.points.iter().map(|p| p.x)),
+            y: bounds_of(line.points.iter().map(|p| p.y)),
+            line: 0,
+        })
+        .collect();
+    if cells.is_empty() {
+        return Table::default();
+    }
+
+    // 按垂直位置聚类为行
+    cells.sort_by(|a, b| a.y.center().total_cmp(&b.y.center()));
+    let mut row_spans: Vec<Span> = Vec::new();
+    let mut previous: Vec<usize> = Vec::new();
+    for i in 0..cells.len() {
+        let same_row = previous.iter().any(|&j| {
+            let (a, b) = (cells[i].y, cells[j].y);
+            a.overlap(b) >= SAME_ROW_OVERLAP * a.extent().min(b.extent())
+        });
+        if !same_row {
+            previous.clear();
+            row_spans.push(cells[i].y);
+        }
+        let row = row_spans.len() - 1;
+        row_spans[row] = Span { start: row_spans[row].start.min(cells[i].y.start), end: row_spans[row].end.max(cells[i].y.end) };
+        cells[i].line = row;
+        previous.push(i);
+    }
+
+    // 横线足够多时按横线合并同一格内的多行文字
+    let row_of: Vec<usize> = if rulings.horizontal.len() >= MIN_ROW_RULINGS {
+        let bands: Vec<usize> = row_spans
+            .iter()
+            .map(|span| rulings.horizontal.iter().filter(|&&y| y < span.center()).count())
+            .collect();
+        let mut row = 0;
+        (0..row_spans.len())
+            .map(|i| {
+                row += usize::from(i > 0 && bands[i] != bands[i - 1]);
+                row
+            })
+            .collect()
+    } else {
+        (0..row_spans.len()).collect()
+    };
+
+    // 列边界：多单元格行的投影空白和竖线
+    let mut counts = vec![0usize; row_spans.len()];
+    for cell in &cells {
+        counts[cell.line] += 1;
+    }
+    let has_multi = counts.iter().any(|&n| n > 1);
+    let line_height = median(cells.iter().map(|c| c.y.extent())).unwrap_or(1.0);
+    let mut cuts = gaps(
+        cells.iter().filter(|c| !has_multi || counts[c.line] > 1).map(|c| c.x),
+        COLUMN_GAP * line_height,
+    );
+    cuts.extend(&rulings.vertical);
+    let column_of = |cell: &Cell| cuts.iter().filter(|&&x| x < cell.x.center()).count();
+
+    // 去掉空列
+    let mut used: Vec<usize> = cells.iter().map(column_of).collect();
+    used.sort_unstable();
+    used.dedup();
+
+    let row_count = row_of.last().map_or(0, |&r| r + 1);
+    let mut grid: Vec<Vec<Vec<&Cell>>> = vec![vec![Vec::new(); used.len()]; row_count];
+    for cell in &cells {
+        let column = used.binary_search(&column_of(cell)).unwrap_or_default();
+        grid[row_of[cell.line]][column].push(cell);
+    }
+
+    let rows = grid
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|mut parts| {
+                    parts.sort_by(|a, b| a.line.cmp(&b.line).then(a.x.start.total_cmp(&b.x.start)));
+                    let mut text = String::new();
+                    for part in parts {
+                        if !text.is_empty() && !joins_without_space(&text, part.text) {
+                            text.push(' ');
+                        }
+                        text.push_str(part.text);
+                    }
+                    text
+                })
+                .collect()
+        })
+        .collect();
+    Table { rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_scanner::Point;
+    use image::{Rgb, RgbImage};
+
+    fn cell(text: &str, x: f32, y: f32, width: f32) -> OcrLine {
+        OcrLine {
+            text: text.to_string(),
+            points: vec![
+                Point { x, y },
+                Point { x: x + width, y },
+                Point { x: x + width, y: y + 20.0 },
+                Point { x, y: y + 20.0 },
+            ],
+            confidence: Some(0.9),
+            angle: 0.0,
+        }
+    }
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter().map(|row| row.iter().map(|s| s.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn test_borderless_table() {
+        let lines = [
+            cell("1.50", 300.0, 30.0, 40.0),
+            cell("Name", 0.0, 0.0, 40.0),
+            cell("12", 200.0, 60.0, 20.0),
+            cell("Qty", 200.0, 0.0, 30.0),
+            cell("Apple", 0.0, 31.0, 50.0),
+            cell("Price", 300.0, 0.0, 50.0),
+            cell("3", 200.0, 29.0, 10.0),
+            cell("Banana bread", 0.0, 60.0, 120.0),
+        ];
+        let table = reconstruct(&lines, &Rulings::default());
+        assert_eq!(table.rows, rows(&[&["Name", "Qty", "Price"], &["Apple", "3", "1.50"], &["Banana bread", "12", ""]]));
+    }
+
+    #[test]
+    fn test_ruled_table_with_multiline_cell() {
+        // 300x200 的白底图像，灰色 1 像素表格线
+        let mut image = RgbImage::from_pixel(300, 200, Rgb([255, 255, 255]));
+        for y in [10, 60, 110, 190] {
+            for x in 10..=290 {
+                image.put_pixel(x, y, Rgb([180, 180, 180]));
+            }
+        }
+        for x in [10, 150, 290] {
+            for y in 10..=190 {
+                image.put_pixel(x, y, Rgb([180, 180, 180]));
+            }
+        }
+        // 模拟文字笔画：短横线不应被识别为表格线
+        for x in 30..70 {
+            image.put_pixel(x, 35, Rgb([0, 0, 0]));
+        }
+
+        let rulings = detect_rulings(&DynamicImage::ImageRgb8(image));
+        assert_eq!(rulings.horizontal, vec![10.0, 60.0, 110.0, 190.0]);
+        assert_eq!(rulings.vertical, vec![10.0, 150.0, 290.0]);
+
+        let lines = [
+            cell("City", 30.0, 25.0, 40.0),
+            cell("Population", 170.0, 25.0, 100.0),
+            cell("Paris", 30.0, 75.0, 50.0),
+            cell("2.1M", 170.0, 75.0, 40.0),
+            cell("New", 30.0, 120.0, 30.0),
+            cell("8.3M", 170.0, 120.0, 40.0),
+            cell("York", 30.0, 150.0, 40.0),
+        ];
+        let table = reconstruct(&lines, &rulings);
+        assert_eq!(table.rows, rows(&[&["City", "Population"], &["Paris", "2.1M"], &["New York", "8.3M"]]));
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let table = Table { rows: rows(&[&["a", "b,c"], &["x|y", "line\nbreak"]]) };
+        assert_eq!(table.export(TableFormat::Tsv), "a\tb,c\nx|y\tline break\n");
+        assert_eq!(table.export(TableFormat::Csv), "a,\"b,c\"\nx|y,\"line\nbreak\"\n");
+        assert_eq!(table.export(TableFormat::Markdown), "| a | b,c |\n| --- | --- |\n| x\\|y | line<br>break |\n");
+        assert_eq!(Table::default().export(TableFormat::Markdown), "");
+
+        // 可能被电子表格当作公式的单元格加 ' 前缀
+        let formulas = Table { rows: rows(&[&["=1+1", "-2"], &["@cmd", "\tx"]]) };
+        assert_eq!(formulas.export(TableFormat::Tsv), "'=1+1\t'-2\n'@cmd\t' x\n");
+        assert_eq!(formulas.export(TableFormat::Csv), "'=1+1,'-2\n'@cmd,'\tx\n");
+    }
+}
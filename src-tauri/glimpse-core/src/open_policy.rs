@@ -0,0 +1,348 @@
+//! 链接打开策略
+//! 按用户配置的规则（域名、协议、正则）决定识别到的链接是自动打开、需确认还是禁止打开。
+//! 规则按顺序匹配，第一条命中的规则生效；均未命中时使用默认动作
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::error::{Error, Result};
+use crate::url_risk::{self, RiskLevel};
+
+/// 打开动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpenAction {
+    /// 识别后自动打开
+    Open,
+    /// 需用户确认后打开
+    Confirm,
+    /// 禁止打开
+    Block,
+}
+
+/// 规则的匹配条件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum RulePattern {
+    /// 域名：`example.com` 匹配该域名及其所有子域名，`*.example.com` 只匹配子域名
+    Domain(String),
+    /// 协议名，如 `https`、`weixin`（不区分大小写）
+    Scheme(String),
+    /// 对完整链接文本进行正则匹配（部分匹配即可，需整体匹配时使用 ^...$）
+    Regex(String),
+}
+
+/// 单条规则
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenRule {
+    pub pattern: RulePattern,
+    pub action: OpenAction,
+    /// 是否启用（停用的规则保留在列表中但不参与匹配）
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 链接打开策略
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenPolicy {
+    /// 规则列表，按顺序匹配
+    pub rules: Vec<OpenRule>,
+    /// 没有规则命中时的动作
+    pub default_action: OpenAction,
+}
+
+impl Default for OpenPolicy {
+    /// 默认禁止可执行脚本或读取本地文件的协议，其余链接需确认后打开
+    fn default() -> Self {
+        let block = |scheme: &str| OpenRule {
+            pattern: RulePattern::Scheme(scheme.to_string()),
+            action: OpenAction::Block,
+            enabled: true,
+        };
+        Self {
+            rules: vec![block("javascript"), block("vbscript"), block("data"), block("file")],
+            default_action: OpenAction::Confirm,
+        }
+    }
+}
+
+/// 策略判定结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OpenDecision {
+    pub action: OpenAction,
+    /// 命中的规则序号，None 表示使用默认动作或内容不是链接
+    pub rule: Option<usize>,
+    /// 面向用户的说明
+    pub reason: String,
+}
+
+impl OpenPolicy {
+    /// 检查所有规则是否有效（域名非空、协议名合法、正则可编译）
+    pub fn validate(&self) -> Result<()> {
+        self.compile().map(|_| ())
+    }
+
+    /// 检查并编译规则，得到可反复判定的策略（正则只编译一次）
+    pub fn compile(&self) -> Result<CompiledPolicy> {
+        let matchers = self
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let invalid = |message: String| Error::InvalidRule(format!("第 {} 条规则{}", index + 1, message));
+                match &rule.pattern {
+                    RulePattern::Domain(domain) => normalize_domain(domain)
+                        .map(|normalized| Matcher::Domain {
+                            domain: normalized,
+                            subdomains_only: domain.trim().starts_with("*."),
+                        })
+                        .ok_or_else(|| invalid(format!("的域名“{}”无效", domain))),
+                    RulePattern::Scheme(scheme) => normalize_scheme(scheme)
+                        .map(Matcher::Scheme)
+                        .ok_or_else(|| invalid(format!("的协议名“{}”无效", scheme))),
+                    RulePattern::Regex(pattern) => Regex::new(pattern)
+                        .map(Matcher::Regex)
+                        .map_err(|e| invalid(format!("的正则表达式无效: {}", e))),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(CompiledPolicy { policy: self.clone(), matchers })
+    }
+}
+
+/// 编译后的链接打开策略，由 [`OpenPolicy::compile`] 生成
+#[derive(Debug, Clone)]
+pub struct CompiledPolicy {
+    policy: OpenPolicy,
+    /// 与 `policy.rules` 一一对应
+    matchers: Vec<Matcher>,
+}
+
+/// 规范化后的匹配条件
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// ASCII 小写域名，`subdomains_only` 对应 `*.` 前缀
+    Domain { domain: String, subdomains_only: bool },
+    /// 小写协议名
+    Scheme(String),
+    Regex(Regex),
+}
+
+impl CompiledPolicy {
+    /// 编译前的策略配置
+    pub fn policy(&self) -> &OpenPolicy {
+        &self.policy
+    }
+
+    /// 判定链接的打开动作
+    ///
+    /// 高风险链接（见 [`url_risk::analyze_url`]）即使命中自动打开规则也降级为需确认
+    pub fn evaluate(&self, url: &str) -> OpenDecision {
+        let text = url.trim();
+        let Some(scheme) = url_risk::scheme_of(text) else {
+            return OpenDecision { action: OpenAction::Block, rule: None, reason: "内容不是链接".to_string() };
+        };
+        let host = Url::parse(text)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.trim_end_matches('.').to_ascii_lowercase()));
+
+        let matched = self
+            .policy
+            .rules
+            .iter()
+            .zip(&self.matchers)
+            .enumerate()
+            .filter(|(_, (rule, _))| rule.enabled)
+            .find(|(_, (_, matcher))| matcher.matches(text, &scheme, host.as_deref()));
+
+        let mut decision = match matched {
+            Some((index, (rule, _))) => OpenDecision {
+                action: rule.action,
+                rule: Some(index),
+                reason: format!("命中第 {} 条规则（{}）", index + 1, rule.pattern),
+            },
+            None => OpenDecision {
+                action: self.policy.default_action,
+                rule: None,
+                reason: "未命中任何规则，使用默认动作".to_string(),
+            },
+        };
+
+        if decision.action == OpenAction::Open {
+            if let Some(risk) = url_risk::analyze_url(text).filter(|risk| risk.level == RiskLevel::High) {
+                let detail = risk.reasons.first().map(|r| r.message.as_str()).unwrap_or_default();
+                decision.action = OpenAction::Confirm;
+                decision.reason = format!("{}，但链接存在高风险，需确认后打开: {}", decision.reason, detail);
+            }
+        }
+
+        debug_println!("[DEBUG] 链接打开策略: {} -> {:?} ({})", text, decision.action, decision.reason);
+        decision
+    }
+}
+
+impl Matcher {
+    /// `host` 为 ASCII 小写形式（国际化域名已编码为 xn--）
+    fn matches(&self, text: &str, scheme: &str, host: Option<&str>) -> bool {
+        match self {
+            Matcher::Domain { domain, subdomains_only } => {
+                let Some(host) = host else {
+                    return false;
+                };
+                let subdomain = host.len() > domain.len()
+                    && host.ends_with(domain.as_str())
+                    && host.as_bytes()[host.len() - domain.len() - 1] == b'.';
+                subdomain || (!subdomains_only && host == domain)
+            }
+            Matcher::Scheme(pattern) => pattern == scheme,
+            Matcher::Regex(re) => re.is_match(text),
+        }
+    }
+}
+
+impl std::fmt::Display for RulePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RulePattern::Domain(domain) => write!(f, "域名 {}", domain.trim()),
+            RulePattern::Scheme(scheme) => write!(f, "协议 {}", scheme.trim()),
+            RulePattern::Regex(pattern) => write!(f, "正则 {}", pattern),
+        }
+    }
+}
+
+/// 将域名规则转换为 ASCII 小写形式（去掉 `*.` 前缀），无效时返回 None
+fn normalize_domain(pattern: &str) -> Option<String> {
+    let pattern = pattern.trim();
+    let domain = pattern.strip_prefix("*.").unwrap_or(pattern).trim_end_matches('.');
+    if domain.is_empty() || domain.contains(['/', ':', '*', '@']) {
+        return None;
+    }
+    idna::domain_to_ascii(domain).ok().filter(|d| !d.is_empty())
+}
+
+/// 将协议规则转换为小写（允许带结尾的冒号），无效时返回 None
+fn normalize_scheme(pattern: &str) -> Option<String> {
+    let scheme = pattern.trim().trim_end_matches(':');
+    url_risk::scheme_of(&format!("{}:", scheme))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: RulePattern, action: OpenAction) -> OpenRule {
+        OpenRule { pattern, action, enabled: true }
+    }
+
+    fn domain(value: &str) -> RulePattern {
+        RulePattern::Domain(value.to_string())
+    }
+
+    fn action(policy: &OpenPolicy, url: &str) -> OpenAction {
+        policy.compile().unwrap().evaluate(url).action
+    }
+
+    #[test]
+    fn test_domain_pattern() {
+        let policy = OpenPolicy {
+            rules: vec![rule(domain("example.com"), OpenAction::Open), rule(domain("*.corp.net"), OpenAction::Open)],
+            default_action: OpenAction::Block,
+        };
+        assert_eq!(action(&policy, "https://example.com/a"), OpenAction::Open);
+        assert_eq!(action(&policy, "https://WWW.Example.COM./a"), OpenAction::Open);
+        assert_eq!(action(&policy, "https://notexample.com"), OpenAction::Block);
+        assert_eq!(action(&policy, "https://example.com.evil.tld"), OpenAction::Block);
+        assert_eq!(action(&policy, "https://wiki.corp.net"), OpenAction::Open);
+        // *.corp.net 不匹配 corp.net 本身
+        assert_eq!(action(&policy, "https://corp.net"), OpenAction::Block);
+    }
+
+    #[test]
+    fn test_unicode_domain_pattern() {
+        let policy = OpenPolicy {
+            rules: vec![rule(domain("例子.中国"), OpenAction::Open)],
+            default_action: OpenAction::Block,
+        };
+        assert_eq!(action(&policy, "https://www.例子.中国/"), OpenAction::Open);
+        assert_eq!(action(&policy, "https://www.xn--fsqu00a.xn--fiqs8s/"), OpenAction::Open);
+    }
+
+    #[test]
+    fn test_scheme_and_regex_pattern() {
+        let policy = OpenPolicy {
+            rules: vec![
+                rule(RulePattern::Scheme("WEIXIN:".to_string()), OpenAction::Block),
+                rule(RulePattern::Regex(r"^https://[^/]+/share/\d+$".to_string()), OpenAction::Open),
+            ],
+            default_action: OpenAction::Confirm,
+        };
+        assert_eq!(action(&policy, "weixin://dl/business"), OpenAction::Block);
+        assert_eq!(action(&policy, "https://a.io/share/123"), OpenAction::Open);
+        assert_eq!(action(&policy, "https://a.io/share/123?x=1"), OpenAction::Confirm);
+    }
+
+    #[test]
+    fn test_rule_order_and_disabled() {
+        let mut policy = OpenPolicy {
+            rules: vec![
+                rule(domain("ads.example.com"), OpenAction::Block),
+                rule(domain("example.com"), OpenAction::Open),
+            ],
+            default_action: OpenAction::Confirm,
+        };
+        let decision = policy.compile().unwrap().evaluate("https://ads.example.com/x");
+        assert_eq!((decision.action, decision.rule), (OpenAction::Block, Some(0)));
+
+        policy.rules[0].enabled = false;
+        let decision = policy.compile().unwrap().evaluate("https://ads.example.com/x");
+        assert_eq!((decision.action, decision.rule), (OpenAction::Open, Some(1)));
+
+        let decision = policy.compile().unwrap().evaluate("https://other.org");
+        assert_eq!((decision.action, decision.rule), (OpenAction::Confirm, None));
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policy = OpenPolicy::default();
+        assert_eq!(action(&policy, "javascript:alert(1)"), OpenAction::Block);
+        assert_eq!(action(&policy, "DATA:text/html,hi"), OpenAction::Block);
+        assert_eq!(action(&policy, "https://example.com"), OpenAction::Confirm);
+        assert_eq!(action(&policy, "普通文本"), OpenAction::Block);
+    }
+
+    #[test]
+    fn test_high_risk_downgraded() {
+        let policy = OpenPolicy { rules: Vec::new(), default_action: OpenAction::Open };
+        assert_eq!(action(&policy, "https://example.com"), OpenAction::Open);
+        // @ 前缀伪装为高风险，不自动打开
+        assert_eq!(action(&policy, "https://bank.com@evil.tld/login"), OpenAction::Confirm);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(OpenPolicy::default().validate().is_ok());
+        let invalid = [
+            RulePattern::Domain(" ".to_string()),
+            RulePattern::Domain("https://example.com".to_string()),
+            RulePattern::Scheme("1http".to_string()),
+            RulePattern::Regex("(unclosed".to_string()),
+        ];
+        for pattern in invalid {
+            let policy = OpenPolicy { rules: vec![rule(pattern.clone(), OpenAction::Open)], ..Default::default() };
+            assert!(policy.validate().is_err(), "{:?} 应无效", pattern);
+        }
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{"rules":[{"pattern":{"type":"Domain","value":"example.com"},"action":"Open"}]}"#;
+        let policy: OpenPolicy = serde_json::from_str(json).unwrap();
+        assert!(policy.rules[0].enabled);
+        assert_eq!(policy.default_action, OpenAction::Confirm);
+    }
+}
@@ -0,0 +1,731 @@
+//! 二维码载荷解析模块
+//! 将 Wi-Fi、vCard、MeCard、geo、SMS、日历事件和邮件等常见格式
+//! 解析为结构化数据，供前端渲染为表单
+
+use serde::{Deserialize, Serialize};
+
+/// 结构化载荷
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data")]
+pub enum QrPayload {
+    Wifi(WifiConfig),
+    Contact(Contact),
+    Geo(GeoLocation),
+    Sms(SmsMessage),
+    Event(CalendarEvent),
+    Email(EmailMessage),
+}
+
+/// Wi-Fi 网络配置（`WIFI:T:WPA;S:ssid;P:password;H:true;;`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WifiConfig {
+    /// 网络名称
+    pub ssid: String,
+    /// 认证方式（WPA、WEP、nopass 等）
+    pub auth: String,
+    /// 密码
+    pub password: Option<String>,
+    /// 是否为隐藏网络
+    pub hidden: bool,
+}
+
+/// 联系人（vCard / MeCard）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    pub name: Option<String>,
+    pub organization: Option<String>,
+    pub title: Option<String>,
+    pub phones: Vec<String>,
+    pub emails: Vec<String>,
+    pub urls: Vec<String>,
+    pub address: Option<String>,
+    pub note: Option<String>,
+}
+
+/// 地理位置（`geo:lat,long[,alt][?q=...]`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: Option<f64>,
+    /// 地点查询文本
+    pub query: Option<String>,
+}
+
+/// 短信（`SMSTO:number:body` 或 `sms:number?body=...`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmsMessage {
+    pub number: String,
+    pub body: Option<String>,
+}
+
+/// 日历事件（VEVENT）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    pub summary: Option<String>,
+    /// 开始时间（ISO 8601 格式）
+    pub start: Option<String>,
+    /// 结束时间（ISO 8601 格式）
+    pub end: Option<String>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+}
+
+/// 邮件（`MATMSG:` 或 `mailto:`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+}
+
+/// 解析二维码内容，无法识别的格式返回 None
+pub fn parse(content: &str) -> Option<QrPayload> {
+    let content = content.trim();
+
+    if let Some(rest) = strip_prefix_ignore_case(content, "WIFI:") {
+        parse_wifi(rest).map(QrPayload::Wifi)
+    } else if let Some(rest) = strip_prefix_ignore_case(content, "MECARD:") {
+        parse_mecard(rest).map(QrPayload::Contact)
+    } else if let Some(rest) = strip_prefix_ignore_case(content, "MATMSG:") {
+        parse_matmsg(rest).map(QrPayload::Email)
+    } else if strip_prefix_ignore_case(content, "BEGIN:VCARD").is_some() {
+        parse_vcard(content).map(QrPayload::Contact)
+    } else if strip_prefix_ignore_case(content, "BEGIN:VCALENDAR").is_some()
+        || strip_prefix_ignore_case(content, "BEGIN:VEVENT").is_some()
+    {
+        parse_vevent(content).map(QrPayload::Event)
+    } else if let Some(rest) = strip_prefix_ignore_case(content, "geo:") {
+        parse_geo(rest).map(QrPayload::Geo)
+    } else if let Some(rest) = strip_prefix_ignore_case(content, "SMSTO:") {
+        parse_smsto(rest).map(QrPayload::Sms)
+    } else if let Some(rest) = strip_prefix_ignore_case(content, "sms:") {
+        parse_sms_uri(rest).map(QrPayload::Sms)
+    } else if let Some(rest) = strip_prefix_ignore_case(content, "mailto:") {
+        parse_mailto(rest).map(QrPayload::Email)
+    } else {
+        None
+    }
+}
+
+/// 忽略大小写去除前缀
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// 空字符串转为 None
+fn non_empty(s: String) -> Option<String> {
+    if s.trim().is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+// ============ MeCard 风格（WIFI / MECARD / MATMSG） ============
+
+/// 拆分 `KEY:value;KEY:value;;` 形式的字段
+///
+/// 反斜杠转义 `\;`、`\:`、`\,`、`\\`、`\"`，键统一转为大写
+fn split_mecard_fields(input: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut key = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    if in_value {
+                        value.push(next);
+                    } else {
+                        key.push(next);
+                    }
+                }
+            }
+            ';' => {
+                if in_value {
+                    fields.push((key.trim().to_ascii_uppercase(), std::mem::take(&mut value)));
+                }
+                key.clear();
+                in_value = false;
+            }
+            ':' if !in_value => in_value = true,
+            _ => {
+                if in_value {
+                    value.push(c);
+                } else {
+                    key.push(c);
+                }
+            }
+        }
+    }
+
+    if in_value {
+        fields.push((key.trim().to_ascii_uppercase(), value));
+    }
+
+    fields
+}
+
+/// 解析 Wi-Fi 配置
+fn parse_wifi(rest: &str) -> Option<WifiConfig> {
+    let mut ssid = None;
+    let mut auth = None;
+    let mut password = None;
+    let mut hidden = false;
+
+    for (key, value) in split_mecard_fields(rest) {
+        match key.as_str() {
+            "S" => ssid = Some(value),
+            "T" => auth = non_empty(value),
+            "P" => password = non_empty(value),
+            "H" => hidden = value.eq_ignore_ascii_case("true") || value == "1",
+            _ => {}
+        }
+    }
+
+    let auth = auth.unwrap_or_else(|| {
+        if password.is_some() { "WPA".to_string() } else { "nopass".to_string() }
+    });
+
+    Some(WifiConfig {
+        ssid: ssid?,
+        auth,
+        password,
+        hidden,
+    })
+}
+
+/// 解析 MeCard 联系人
+fn parse_mecard(rest: &str) -> Option<Contact> {
+    let mut contact = Contact::default();
+
+    for (key, value) in split_mecard_fields(rest) {
+        match key.as_str() {
+            "N" => {
+                // MeCard 的姓名格式为 "姓,名"
+                contact.name = match value.split_once(',') {
+                    Some((family, given)) => non_empty(join_name(family, given)),
+                    None => non_empty(value),
+                };
+            }
+            "ORG" => contact.organization = non_empty(value),
+            "TITLE" => contact.title = non_empty(value),
+            "TEL" => contact.phones.extend(non_empty(value)),
+            "EMAIL" => contact.emails.extend(non_empty(value)),
+            "URL" => contact.urls.extend(non_empty(value)),
+            "ADR" => contact.address = non_empty(value),
+            "NOTE" => contact.note = non_empty(value),
+            _ => {}
+        }
+    }
+
+    Some(contact)
+}
+
+/// 解析 MATMSG 邮件
+fn parse_matmsg(rest: &str) -> Option<EmailMessage> {
+    let mut to = None;
+    let mut subject = None;
+    let mut body = None;
+
+    for (key, value) in split_mecard_fields(rest) {
+        match key.as_str() {
+            "TO" => to = non_empty(value),
+            "SUB" => subject = non_empty(value),
+            "BODY" => body = non_empty(value),
+            _ => {}
+        }
+    }
+
+    Some(EmailMessage { to: to?, subject, body })
+}
+
+/// 拼接姓和名：中日韩姓名直接连接，其他语言按 "名 姓" 以空格分隔
+fn join_name(family: &str, given: &str) -> String {
+    let family = family.trim();
+    let given = given.trim();
+    if family.is_empty() || given.is_empty() {
+        return format!("{}{}", given, family);
+    }
+    if family.is_ascii() || given.is_ascii() {
+        format!("{} {}", given, family)
+    } else {
+        format!("{}{}", family, given)
+    }
+}
+
+// ============ vCard / iCalendar ============
+
+/// 展开折行（以空格或制表符开头的行是上一行的延续）
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue;
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+/// 拆分属性行，返回（大写属性名, 原始值）
+///
+/// 会去掉分组前缀（`item1.TEL`）和参数（`TEL;TYPE=CELL`）
+fn split_property(line: &str) -> Option<(String, &str)> {
+    let (name, value) = line.split_once(':')?;
+    let name = name.split(';').next().unwrap_or(name);
+    let name = name.rsplit('.').next().unwrap_or(name);
+    Some((name.trim().to_ascii_uppercase(), value))
+}
+
+/// 按未转义的分隔符拆分 vCard 值，并处理 `\n`、`\,`、`\;`、`\\` 转义
+fn split_escaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => current.push('\n'),
+                Some(next) => current.push(next),
+                None => current.push('\\'),
+            }
+        } else if c == separator {
+            parts.push(std::mem::take(&mut current));
+        } else {
+            current.push(c);
+        }
+    }
+    parts.push(current);
+    parts
+}
+
+/// 处理 vCard 文本值的转义
+fn unescape_text(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => result.push('\n'),
+                Some(next) => result.push(next),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// 拼接结构化值中的非空部分
+fn join_components(value: &str, separator: &str) -> Option<String> {
+    let parts: Vec<String> = split_escaped(value, ';')
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    non_empty(parts.join(separator))
+}
+
+/// 解析 vCard 联系人
+fn parse_vcard(content: &str) -> Option<Contact> {
+    let mut contact = Contact::default();
+    let mut structured_name = None;
+
+    for line in unfold_lines(content) {
+        let Some((name, value)) = split_property(&line) else {
+            continue;
+        };
+        match name.as_str() {
+            "FN" => contact.name = non_empty(unescape_text(value)),
+            "N" => {
+                let parts = split_escaped(value, ';');
+                let family = parts.first().map(String::as_str).unwrap_or("");
+                let given = parts.get(1).map(String::as_str).unwrap_or("");
+                structured_name = non_empty(join_name(family, given));
+            }
+            "ORG" => contact.organization = join_components(value, " "),
+            "TITLE" => contact.title = non_empty(unescape_text(value)),
+            "TEL" => contact.phones.extend(non_empty(unescape_text(value))),
+            "EMAIL" => contact.emails.extend(non_empty(unescape_text(value))),
+            "URL" => contact.urls.extend(non_empty(unescape_text(value))),
+            "ADR" => contact.address = join_components(value, ", "),
+            "NOTE" => contact.note = non_empty(unescape_text(value)),
+            _ => {}
+        }
+    }
+
+    if contact.name.is_none() {
+        contact.name = structured_name;
+    }
+
+    Some(contact)
+}
+
+/// 解析日历事件（只读取第一个 VEVENT）
+fn parse_vevent(content: &str) -> Option<CalendarEvent> {
+    let mut event = CalendarEvent::default();
+    let lines = unfold_lines(content);
+    let has_vevent = lines.iter().any(|l| l.eq_ignore_ascii_case("BEGIN:VEVENT"));
+    let mut in_event = !has_vevent;
+    let mut found = false;
+
+    for line in &lines {
+        if line.eq_ignore_ascii_case("BEGIN:VEVENT") {
+            in_event = true;
+            continue;
+        }
+        if line.eq_ignore_ascii_case("END:VEVENT") {
+            break;
+        }
+        if !in_event {
+            continue;
+        }
+        let Some((name, value)) = split_property(line) else {
+            continue;
+        };
+        match name.as_str() {
+            "SUMMARY" => event.summary = non_empty(unescape_text(value)),
+            "DTSTART" => event.start = Some(format_ical_datetime(value.trim())),
+            "DTEND" => event.end = Some(format_ical_datetime(value.trim())),
+            "LOCATION" => event.location = non_empty(unescape_text(value)),
+            "DESCRIPTION" => event.description = non_empty(unescape_text(value)),
+            _ => continue,
+        }
+        found = true;
+    }
+
+    found.then_some(event)
+}
+
+/// 将 iCalendar 日期时间（`20240101T090000Z` / `20240101`）转为 ISO 8601 格式
+///
+/// 无法识别的格式原样返回
+fn format_ical_datetime(value: &str) -> String {
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return value.to_string();
+    }
+    let date = format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..8]);
+
+    match time {
+        None => date,
+        Some(time) => {
+            let (digits, utc) = match time.strip_suffix('Z') {
+                Some(digits) => (digits, "Z"),
+                None => (time, ""),
+            };
+            if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return value.to_string();
+            }
+            format!("{}T{}:{}:{}{}", date, &digits[..2], &digits[2..4], &digits[4..6], utc)
+        }
+    }
+}
+
+// ============ URI 风格（geo / sms / mailto） ============
+
+/// 百分号解码，`plus_as_space` 为 true 时将 `+` 解码为空格；`%` 后不是两位十六进制数字时保留原样
+pub(crate) fn percent_decode(input: &str, plus_as_space: bool) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = |offset: usize| bytes.get(i + offset).and_then(|&b| (b as char).to_digit(16));
+        match bytes[i] {
+            b'%' => match (hex(1), hex(2)) {
+                (Some(high), Some(low)) => {
+                    decoded.push((high * 16 + low) as u8);
+                    i += 3;
+                    continue;
+                }
+                _ => decoded.push(b'%'),
+            },
+            b'+' if plus_as_space => decoded.push(b' '),
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// 解析 URI 查询参数，键转为小写
+fn parse_query(query: &str, plus_as_space: bool) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_ascii_lowercase(), percent_decode(value, plus_as_space))
+        })
+        .collect()
+}
+
+/// 查找查询参数
+fn query_value(params: &[(String, String)], key: &str) -> Option<String> {
+    params
+        .iter()
+        .find(|(k, _)| k == key)
+        .and_then(|(_, v)| non_empty(v.clone()))
+}
+
+/// 解析地理位置
+fn parse_geo(rest: &str) -> Option<GeoLocation> {
+    let (coords, query) = match rest.split_once('?') {
+        Some((coords, query)) => (coords, Some(query)),
+        None => (rest, None),
+    };
+    // 去掉 `;crs=...;u=...` 等参数
+    let coords = coords.split(';').next().unwrap_or(coords);
+
+    let mut numbers = coords.split(',').map(|n| n.trim().parse::<f64>());
+    let latitude = numbers.next()?.ok()?;
+    let longitude = numbers.next()?.ok()?;
+    let altitude = match numbers.next() {
+        Some(n) => Some(n.ok()?),
+        None => None,
+    };
+
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return None;
+    }
+
+    let query = query.and_then(|q| query_value(&parse_query(q, true), "q"));
+
+    Some(GeoLocation { latitude, longitude, altitude, query })
+}
+
+/// 解析 `SMSTO:number:body`
+fn parse_smsto(rest: &str) -> Option<SmsMessage> {
+    let (number, body) = match rest.split_once(':') {
+        Some((number, body)) => (number, non_empty(body.to_string())),
+        None => (rest, None),
+    };
+    let number = non_empty(number.trim().to_string())?;
+    Some(SmsMessage { number, body })
+}
+
+/// 解析 `sms:number?body=...`（也兼容 `sms:number:body`）
+fn parse_sms_uri(rest: &str) -> Option<SmsMessage> {
+    let (number, query) = match rest.split_once('?') {
+        Some((number, query)) => (number, Some(query)),
+        None => (rest, None),
+    };
+
+    if query.is_none() && number.contains(':') {
+        return parse_smsto(number);
+    }
+
+    let number = non_empty(percent_decode(number.trim(), false))?;
+    let body = query.and_then(|q| query_value(&parse_query(q, true), "body"));
+    Some(SmsMessage { number, body })
+}
+
+/// 解析 `mailto:address?subject=...&body=...`
+fn parse_mailto(rest: &str) -> Option<EmailMessage> {
+    let (to, query) = match rest.split_once('?') {
+        Some((to, query)) => (to, Some(query)),
+        None => (rest, None),
+    };
+    // mailto 中 `+` 是合法的地址字符，不能解码为空格
+    let params = query.map(|q| parse_query(q, false)).unwrap_or_default();
+
+    Some(EmailMessage {
+        to: non_empty(percent_decode(to, false))?,
+        subject: query_value(&params, "subject"),
+        body: query_value(&params, "body"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("bank%2Ecom", false), "bank.com");
+        assert_eq!(percent_decode("a+b%20c", true), "a b c");
+        assert_eq!(percent_decode("a+b", false), "a+b");
+        assert_eq!(percent_decode("%41", false), "A");
+        // 只接受两位十六进制数字，符号、不完整的编码保留原样
+        assert_eq!(percent_decode("%+1%-f", false), "%+1%-f");
+        assert_eq!(percent_decode("100%", false), "100%");
+        assert_eq!(percent_decode("%4", false), "%4");
+    }
+
+    #[test]
+    fn test_parse_wifi() {
+        let payload = parse(r#"WIFI:T:WPA;S:office\;5G;P:pa\:ss\\word;H:true;;"#);
+        assert_eq!(
+            payload,
+            Some(QrPayload::Wifi(WifiConfig {
+                ssid: "office;5G".to_string(),
+                auth: "WPA".to_string(),
+                password: Some(r"pa:ss\word".to_string()),
+                hidden: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_wifi_without_password() {
+        let Some(QrPayload::Wifi(wifi)) = parse("WIFI:S:guest;;") else {
+            panic!("应解析为 Wi-Fi");
+        };
+        assert_eq!(wifi.auth, "nopass");
+        assert_eq!(wifi.password, None);
+        assert!(!wifi.hidden);
+    }
+
+    #[test]
+    fn test_parse_mecard() {
+        let Some(QrPayload::Contact(contact)) =
+            parse(r"MECARD:N:Doe,John;TEL:+1234;EMAIL:john@example.com;NOTE:a\;b;;")
+        else {
+            panic!("应解析为联系人");
+        };
+        assert_eq!(contact.name.as_deref(), Some("John Doe"));
+        assert_eq!(contact.phones, vec!["+1234"]);
+        assert_eq!(contact.emails, vec!["john@example.com"]);
+        assert_eq!(contact.note.as_deref(), Some("a;b"));
+    }
+
+    #[test]
+    fn test_parse_mecard_cjk_name() {
+        let Some(QrPayload::Contact(contact)) = parse("MECARD:N:张,三;;") else {
+            panic!("应解析为联系人");
+        };
+        assert_eq!(contact.name.as_deref(), Some("张三"));
+    }
+
+    #[test]
+    fn test_parse_vcard() {
+        let vcard = "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;Jane;;;\r\nORG:Acme\\, Inc.;R&D\r\n\
+                     item1.TEL;TYPE=CELL:+86 138\r\nEMAIL:jane@example.com\r\n\
+                     ADR;TYPE=WORK:;;1 Main St;Springfield;;12345;US\r\nNOTE:line1\\nli\r\n ne2\r\nEND:VCARD";
+        let Some(QrPayload::Contact(contact)) = parse(vcard) else {
+            panic!("应解析为联系人");
+        };
+        assert_eq!(contact.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(contact.organization.as_deref(), Some("Acme, Inc. R&D"));
+        assert_eq!(contact.phones, vec!["+86 138"]);
+        assert_eq!(contact.address.as_deref(), Some("1 Main St, Springfield, 12345, US"));
+        assert_eq!(contact.note.as_deref(), Some("line1\nline2"));
+    }
+
+    #[test]
+    fn test_parse_vcard_prefers_formatted_name() {
+        let vcard = "BEGIN:VCARD\nN:Doe;Jane\nFN:Dr. Jane Doe\nEND:VCARD";
+        let Some(QrPayload::Contact(contact)) = parse(vcard) else {
+            panic!("应解析为联系人");
+        };
+        assert_eq!(contact.name.as_deref(), Some("Dr. Jane Doe"));
+    }
+
+    #[test]
+    fn test_parse_geo() {
+        assert_eq!(
+            parse("geo:39.9042,116.4074,43.5?q=Tian%27anmen+Square"),
+            Some(QrPayload::Geo(GeoLocation {
+                latitude: 39.9042,
+                longitude: 116.4074,
+                altitude: Some(43.5),
+                query: Some("Tian'anmen Square".to_string()),
+            }))
+        );
+        assert_eq!(parse("geo:91,0"), None);
+        assert_eq!(parse("geo:abc,def"), None);
+    }
+
+    #[test]
+    fn test_parse_sms() {
+        assert_eq!(
+            parse("SMSTO:+8610086:余额查询"),
+            Some(QrPayload::Sms(SmsMessage {
+                number: "+8610086".to_string(),
+                body: Some("余额查询".to_string()),
+            }))
+        );
+        assert_eq!(
+            parse("sms:+1555?body=hello%20world"),
+            Some(QrPayload::Sms(SmsMessage {
+                number: "+1555".to_string(),
+                body: Some("hello world".to_string()),
+            }))
+        );
+        assert_eq!(parse("sms:"), None);
+    }
+
+    #[test]
+    fn test_parse_vevent() {
+        let ics = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Team sync\\, weekly\n\
+                   DTSTART;TZID=Asia/Shanghai:20240105T093000\nDTEND:20240105T103000Z\n\
+                   LOCATION:Room 1\nEND:VEVENT\nEND:VCALENDAR";
+        assert_eq!(
+            parse(ics),
+            Some(QrPayload::Event(CalendarEvent {
+                summary: Some("Team sync, weekly".to_string()),
+                start: Some("2024-01-05T09:30:00".to_string()),
+                end: Some("2024-01-05T10:30:00Z".to_string()),
+                location: Some("Room 1".to_string()),
+                description: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_format_ical_date_only() {
+        assert_eq!(format_ical_datetime("20241231"), "2024-12-31");
+        assert_eq!(format_ical_datetime("not-a-date"), "not-a-date");
+    }
+
+    #[test]
+    fn test_parse_email() {
+        assert_eq!(
+            parse("MATMSG:TO:a@example.com;SUB:Hi;BODY:See you\\; bye;;"),
+            Some(QrPayload::Email(EmailMessage {
+                to: "a@example.com".to_string(),
+                subject: Some("Hi".to_string()),
+                body: Some("See you; bye".to_string()),
+            }))
+        );
+        assert_eq!(
+            parse("mailto:a+tag@example.com?subject=Hello%20there&body=1+1"),
+            Some(QrPayload::Email(EmailMessage {
+                to: "a+tag@example.com".to_string(),
+                subject: Some("Hello there".to_string()),
+                body: Some("1+1".to_string()),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_plain_text_is_not_payload() {
+        assert_eq!(parse("https://example.com"), None);
+        assert_eq!(parse("这是普通文本"), None);
+    }
+}
@@ -0,0 +1,358 @@
+//! 二维码识别的局部图像预处理
+//! 自适应二值化（Otsu、Sauvola）、形态学闭运算、中值去噪和颜色通道提取
+
+use image::{DynamicImage, GrayImage, Luma};
+
+/// 二值化后的深色像素值
+const DARK: u8 = 0;
+/// 二值化后的浅色像素值
+const LIGHT: u8 = 255;
+
+/// 颜色通道
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+}
+
+/// 计算 Otsu 全局阈值（使类间方差最大的灰度值）
+pub fn otsu_threshold(gray: &GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in gray.pixels() {
+        histogram[pixel[0] as usize] += 1;
+    }
+
+    let total: u64 = histogram.iter().sum();
+    let sum_all: f64 = histogram.iter().enumerate().map(|(v, &n)| v as f64 * n as f64).sum();
+
+    let mut best_threshold = 0u8;
+    let mut best_variance = 0.0;
+    let mut weight_dark = 0u64;
+    let mut sum_dark = 0.0;
+    for (value, &count) in histogram.iter().enumerate() {
+        weight_dark += count;
+        if weight_dark == 0 {
+            continue;
+        }
+        let weight_light = total - weight_dark;
+        if weight_light == 0 {
+            break;
+        }
+        sum_dark += value as f64 * count as f64;
+        let mean_dark = sum_dark / weight_dark as f64;
+        let mean_light = (sum_all - sum_dark) / weight_light as f64;
+        let variance = weight_dark as f64 * weight_light as f64 * (mean_dark - mean_light).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best_threshold = value as u8;
+        }
+    }
+    best_threshold
+}
+
+/// Otsu 全局二值化（不大于阈值的像素为深色）
+pub fn binarize_otsu(gray: &GrayImage) -> GrayImage {
+    let threshold = otsu_threshold(gray);
+    GrayImage::from_fn(gray.width(), gray.height(), |x, y| {
+        Luma([if gray.get_pixel(x, y)[0] <= threshold { DARK } else { LIGHT }])
+    })
+}
+
+/// Sauvola 默认窗口边长：取短边的 1/8，至少 15 像素，保证窗口能覆盖定位图形中心
+pub fn sauvola_window(width: u32, height: u32) -> u32 {
+    (width.min(height) / 8).max(15) | 1
+}
+
+/// Sauvola 局部自适应二值化，适用于光照不均（渐变、阴影、照片翻拍）的图像
+///
+/// 阈值 T = m · (1 + k · (s / 128 − 1))，m、s 为窗口内的均值和标准差
+pub fn binarize_sauvola(gray: &GrayImage, window: u32, k: f64) -> GrayImage {
+    const DYNAMIC_RANGE: f64 = 128.0;
+
+    let (width, height) = gray.dimensions();
+    let (w, h) = (width as usize, height as usize);
+
+    // 积分图（多一行一列，便于计算窗口和）
+    let stride = w + 1;
+    let mut sum = vec![0u64; stride * (h + 1)];
+    let mut sum_sq = vec![0u64; stride * (h + 1)];
+    for y in 0..h {
+        let mut row_sum = 0u64;
+        let mut row_sum_sq = 0u64;
+        for x in 0..w {
+            let v = gray.get_pixel(x as u32, y as u32)[0] as u64;
+            row_sum += v;
+            row_sum_sq += v * v;
+            sum[(y + 1) * stride + x + 1] = sum[y * stride + x + 1] + row_sum;
+            sum_sq[(y + 1) * stride + x + 1] = sum_sq[y * stride + x + 1] + row_sum_sq;
+        }
+    }
+    let rect = |table: &[u64], x0: usize, y0: usize, x1: usize, y1: usize| {
+        table[y1 * stride + x1] + table[y0 * stride + x0] - table[y0 * stride + x1] - table[y1 * stride + x0]
+    };
+
+    let half = (window / 2) as usize;
+    GrayImage::from_fn(width, height, |x, y| {
+        let (x, y) = (x as usize, y as usize);
+        let x0 = x.saturating_sub(half);
+        let y0 = y.saturating_sub(half);
+        let x1 = (x + half + 1).min(w);
+        let y1 = (y + half + 1).min(h);
+        let area = ((x1 - x0) * (y1 - y0)) as f64;
+
+        let mean = rect(&sum, x0, y0, x1, y1) as f64 / area;
+        let variance = (rect(&sum_sq, x0, y0, x1, y1) as f64 / area - mean * mean).max(0.0);
+        let threshold = mean * (1.0 + k * (variance.sqrt() / DYNAMIC_RANGE - 1.0));
+
+        let value = gray.get_pixel(x as u32, y as u32)[0] as f64;
+        Luma([if value <= threshold { DARK } else { LIGHT }])
+    })
+}
+
+/// 对二值图像做形态学闭运算（深色为前景），填补模块内部的细小断裂和空洞
+pub fn morph_close(binary: &GrayImage, radius: u32) -> GrayImage {
+    // 深色前景的膨胀即最小值滤波，腐蚀即最大值滤波
+    let dilated = rank_filter(binary, radius, |window| window.iter().copied().min().unwrap_or(LIGHT));
+    rank_filter(&dilated, radius, |window| window.iter().copied().max().unwrap_or(DARK))
+}
+
+/// 中值滤波去噪（去除椒盐噪声、视频压缩噪点）
+pub fn median_filter(gray: &GrayImage, radius: u32) -> GrayImage {
+    let mut buffer = Vec::new();
+    neighborhood_filter(gray, radius, |window| {
+        buffer.clear();
+        buffer.extend_from_slice(window);
+        let mid = buffer.len() / 2;
+        *buffer.select_nth_unstable(mid).1
+    })
+}
+
+/// 可分离的排序滤波：先按行后按列各做一次一维滤波（最小值、最大值滤波适用）
+fn rank_filter(gray: &GrayImage, radius: u32, pick: impl Fn(&[u8]) -> u8) -> GrayImage {
+    let (width, height) = gray.dimensions();
+    let (w, r) = (width as usize, radius as usize);
+
+    // 行内窗口在内存中连续，直接取切片
+    let raw = gray.as_raw();
+    let horizontal = GrayImage::from_fn(width, height, |x, y| {
+        let row = &raw[y as usize * w..(y as usize + 1) * w];
+        let x = x as usize;
+        Luma([pick(&row[x.saturating_sub(r)..(x + r + 1).min(w)])])
+    });
+
+    // 列内窗口复用同一个缓冲区
+    let mut window = Vec::with_capacity(2 * r + 1);
+    GrayImage::from_fn(width, height, |x, y| {
+        window.clear();
+        let y0 = y.saturating_sub(radius);
+        let y1 = (y + radius).min(height - 1);
+        window.extend((y0..=y1).map(|ny| horizontal.get_pixel(x, ny)[0]));
+        Luma([pick(&window)])
+    })
+}
+
+/// 二维邻域滤波（边界处只取图像内的像素）
+fn neighborhood_filter(gray: &GrayImage, radius: u32, mut pick: impl FnMut(&[u8]) -> u8) -> GrayImage {
+    let (width, height) = gray.dimensions();
+    let r = radius as i64;
+    let mut window = Vec::with_capacity(((2 * r + 1) * (2 * r + 1)) as usize);
+
+    let mut output = GrayImage::new(width, height);
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            window.clear();
+            for ny in (y - r).max(0)..=(y + r).min(height as i64 - 1) {
+                for nx in (x - r).max(0)..=(x + r).min(width as i64 - 1) {
+                    window.push(gray.get_pixel(nx as u32, ny as u32)[0]);
+                }
+            }
+            output.put_pixel(x as u32, y as u32, Luma([pick(&window)]));
+        }
+    }
+    output
+}
+
+/// 提取单个颜色通道作为灰度图（彩色二维码在亮度上对比度低，但在某个通道上对比明显）
+pub fn extract_channel(image: &DynamicImage, channel: Channel) -> GrayImage {
+    let rgb = image.to_rgb8();
+    let index = match channel {
+        Channel::Red => 0,
+        Channel::Green => 1,
+        Channel::Blue => 2,
+    };
+    GrayImage::from_fn(rgb.width(), rgb.height(), |x, y| Luma([rgb.get_pixel(x, y)[index]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_scanner::{scan_qr_codes, PreprocessMethod};
+    use crate::scan_pipeline::ScanPipeline;
+    use image::{Rgb, RgbImage};
+    use qrcode::{Color, EcLevel, QrCode};
+
+    /// 每个模块的像素数
+    const MODULE_PX: u32 = 5;
+    /// 静区宽度（模块数）
+    const QUIET_ZONE: u32 = 4;
+    const MESSAGE: &str = "https://example.com/glimpse?corpus=1";
+
+    /// 测试样本用的二维码模块矩阵（深色为 true）
+    fn modules() -> (usize, Vec<bool>) {
+        let code = QrCode::with_error_correction_level(MESSAGE, EcLevel::M).unwrap();
+        let colors = code.to_colors().iter().map(|c| *c == Color::Dark).collect();
+        (code.width(), colors)
+    }
+
+    /// 按模块渲染图像，`paint` 参数为（像素坐标, 图像边长, 是否深色模块）
+    fn render(paint: impl Fn(u32, u32, u32, bool) -> Rgb<u8>) -> RgbImage {
+        let (size, colors) = modules();
+        let side = (size as u32 + 2 * QUIET_ZONE) * MODULE_PX;
+        RgbImage::from_fn(side, side, |x, y| {
+            let col = (x / MODULE_PX) as i64 - QUIET_ZONE as i64;
+            let row = (y / MODULE_PX) as i64 - QUIET_ZONE as i64;
+            let inside = (0..size as i64).contains(&row) && (0..size as i64).contains(&col);
+            let dark = inside && colors[row as usize * size + col as usize];
+            paint(x, y, side, dark)
+        })
+    }
+
+    fn gray(value: u8) -> Rgb<u8> {
+        Rgb([value, value, value])
+    }
+
+    /// 伪随机数（线性同余），保证测试样本可复现
+    fn lcg(state: &mut u64) -> u32 {
+        *state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (*state >> 33) as u32
+    }
+
+    /// 从左到右由暗到亮的光照渐变
+    fn uneven_lighting() -> GrayImage {
+        let image = render(|x, _, side, dark| {
+            let light = 0.15 + 0.85 * x as f64 / side as f64;
+            gray(((if dark { 40.0 } else { 255.0 }) * light) as u8)
+        });
+        DynamicImage::ImageRgb8(image).to_luma8()
+    }
+
+    /// 1/3 的像素被椒盐噪声替换
+    fn salt_and_pepper() -> GrayImage {
+        let clean = DynamicImage::ImageRgb8(render(|_, _, _, dark| gray(if dark { 0 } else { 255 }))).to_luma8();
+        let mut state = 42;
+        let mut noisy = clean.clone();
+        for pixel in noisy.pixels_mut() {
+            if lcg(&mut state).is_multiple_of(3) {
+                pixel[0] = if lcg(&mut state).is_multiple_of(2) { 0 } else { 255 };
+            }
+        }
+        noisy
+    }
+
+    /// 深色模块内部带有 1 像素宽的浅色十字裂纹（打印磨损、隔行视频）
+    fn cracked_modules() -> GrayImage {
+        let image = render(|x, y, _, dark| {
+            let crack = x % MODULE_PX == MODULE_PX / 2 || y % MODULE_PX == MODULE_PX / 2;
+            gray(if dark && !crack { 0 } else { 255 })
+        });
+        DynamicImage::ImageRgb8(image).to_luma8()
+    }
+
+    /// 亮度相同（按 BT.709 和 BT.601 两种灰度公式都相同）、只在蓝色通道上有对比的彩色二维码
+    fn colored() -> DynamicImage {
+        DynamicImage::ImageRgb8(render(|_, _, _, dark| {
+            if dark { Rgb([200, 120, 20]) } else { Rgb([112, 126, 220]) }
+        }))
+    }
+
+    /// 水平镜像的二维码（前置摄像头画面、镜像投影）
+    fn mirrored() -> DynamicImage {
+        DynamicImage::ImageRgb8(render(|_, _, _, dark| gray(if dark { 0 } else { 255 }))).fliph()
+    }
+
+    #[test]
+    fn test_otsu_threshold_separates_bimodal_histogram() {
+        let image = GrayImage::from_fn(20, 10, |x, _| Luma([if x < 10 { 30 } else { 200 }]));
+        let threshold = otsu_threshold(&image);
+        assert!((30..200).contains(&threshold));
+        let binary = binarize_otsu(&image);
+        assert_eq!(binary.get_pixel(0, 0)[0], DARK);
+        assert_eq!(binary.get_pixel(19, 0)[0], LIGHT);
+    }
+
+    #[test]
+    fn test_sauvola_window_is_odd() {
+        assert_eq!(sauvola_window(100, 100), 15);
+        assert_eq!(sauvola_window(1920, 1080), 135);
+        assert_eq!(sauvola_window(800, 1600) % 2, 1);
+    }
+
+    #[test]
+    fn test_morph_close_fills_gap() {
+        // 深色方块中间有一条 1 像素的浅色缝隙
+        let image = GrayImage::from_fn(9, 9, |x, y| {
+            let inside = (2..7).contains(&x) && (2..7).contains(&y);
+            Luma([if inside && x != 4 { DARK } else { LIGHT }])
+        });
+        let closed = morph_close(&image, 1);
+        assert_eq!(closed.get_pixel(4, 4)[0], DARK);
+        assert_eq!(closed.get_pixel(0, 0)[0], LIGHT);
+    }
+
+    #[test]
+    fn test_median_filter_removes_isolated_pixel() {
+        let mut image = GrayImage::from_pixel(5, 5, Luma([LIGHT]));
+        image.put_pixel(2, 2, Luma([DARK]));
+        assert_eq!(median_filter(&image, 1).get_pixel(2, 2)[0], LIGHT);
+    }
+
+    #[test]
+    fn test_extract_channel() {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(2, 2, Rgb([10, 20, 30])));
+        assert_eq!(extract_channel(&image, Channel::Red).get_pixel(0, 0)[0], 10);
+        assert_eq!(extract_channel(&image, Channel::Green).get_pixel(1, 1)[0], 20);
+        assert_eq!(extract_channel(&image, Channel::Blue).get_pixel(0, 1)[0], 30);
+    }
+
+    /// 用完整的识别流程（rqrr、rxing 与流水线）扫描样本，判断能否识别出测试消息
+    fn scans(image: &DynamicImage, pipeline: &ScanPipeline) -> bool {
+        scan_qr_codes(image, pipeline).is_ok_and(|report| report.results.iter().any(|r| r.content == MESSAGE))
+    }
+
+    /// 测试集：只使用原有预处理方法（缩放、对比度、亮度、锐化、反色）的流水线无法识别，
+    /// 加入局部预处理方法的默认流水线全部可以识别
+    #[test]
+    fn test_corpus_recall_improvement() {
+        let corpus: Vec<(&str, DynamicImage)> = vec![
+            ("光照不均", DynamicImage::ImageLuma8(uneven_lighting())),
+            ("椒盐噪声", DynamicImage::ImageLuma8(salt_and_pepper())),
+            ("模块裂纹", DynamicImage::ImageLuma8(cracked_modules())),
+            ("彩色低亮度对比", colored()),
+            ("水平镜像", mirrored()),
+        ];
+
+        let full = ScanPipeline::default();
+        let mut baseline = full.clone();
+        baseline.strategies.retain(|s| {
+            matches!(
+                s.method,
+                PreprocessMethod::None
+                    | PreprocessMethod::ContrastBoost
+                    | PreprocessMethod::Brightness
+                    | PreprocessMethod::Sharpen
+                    | PreprocessMethod::Invert
+            )
+        });
+
+        let results: Vec<(&str, bool, bool)> =
+            corpus.iter().map(|(name, image)| (*name, scans(image, &baseline), scans(image, &full))).collect();
+        let before = results.iter().filter(|(_, before, _)| *before).count();
+        let after = results.iter().filter(|(_, _, after)| *after).count();
+        println!("测试集召回率: 原有预处理 {}/{}，默认流水线 {}/{}", before, corpus.len(), after, corpus.len());
+        for (name, before, after) in &results {
+            assert!(!before, "{} 不应能被原有预处理识别", name);
+            assert!(after, "{} 应能被默认流水线识别", name);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! 二维码生成
+//! 将文本编码为二维码，同时输出 PNG 图像和 SVG 矢量图，可指定纠错等级、版本、静区和颜色
+
+use image::{DynamicImage, Rgb, RgbImage};
+use qrcode::{Color, EcLevel, QrCode, Version};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+use crate::error::Error;
+use crate::qr_symbol::EccLevel;
+use crate::screenshot::image_to_png_bytes;
+
+/// 最大模块像素数（避免生成过大的图像）
+pub const MAX_MODULE_SIZE: u32 = 64;
+/// 最大静区宽度（模块数）
+pub const MAX_QUIET_ZONE: u32 = 16;
+/// PNG 图像的最大边长（像素），版本 40 配合最大模块尺寸时图像可达上万像素
+pub const MAX_IMAGE_SIZE: u32 = 4096;
+
+/// 二维码生成选项，未指定的字段使用默认值
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QrGenerateOptions {
+    /// 纠错等级
+    pub ecc_level: EccLevel,
+    /// 版本（1~40），None 表示按内容长度自动选择最小版本
+    pub version: Option<u8>,
+    /// 静区宽度（模块数），标准要求至少 4
+    pub quiet_zone: u32,
+    /// 每个模块的像素数（仅影响 PNG 和 SVG 的显示尺寸）
+    pub module_size: u32,
+    /// 前景色（深色模块），格式为 #RRGGBB 或 #RGB
+    pub foreground: String,
+    /// 背景色，格式同上
+    pub background: String,
+}
+
+impl Default for QrGenerateOptions {
+    fn default() -> Self {
+        Self {
+            ecc_level: EccLevel::M,
+            version: None,
+            quiet_zone: 4,
+            module_size: 8,
+            foreground: "#000000".to_string(),
+            background: "#ffffff".to_string(),
+        }
+    }
+}
+
+/// 生成结果
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedQr {
+    /// 实际使用的版本
+    pub version: u8,
+    /// 每边模块数（不含静区）
+    pub modules: usize,
+    /// PNG 图像数据
+    pub png: Vec<u8>,
+    /// SVG 文本
+    pub svg: String,
+}
+
+/// 将文本编码为二维码
+pub fn generate_qr(text: &str, options: &QrGenerateOptions) -> Result<GeneratedQr, Error> {
+    if text.is_empty() {
+        return Err(Error::Encode("内容不能为空".to_string()));
+    }
+    if options.module_size == 0 || options.module_size > MAX_MODULE_SIZE {
+        return Err(Error::Encode(format!("模块尺寸需在 1~{} 像素之间", MAX_MODULE_SIZE)));
+    }
+    if options.quiet_zone > MAX_QUIET_ZONE {
+        return Err(Error::Encode(format!("静区宽度不能超过 {} 个模块", MAX_QUIET_ZONE)));
+    }
+    let foreground = parse_color(&options.foreground)?;
+    let background = parse_color(&options.background)?;
+
+    let ecc = match options.ecc_level {
+        EccLevel::L => EcLevel::L,
+        EccLevel::M => EcLevel::M,
+        EccLevel::Q => EcLevel::Q,
+        EccLevel::H => EcLevel::H,
+    };
+    let code = match options.version {
+        Some(version @ 1..=40) => QrCode::with_version(text, Version::Normal(version as i16), ecc)
+            .map_err(|e| Error::Encode(format!("内容超出版本 {} 的容量: {}", version, e)))?,
+        Some(version) => return Err(Error::Encode(format!("版本需在 1~40 之间，当前为 {}", version))),
+        None => QrCode::with_error_correction_level(text, ecc)
+            .map_err(|e| Error::Encode(format!("二维码编码失败: {}", e)))?,
+    };
+    let version = match code.version() {
+        Version::Normal(version) => version as u8,
+        Version::Micro(_) => unreachable!("仅生成标准二维码"),
+    };
+
+    let modules = code.width();
+    let side = (modules as u32 + 2 * options.quiet_zone) * options.module_size;
+    if side > MAX_IMAGE_SIZE {
+        return Err(Error::Encode(format!(
+            "图像边长 {} 像素超过上限 {} 像素，请减小模块尺寸或静区宽度",
+            side, MAX_IMAGE_SIZE
+        )));
+    }
+    let dark: Vec<bool> = code.to_colors().iter().map(|c| *c == Color::Dark).collect();
+
+    let image = render_image(modules, &dark, options, foreground, background);
+    let png = image_to_png_bytes(&DynamicImage::ImageRgb8(image))?;
+    let svg = render_svg(modules, &dark, options);
+
+    Ok(GeneratedQr { version, modules, png, svg })
+}
+
+/// 解析 #RRGGBB 或 #RGB 格式的颜色
+fn parse_color(value: &str) -> Result<Rgb<u8>, Error> {
+    let invalid = || Error::Encode(format!("无效的颜色值: {}", value));
+    let hex = value.trim().strip_prefix('#').ok_or_else(invalid)?;
+    if !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+    match hex.len() {
+        6 => Ok(Rgb([channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?])),
+        3 => {
+            // #RGB 中每位重复一次，如 #f80 即 #ff8800
+            let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+            Ok(Rgb([short(0)?, short(1)?, short(2)?]))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// 按模块渲染位图
+fn render_image(
+    modules: usize,
+    dark: &[bool],
+    options: &QrGenerateOptions,
+    foreground: Rgb<u8>,
+    background: Rgb<u8>,
+) -> RgbImage {
+    let scale = options.module_size;
+    let quiet = options.quiet_zone as i64;
+    let side = (modules as u32 + 2 * options.quiet_zone) * scale;
+    RgbImage::from_fn(side, side, |x, y| {
+        let col = (x / scale) as i64 - quiet;
+        let row = (y / scale) as i64 - quiet;
+        let inside = (0..modules as i64).contains(&row) && (0..modules as i64).contains(&col);
+        if inside && dark[row as usize * modules + col as usize] {
+            foreground
+        } else {
+            background
+        }
+    })
+}
+
+/// 渲染 SVG：背景矩形加一条由所有深色模块组成的路径
+fn render_svg(modules: usize, dark: &[bool], options: &QrGenerateOptions) -> String {
+    let quiet = options.quiet_zone as usize;
+    let total = modules + 2 * quiet;
+    let pixels = total as u32 * options.module_size;
+
+    let mut path = String::new();
+    for row in 0..modules {
+        for col in 0..modules {
+            if dark[row * modules + col] {
+                let _ = write!(path, "M{} {}h1v1h-1z", col + quiet, row + quiet);
+            }
+        }
+    }
+
+    format!(
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{px}\" height=\"{px}\" ",
+            "viewBox=\"0 0 {total} {total}\" shape-rendering=\"crispEdges\">",
+            "<rect width=\"{total}\" height=\"{total}\" fill=\"{bg}\"/>",
+            "<path d=\"{path}\" fill=\"{fg}\"/></svg>"
+        ),
+        px = pixels,
+        total = total,
+        bg = options.background.trim(),
+        fg = options.foreground.trim(),
+        path = path,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_scanner::scan_qr_codes;
+    use crate::scan_pipeline::ScanPipeline;
+
+    /// 生成二维码后重新识别，返回识别到的内容和符号信息
+    fn round_trip(text: &str, options: &QrGenerateOptions) -> (GeneratedQr, String, usize, EccLevel) {
+        let generated = generate_qr(text, options).unwrap();
+        let image = image::load_from_memory(&generated.png).unwrap();
+        let report = scan_qr_codes(&image, &ScanPipeline::default()).unwrap();
+        assert_eq!(report.results.len(), 1, "应识别到 1 个二维码");
+        let result = &report.results[0];
+        let symbol = result.symbol.as_ref().expect("二维码应包含符号信息");
+        (generated, result.content.clone(), symbol.version, symbol.ecc_level)
+    }
+
+    #[test]
+    fn test_round_trip_default() {
+        let text = "https://example.com/glimpse?from=generator";
+        let (generated, content, version, ecc) = round_trip(text, &QrGenerateOptions::default());
+        assert_eq!(content, text);
+        assert_eq!(version, generated.version as usize);
+        assert_eq!(ecc, EccLevel::M);
+        assert_eq!(generated.modules, 17 + 4 * generated.version as usize);
+    }
+
+    #[test]
+    fn test_round_trip_options() {
+        let text = "WIFI:T:WPA;S:灵视办公室;P:secret;;";
+        let options = QrGenerateOptions {
+            ecc_level: EccLevel::H,
+            version: Some(10),
+            quiet_zone: 6,
+            module_size: 5,
+            foreground: "#1a237e".to_string(),
+            background: "#fff8e1".to_string(),
+        };
+        let (generated, content, version, ecc) = round_trip(text, &options);
+        assert_eq!(content, text);
+        assert_eq!((version, generated.version), (10, 10));
+        assert_eq!(ecc, EccLevel::H);
+
+        let image = image::load_from_memory(&generated.png).unwrap();
+        let side = (generated.modules as u32 + 12) * 5;
+        assert_eq!((image.width(), image.height()), (side, side));
+        assert_eq!(image.to_rgb8().get_pixel(0, 0), &Rgb([0xff, 0xf8, 0xe1]));
+    }
+
+    #[test]
+    fn test_svg_output() {
+        let options = QrGenerateOptions { quiet_zone: 2, module_size: 10, ..Default::default() };
+        let generated = generate_qr("hello", &options).unwrap();
+        let total = generated.modules + 4;
+        assert!(generated.svg.starts_with("<svg"));
+        assert!(generated.svg.contains(&format!("viewBox=\"0 0 {} {}\"", total, total)));
+        assert!(generated.svg.contains(&format!("width=\"{}\"", total * 10)));
+        assert!(generated.svg.contains("fill=\"#000000\""));
+        // 左上角定位图案的第一个模块位于静区之后
+        assert!(generated.svg.contains("M2 2h1v1h-1z"));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let defaults = QrGenerateOptions::default();
+        assert!(generate_qr("", &defaults).is_err());
+        assert!(generate_qr("hello", &QrGenerateOptions { version: Some(41), ..defaults.clone() }).is_err());
+        assert!(generate_qr("hello", &QrGenerateOptions { module_size: 0, ..defaults.clone() }).is_err());
+        // 版本 40 + 64 像素模块的边长为 (177 + 8) × 64 像素，超过上限
+        let oversized = QrGenerateOptions { version: Some(40), module_size: MAX_MODULE_SIZE, ..defaults.clone() };
+        assert!(generate_qr("hello", &oversized).is_err());
+        assert!(generate_qr(&"x".repeat(200), &QrGenerateOptions { version: Some(1), ..defaults.clone() }).is_err());
+        assert!(generate_qr("hello", &QrGenerateOptions { foreground: "black".to_string(), ..defaults }).is_err());
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#1A2b3C").unwrap(), Rgb([0x1a, 0x2b, 0x3c]));
+        assert_eq!(parse_color("#f80").unwrap(), Rgb([0xff, 0x88, 0x00]));
+        assert!(parse_color("#12345").is_err());
+        assert!(parse_color("#gg0000").is_err());
+        assert!(parse_color("000000").is_err());
+    }
+}
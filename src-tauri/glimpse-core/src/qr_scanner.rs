@@ -0,0 +1,1107 @@
+//! 二维码及条码识别模块
+//! 二维码由 rqrr 检测网格、自行解析符号；Micro QR、rMQR 及其他码制由 rxing 识别
+
+use base64::Engine;
+use image::{DynamicImage, GrayImage, ImageBuffer};
+use rqrr::{BitGrid, PreparedImage};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use rxing::{DecodeHintValue, DecodeHints};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::charset;
+use crate::error::Error;
+use crate::image_file::ImageFrame;
+use crate::payload::{self, QrPayload};
+use crate::preprocess::{self, Channel};
+use crate::qr_symbol::{self, EccLevel, Segment, StructuredAppend};
+use crate::scan_pipeline::{ScanDiagnostics, ScanPipeline, ScanStrategy, StrategyDiagnostics};
+use crate::screenshot::ScreenCapture;
+use crate::structured_append::{self, StructuredAppendInfo};
+use crate::url_risk::{self, UrlRisk};
+
+/// 二维码识别结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QrCodeResult {
+    /// 二维码内容
+    pub content: String,
+    /// 二维码类型（URL、文本等）
+    pub qr_type: QrCodeType,
+    /// 结构化载荷（Wi-Fi、联系人、地理位置等，无法解析时为 None）
+    pub payload: Option<QrPayload>,
+    /// 链接钓鱼风险分析（内容不是链接时为 None）
+    pub risk: Option<UrlRisk>,
+    /// 码制（QR、EAN-13、Code 128 等）
+    pub format: BarcodeFormat,
+    /// 码的角点坐标（原图像素坐标，二维码为左上、右上、右下、左下四个角）
+    pub points: Vec<Point>,
+    /// 码所在的屏幕（非屏幕来源时为 None）
+    pub screen: Option<ScreenOrigin>,
+    /// 码所在的帧序号（仅多帧 GIF / 多页 TIFF，从 0 开始）
+    pub frame: Option<usize>,
+    /// 二维码符号元数据（仅 QR 码）
+    pub symbol: Option<QrSymbolInfo>,
+    /// 结构化追加拼接状态（仅由多个符号拼接的消息）
+    pub structured_append: Option<StructuredAppendInfo>,
+    /// 相同内容出现的次数（不同位置的相同码合并为一条结果；保留每处位置时为 1）
+    pub count: usize,
+    /// 相同内容的所有位置，第一项与 `points`、`screen`、`frame` 一致
+    pub locations: Vec<SymbolLocation>,
+}
+
+/// 码在图像中的一处位置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolLocation {
+    /// 角点坐标（同 [`QrCodeResult::points`]）
+    pub points: Vec<Point>,
+    pub screen: Option<ScreenOrigin>,
+    pub frame: Option<usize>,
+}
+
+/// 二维码符号元数据
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QrSymbolInfo {
+    /// 版本（1~40）
+    pub version: usize,
+    /// 纠错等级
+    pub ecc_level: EccLevel,
+    /// 掩码图案（0~7）
+    pub mask: u8,
+    /// ECI 字符集指示符
+    pub eci: Option<u32>,
+    /// 解码文本所用的字符集（来自 ECI 或自动推断）
+    pub charset: String,
+    /// 解码后的原始字节流（Base64）
+    pub raw_bytes: String,
+    /// 结构化追加头（序号、总数、奇偶校验）
+    pub structured_append: Option<StructuredAppend>,
+    /// 数据段（用于拼接结构化追加消息，不传给前端）
+    #[serde(skip)]
+    pub segments: Vec<Segment>,
+}
+
+/// 像素坐标点
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// 屏幕信息：索引及其在虚拟桌面中的偏移
+///
+/// 偏移是操作系统报告的逻辑像素，而结果的 `points` 是该屏幕截图中的物理像素，
+/// 换算到虚拟桌面坐标时使用 [`ScreenOrigin::to_desktop`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScreenOrigin {
+    /// 屏幕索引
+    pub index: usize,
+    /// 屏幕左上角在虚拟桌面中的 X 坐标（逻辑像素）
+    pub x: i32,
+    /// 屏幕左上角在虚拟桌面中的 Y 坐标（逻辑像素）
+    pub y: i32,
+    /// DPI 缩放比例（物理像素 / 逻辑像素）
+    #[serde(default = "default_scale_factor")]
+    pub scale_factor: f32,
+}
+
+fn default_scale_factor() -> f32 {
+    1.0
+}
+
+impl ScreenOrigin {
+    /// 屏幕截图的来源信息
+    pub fn of(screen: &ScreenCapture) -> Self {
+        Self { index: screen.index, x: screen.x, y: screen.y, scale_factor: screen.scale_factor }
+    }
+
+    /// 将截图中的物理像素坐标换算为虚拟桌面中的逻辑坐标
+    pub fn to_desktop(&self, point: Point) -> Point {
+        let scale = if self.scale_factor > 0.0 { self.scale_factor } else { 1.0 };
+        Point { x: self.x as f32 + point.x / scale, y: self.y as f32 + point.y / scale }
+    }
+}
+
+impl QrCodeResult {
+    /// 根据内容创建结果，自动推断类型和结构化载荷
+    pub(crate) fn new(content: String, format: BarcodeFormat, points: Vec<Point>) -> Self {
+        let payload = payload::parse(&content);
+        let qr_type = match &payload {
+            Some(payload) => QrCodeType::from_payload(payload),
+            None => QrCodeType::from_prefix(&content),
+        };
+        let risk = url_risk::analyze_url(&content);
+        Self {
+            content,
+            qr_type,
+            payload,
+            risk,
+            format,
+            points,
+            screen: None,
+            frame: None,
+            symbol: None,
+            structured_append: None,
+            count: 1,
+            locations: Vec::new(),
+        }
+    }
+
+    /// 平移角点坐标（例如将裁剪区域内的坐标换算回整屏坐标）
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        let locations = self.locations.iter_mut().map(|l| &mut l.points);
+        for point in std::iter::once(&mut self.points).chain(locations).flatten() {
+            point.x += dx;
+            point.y += dy;
+        }
+    }
+
+    /// 当前结果所在的位置
+    fn location(&self) -> SymbolLocation {
+        SymbolLocation { points: self.points.clone(), screen: self.screen, frame: self.frame }
+    }
+
+    /// 内容、码制和结构化追加头均相同（结构化追加序列中不同序号的部分内容可能相同）
+    fn same_content(&self, other: &QrCodeResult) -> bool {
+        let header = |r: &QrCodeResult| r.symbol.as_ref().and_then(|s| s.structured_append);
+        self.content == other.content && self.format == other.format && header(self) == header(other)
+    }
+
+    /// 是否为同一个码：内容相同且位于同一位置（不同缩放、预处理策略识别到的同一个码）
+    fn same_symbol(&self, other: &QrCodeResult) -> bool {
+        self.same_content(other)
+            && self.screen == other.screen
+            && self.frame == other.frame
+            && same_location(&self.points, &other.points)
+    }
+}
+
+/// 条码码制
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BarcodeFormat {
+    QrCode,
+    /// Micro QR（M1~M4，只有一个定位图案）
+    MicroQrCode,
+    /// 长方形 Micro QR（rMQR，ISO/IEC 23941）
+    RectangularMicroQrCode,
+    Ean13,
+    Ean8,
+    UpcA,
+    UpcE,
+    Code128,
+    Code39,
+    Code93,
+    Codabar,
+    Itf,
+    DataMatrix,
+    Pdf417,
+    Aztec,
+    Other,
+}
+
+impl BarcodeFormat {
+    /// 是否为 Micro QR 或 rMQR
+    fn is_micro(self) -> bool {
+        matches!(self, BarcodeFormat::MicroQrCode | BarcodeFormat::RectangularMicroQrCode)
+    }
+
+    /// 从 rxing 的码制转换
+    fn from_rxing(format: &rxing::BarcodeFormat) -> Self {
+        use rxing::BarcodeFormat as Rx;
+        match format {
+            Rx::QR_CODE => BarcodeFormat::QrCode,
+            Rx::MICRO_QR_CODE => BarcodeFormat::MicroQrCode,
+            Rx::RECTANGULAR_MICRO_QR_CODE => BarcodeFormat::RectangularMicroQrCode,
+            Rx::EAN_13 => BarcodeFormat::Ean13,
+            Rx::EAN_8 => BarcodeFormat::Ean8,
+            Rx::UPC_A => BarcodeFormat::UpcA,
+            Rx::UPC_E => BarcodeFormat::UpcE,
+            Rx::CODE_128 => BarcodeFormat::Code128,
+            Rx::CODE_39 => BarcodeFormat::Code39,
+            Rx::CODE_93 => BarcodeFormat::Code93,
+            Rx::CODABAR => BarcodeFormat::Codabar,
+            Rx::ITF => BarcodeFormat::Itf,
+            Rx::DATA_MATRIX => BarcodeFormat::DataMatrix,
+            Rx::PDF_417 => BarcodeFormat::Pdf417,
+            Rx::AZTEC => BarcodeFormat::Aztec,
+            _ => BarcodeFormat::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QrCodeType {
+    Url,
+    Text,
+    Email,
+    Phone,
+    Wifi,
+    Contact,
+    Geo,
+    Sms,
+    Event,
+    Other,
+}
+
+impl QrCodeType {
+    /// 根据内容推断二维码类型
+    pub fn from_content(content: &str) -> Self {
+        match payload::parse(content) {
+            Some(payload) => Self::from_payload(&payload),
+            None => Self::from_prefix(content),
+        }
+    }
+
+    /// 根据结构化载荷确定类型
+    pub fn from_payload(payload: &QrPayload) -> Self {
+        match payload {
+            QrPayload::Wifi(_) => QrCodeType::Wifi,
+            QrPayload::Contact(_) => QrCodeType::Contact,
+            QrPayload::Geo(_) => QrCodeType::Geo,
+            QrPayload::Sms(_) => QrCodeType::Sms,
+            QrPayload::Event(_) => QrCodeType::Event,
+            QrPayload::Email(_) => QrCodeType::Email,
+        }
+    }
+
+    /// 根据内容前缀推断类型
+    fn from_prefix(content: &str) -> Self {
+        if content.starts_with("http://") || content.starts_with("https://") {
+            QrCodeType::Url
+        } else if content.starts_with("mailto:") {
+            QrCodeType::Email
+        } else if content.starts_with("tel:") {
+            QrCodeType::Phone
+        } else if content.contains("://") {
+            QrCodeType::Other
+        } else {
+            QrCodeType::Text
+        }
+    }
+}
+
+/// 扫描结果及诊断信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanReport {
+    pub results: Vec<QrCodeResult>,
+    /// 诊断信息（每张图像一条）
+    pub diagnostics: Vec<ScanDiagnostics>,
+}
+
+/// 扫描图像中的所有二维码（同时识别 Micro QR、rMQR、一维码、Data Matrix、PDF417、Aztec 等码制）
+///
+/// # 参数
+/// * `image` - 待扫描的图像
+/// * `pipeline` - 预处理流水线配置
+///
+/// # 返回
+/// 返回识别到的所有二维码结果（结构化追加的多个符号会拼接为一条结果）及诊断信息
+pub fn scan_qr_codes(image: &DynamicImage, pipeline: &ScanPipeline) -> Result<ScanReport, Error> {
+    let (results, diagnostics) = scan_symbols(image, pipeline)?;
+    Ok(ScanReport {
+        results: structured_append::merge(group_occurrences(results, pipeline.keep_duplicates)),
+        diagnostics: vec![diagnostics],
+    })
+}
+
+/// 同时执行的策略数上限：每个策略都会复制整张图像（放大时成倍增大），全部并行时内存峰值过高
+const MAX_PARALLEL_STRATEGIES: usize = 4;
+
+/// 执行识别策略的线程池，创建失败时为 None（策略依次执行）
+fn strategy_pool() -> Option<&'static ThreadPool> {
+    static POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        ThreadPoolBuilder::new()
+            .num_threads(MAX_PARALLEL_STRATEGIES)
+            .thread_name(|i| format!("glimpse-scan-{}", i))
+            .build()
+            .map_err(|e| debug_println!("[WARN] 创建识别线程池失败，策略将依次执行: {}", e))
+            .ok()
+    })
+    .as_ref()
+}
+
+/// 策略的执行分组：不放大的策略为一组，放大策略按缩放比例各为一组
+fn scale_group(strategy: &ScanStrategy) -> f32 {
+    strategy.scale.max(1.0)
+}
+
+/// 单个策略的执行结果
+struct StrategyOutcome {
+    results: Vec<QrCodeResult>,
+    diagnostics: StrategyDiagnostics,
+}
+
+/// 扫描图像中的所有码，不拼接结构化追加符号
+///
+/// 已启用的策略按缩放比例分组：不放大的策略最先执行，放大策略按比例从小到大逐组执行，
+/// 组内策略在有限的线程池中并行执行。任一策略找到结果（且结构化追加序列已找齐）后，
+/// 其余策略在下一个检查点放弃执行，后续的放大分组也不再缩放图像。结果按流水线顺序汇总
+fn scan_symbols(
+    image: &DynamicImage,
+    pipeline: &ScanPipeline,
+) -> Result<(Vec<QrCodeResult>, ScanDiagnostics), Error> {
+    pipeline.validate()?;
+    debug_println!("[DEBUG] 开始扫描二维码，图像尺寸: {}x{}", image.width(), image.height());
+
+    let started = Instant::now();
+    let cancel = AtomicBool::new(false);
+    let found: Mutex<Vec<QrCodeResult>> = Mutex::new(Vec::new());
+
+    let mut strategies: Vec<(usize, ScanStrategy)> = pipeline.enabled().map(|(i, s)| (i, *s)).collect();
+    // 稳定排序，同一分组内保持流水线顺序
+    strategies.sort_by(|(_, a), (_, b)| scale_group(a).total_cmp(&scale_group(b)));
+
+    let run = |&(index, strategy): &(usize, ScanStrategy)| {
+        let outcome = run_strategy(image, index, strategy, &cancel);
+        if !outcome.results.is_empty() {
+            let mut found = found.lock().unwrap_or_else(|e| e.into_inner());
+            for result in &outcome.results {
+                push_unique(&mut found, result.clone());
+            }
+            // 结构化追加序列不完整时继续执行其他策略
+            if !structured_append::has_missing_parts(&found) {
+                cancel.store(true, Ordering::Relaxed);
+            }
+        }
+        outcome
+    };
+
+    let mut outcomes: Vec<StrategyOutcome> = Vec::with_capacity(strategies.len());
+    for group in strategies.chunk_by(|(_, a), (_, b)| scale_group(a) == scale_group(b)) {
+        match strategy_pool() {
+            Some(pool) => outcomes.extend(pool.install(|| group.par_iter().map(run).collect::<Vec<_>>())),
+            None => outcomes.extend(group.iter().map(run)),
+        }
+    }
+    outcomes.sort_by_key(|outcome| outcome.diagnostics.index);
+
+    let mut all_results = Vec::new();
+    let mut diagnostics = ScanDiagnostics {
+        image_width: image.width(),
+        image_height: image.height(),
+        strategies: Vec::with_capacity(outcomes.len()),
+        succeeded: None,
+        total_ms: 0,
+    };
+    for outcome in outcomes {
+        if !outcome.results.is_empty() && diagnostics.succeeded.is_none() {
+            diagnostics.succeeded = Some(outcome.diagnostics.index);
+        }
+        for result in outcome.results {
+            push_unique(&mut all_results, result);
+        }
+        diagnostics.strategies.push(outcome.diagnostics);
+    }
+    diagnostics.total_ms = started.elapsed().as_millis() as u64;
+
+    debug_println!(
+        "[DEBUG] 扫描完成，共找到 {} 个码，命中策略: {:?}，耗时 {}ms",
+        all_results.len(),
+        diagnostics.succeeded.map(|i| i + 1),
+        diagnostics.total_ms
+    );
+    Ok((all_results, diagnostics))
+}
+
+/// 执行单个策略并记录耗时
+fn run_strategy(
+    image: &DynamicImage,
+    index: usize,
+    strategy: ScanStrategy,
+    cancel: &AtomicBool,
+) -> StrategyOutcome {
+    let started = Instant::now();
+    let mut outcome = StrategyOutcome {
+        results: Vec::new(),
+        diagnostics: StrategyDiagnostics {
+            index,
+            scale: strategy.scale,
+            method: strategy.method,
+            elapsed_ms: 0,
+            grids_detected: 0,
+            decoded: 0,
+            cancelled: false,
+        },
+    };
+
+    let completed = detect_with_strategy(image, strategy, cancel, &mut outcome).is_some();
+    outcome.diagnostics.cancelled = !completed;
+    outcome.diagnostics.decoded = outcome.results.len();
+    outcome.diagnostics.elapsed_ms = started.elapsed().as_millis() as u64;
+
+    debug_println!(
+        "[DEBUG] 策略 {}: 缩放={}, 预处理={:?}, 网格={}, 解码={}, 耗时={}ms{}",
+        index + 1,
+        strategy.scale,
+        strategy.method,
+        outcome.diagnostics.grids_detected,
+        outcome.diagnostics.decoded,
+        outcome.diagnostics.elapsed_ms,
+        if completed { "" } else { "（已取消）" }
+    );
+    outcome
+}
+
+/// 按策略缩放、预处理并识别，在各耗时步骤之间检查取消标志，被取消时返回 None
+fn detect_with_strategy(
+    image: &DynamicImage,
+    strategy: ScanStrategy,
+    cancel: &AtomicBool,
+    outcome: &mut StrategyOutcome,
+) -> Option<()> {
+    let cancelled = || cancel.load(Ordering::Relaxed);
+    if cancelled() {
+        return None;
+    }
+
+    let scaled = if strategy.scale != 1.0 {
+        let new_width = ((image.width() as f32 * strategy.scale) as u32).max(1);
+        let new_height = ((image.height() as f32 * strategy.scale) as u32).max(1);
+        Cow::Owned(image.resize(new_width, new_height, image::imageops::FilterType::Lanczos3))
+    } else {
+        Cow::Borrowed(image)
+    };
+
+    // 实际缩放比例（用于将坐标还原到原图），镜像处理时先把 X 坐标翻转回来
+    let scaled_width = scaled.width() as f32;
+    let scale_x = scaled_width / image.width().max(1) as f32;
+    let scale_y = scaled.height() as f32 / image.height().max(1) as f32;
+    let mirrored = strategy.method.is_mirrored();
+    let unscale = |x: f32, y: f32| {
+        let x = if mirrored { scaled_width - x } else { x };
+        Point { x: x / scale_x, y: y / scale_y }
+    };
+
+    if cancelled() {
+        return None;
+    }
+    let gray_image = apply_preprocessing(&scaled, strategy.method).to_luma8();
+    drop(scaled);
+
+    if cancelled() {
+        return None;
+    }
+    let mut prepared = prepare_image(&gray_image);
+    let grids = prepared.detect_grids();
+    outcome.diagnostics.grids_detected = grids.len();
+
+    for grid in grids {
+        match decode_grid(&grid) {
+            Ok((content, symbol)) => {
+                debug_println!("[DEBUG]   ✓ 成功解码 ({}): {}", symbol.charset, content);
+                let points = grid.bounds.iter()
+                    .map(|p| unscale(p.x as f32, p.y as f32))
+                    .collect();
+                let mut result = QrCodeResult::new(content, BarcodeFormat::QrCode, points);
+                result.symbol = Some(symbol);
+                push_unique(&mut outcome.results, result);
+            }
+            Err(e) => {
+                debug_println!("[DEBUG]   ✗ 解码失败: {:?}", e);
+            }
+        }
+    }
+
+    if cancelled() {
+        return None;
+    }
+
+    // 多码制识别（与二维码使用相同的预处理结果）
+    for (content, format, points) in decode_other_formats(&gray_image) {
+        debug_println!("[DEBUG]   ✓ 成功解码 {:?}: {}", format, content);
+        let points = points.into_iter().map(|p| unscale(p.x, p.y)).collect();
+        push_unique(&mut outcome.results, QrCodeResult::new(content, format, points));
+    }
+
+    Some(())
+}
+
+/// 添加结果（同一位置的相同码只保留一个，不同位置的相同码分别保留）
+fn push_unique(results: &mut Vec<QrCodeResult>, result: QrCodeResult) {
+    if results.iter().any(|r| r.same_symbol(&result)) {
+        return;
+    }
+    results.push(result);
+}
+
+/// 判断位置时码的最小边长（像素）：一维码的角点可能只有一条扫描线上的两个点
+const MIN_SYMBOL_SIZE: f32 = 16.0;
+
+/// 判断两组角点是否属于同一个码：外接矩形中心距离不超过较大一方边长的一半。
+/// 缺少角点时无法区分位置，视为同一个码
+fn same_location(a: &[Point], b: &[Point]) -> bool {
+    let (Some((ax, ay, a_size)), Some((bx, by, b_size))) = (bounding_box(a), bounding_box(b)) else {
+        return true;
+    };
+    let size = a_size.max(b_size).max(MIN_SYMBOL_SIZE);
+    (ax - bx).hypot(ay - by) <= size / 2.0
+}
+
+/// 角点外接矩形的中心和较长边长
+fn bounding_box(points: &[Point]) -> Option<(f32, f32, f32)> {
+    let first = points.first()?;
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (first.x, first.y, first.x, first.y);
+    for p in &points[1..] {
+        min_x = min_x.min(p.x);
+        min_y = min_y.min(p.y);
+        max_x = max_x.max(p.x);
+        max_y = max_y.max(p.y);
+    }
+    Some(((min_x + max_x) / 2.0, (min_y + max_y) / 2.0, (max_x - min_x).max(max_y - min_y)))
+}
+
+/// 合并不同位置的相同码，记录出现次数和所有位置，结果按首次出现的顺序排列；
+/// `keep_duplicates` 为 true 时每处位置单独作为一条结果
+fn group_occurrences(results: Vec<QrCodeResult>, keep_duplicates: bool) -> Vec<QrCodeResult> {
+    let mut grouped: Vec<QrCodeResult> = Vec::with_capacity(results.len());
+    for mut result in results {
+        let location = result.location();
+        if !keep_duplicates {
+            if let Some(existing) = grouped.iter_mut().find(|r| r.same_content(&result)) {
+                existing.count += 1;
+                existing.locations.push(location);
+                debug_println!("[DEBUG] 相同内容出现 {} 次: {}", existing.count, existing.content);
+                continue;
+            }
+        }
+        result.count = 1;
+        result.locations = vec![location];
+        grouped.push(result);
+    }
+    grouped
+}
+
+/// 解码二维码网格，返回文本和符号元数据
+///
+/// 优先从模块网格自行解析数据段（按 ECI 或字符集推断解码文本），
+/// 失败时退回 rqrr 的 UTF-8 解码
+fn decode_grid<G: BitGrid>(grid: &rqrr::Grid<G>) -> Result<(String, QrSymbolInfo), String> {
+    let base64 = &base64::engine::general_purpose::STANDARD;
+    let modules = &grid.grid;
+
+    match qr_symbol::decode_modules(modules.size(), |row, col| modules.bit(row, col)) {
+        Ok(symbol) => {
+            let decoded = charset::decode_segments(&symbol.segments);
+            let info = QrSymbolInfo {
+                version: symbol.version,
+                ecc_level: symbol.ecc_level,
+                mask: symbol.mask,
+                eci: symbol.eci(),
+                charset: decoded.charset.to_string(),
+                raw_bytes: base64.encode(symbol.raw_bytes()),
+                structured_append: symbol.structured_append,
+                segments: symbol.segments,
+            };
+            Ok((decoded.text, info))
+        }
+        Err(e) => {
+            debug_println!("[DEBUG]   符号解析失败: {}，使用 rqrr 解码", e);
+            let (meta, content) = grid.decode().map_err(|e| format!("{:?}", e))?;
+            let info = QrSymbolInfo {
+                version: meta.version.0,
+                ecc_level: EccLevel::from_format_bits(meta.ecc_level),
+                mask: meta.mask as u8,
+                eci: None,
+                charset: "UTF-8".to_string(),
+                raw_bytes: base64.encode(content.as_bytes()),
+                structured_append: None,
+                segments: Vec::new(),
+            };
+            Ok((content, info))
+        }
+    }
+}
+
+/// 使用 rxing 识别标准二维码以外的码制
+///
+/// 标准二维码仍由 rqrr 负责，这里排除 QR_CODE 以避免重复识别；
+/// Micro QR 和 rMQR 缺少 rqrr 依赖的三个定位图案，由 rxing 检测，常规检测未找到时再按纯条码方式识别
+fn decode_other_formats(gray_image: &GrayImage) -> Vec<(String, BarcodeFormat, Vec<Point>)> {
+    use rxing::BarcodeFormat as Rx;
+
+    let formats: HashSet<Rx> = [
+        Rx::MICRO_QR_CODE,
+        Rx::RECTANGULAR_MICRO_QR_CODE,
+        Rx::EAN_13,
+        Rx::EAN_8,
+        Rx::UPC_A,
+        Rx::UPC_E,
+        Rx::CODE_128,
+        Rx::CODE_39,
+        Rx::CODE_93,
+        Rx::CODABAR,
+        Rx::ITF,
+        Rx::DATA_MATRIX,
+        Rx::PDF_417,
+        Rx::AZTEC,
+    ]
+    .into_iter()
+    .collect();
+
+    let mut hints = DecodeHints::default()
+        .with(DecodeHintValue::PossibleFormats(formats))
+        .with(DecodeHintValue::TryHarder(true));
+
+    let mut decoded = match rxing::helpers::detect_multiple_in_luma_with_hints(
+        gray_image.as_raw().clone(),
+        gray_image.width(),
+        gray_image.height(),
+        &mut hints,
+    ) {
+        Ok(results) => results.iter().map(convert_rxing_result).collect(),
+        // 未找到条码时 rxing 返回 NotFound，属于正常情况
+        Err(_) => Vec::new(),
+    };
+
+    if !decoded.iter().any(|(_, format, _)| format.is_micro()) {
+        decoded.extend(decode_pure_micro(gray_image));
+    }
+    decoded
+}
+
+/// 按纯条码方式再识别一次 Micro QR / rMQR
+///
+/// rxing 的常规检测依赖定位图案的扫描线比例，模块较大的 rMQR 等常被漏检；
+/// 纯条码模式直接按图像中深色区域的外接矩形采样，适合截图中单独出现的符号
+fn decode_pure_micro(gray_image: &GrayImage) -> Option<(String, BarcodeFormat, Vec<Point>)> {
+    use rxing::BarcodeFormat as Rx;
+
+    let formats: HashSet<Rx> = [Rx::MICRO_QR_CODE, Rx::RECTANGULAR_MICRO_QR_CODE].into_iter().collect();
+    let mut hints = DecodeHints::default()
+        .with(DecodeHintValue::PossibleFormats(formats))
+        .with(DecodeHintValue::TryHarder(true))
+        .with(DecodeHintValue::PureBarcode(true));
+
+    rxing::helpers::detect_in_luma_with_hints(
+        gray_image.as_raw().clone(),
+        gray_image.width(),
+        gray_image.height(),
+        None,
+        &mut hints,
+    )
+    .ok()
+    .map(|r| convert_rxing_result(&r))
+    // 纯条码模式下 rxing 也会按标准二维码解码，标准二维码仍只由 rqrr 识别
+    .filter(|(_, format, _)| format.is_micro())
+}
+
+/// 转换 rxing 的识别结果（内容、码制、角点）
+fn convert_rxing_result(r: &rxing::RXingResult) -> (String, BarcodeFormat, Vec<Point>) {
+    let points = r.getPoints().iter().map(|p| Point { x: p.x, y: p.y }).collect();
+    (r.getText().to_string(), BarcodeFormat::from_rxing(r.getBarcodeFormat()), points)
+}
+
+/// 图像预处理方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreprocessMethod {
+    None,              // 不处理
+    ContrastBoost,     // 增强对比度
+    Brightness,        // 增加亮度
+    Sharpen,           // 锐化
+    Invert,            // 反色
+    Otsu,              // Otsu 全局二值化
+    Sauvola,           // Sauvola 局部自适应二值化（光照不均）
+    MorphClose,        // 二值化后形态学闭运算（模块断裂）
+    Median,            // 中值滤波去噪
+    RedChannel,        // 红色通道（彩色二维码）
+    GreenChannel,      // 绿色通道
+    BlueChannel,       // 蓝色通道
+    Mirror,            // 水平镜像（镜像二维码）
+}
+
+impl PreprocessMethod {
+    /// 处理后的图像是否水平翻转（识别出的坐标需要翻转回原图）
+    fn is_mirrored(self) -> bool {
+        self == PreprocessMethod::Mirror
+    }
+}
+
+/// 应用图像预处理
+fn apply_preprocessing(image: &DynamicImage, method: PreprocessMethod) -> Cow<'_, DynamicImage> {
+    use image::imageops;
+    let processed = match method {
+        PreprocessMethod::None => return Cow::Borrowed(image),
+        PreprocessMethod::ContrastBoost => {
+            // 增强对比度（从30增加到50，更强）
+            image.adjust_contrast(50.0)
+        }
+        PreprocessMethod::Brightness => {
+            // 增加亮度
+            image.brighten(30)
+        }
+        PreprocessMethod::Sharpen => {
+            // 锐化
+            image.unsharpen(2.0, 5)
+        }
+        PreprocessMethod::Invert => {
+            // 反色（处理白色二维码或深色背景）
+            let mut inverted = image.clone();
+            imageops::invert(&mut inverted);
+            inverted
+        }
+        PreprocessMethod::Otsu => DynamicImage::ImageLuma8(preprocess::binarize_otsu(&image.to_luma8())),
+        PreprocessMethod::Sauvola => {
+            let gray = image.to_luma8();
+            let window = preprocess::sauvola_window(gray.width(), gray.height());
+            DynamicImage::ImageLuma8(preprocess::binarize_sauvola(&gray, window, 0.2))
+        }
+        PreprocessMethod::MorphClose => {
+            let binary = preprocess::binarize_otsu(&image.to_luma8());
+            DynamicImage::ImageLuma8(preprocess::morph_close(&binary, 1))
+        }
+        PreprocessMethod::Median => DynamicImage::ImageLuma8(preprocess::median_filter(&image.to_luma8(), 1)),
+        PreprocessMethod::RedChannel => DynamicImage::ImageLuma8(preprocess::extract_channel(image, Channel::Red)),
+        PreprocessMethod::GreenChannel => DynamicImage::ImageLuma8(preprocess::extract_channel(image, Channel::Green)),
+        PreprocessMethod::BlueChannel => DynamicImage::ImageLuma8(preprocess::extract_channel(image, Channel::Blue)),
+        PreprocessMethod::Mirror => image.fliph(),
+    };
+    Cow::Owned(processed)
+}
+
+/// 准备图像用于二维码识别（包含图像增强）
+fn prepare_image(gray_image: &GrayImage) -> PreparedImage<ImageBuffer<image::Luma<u8>, Vec<u8>>> {
+    PreparedImage::prepare(gray_image.clone())
+}
+
+/// 批量扫描多个屏幕截图（用于多屏幕场景）
+pub fn scan_multiple_images(screens: &[ScreenCapture], pipeline: &ScanPipeline) -> Result<ScanReport, Error> {
+    pipeline.validate()?;
+
+    let mut all_results = Vec::new();
+    let mut all_diagnostics = Vec::with_capacity(screens.len());
+
+    for screen in screens {
+        match scan_symbols(&screen.image, pipeline) {
+            Ok((mut results, diagnostics)) => {
+                // 标记结果所在的屏幕
+                let origin = ScreenOrigin::of(screen);
+                for result in &mut results {
+                    result.screen = Some(origin);
+                }
+                all_results.append(&mut results);
+                all_diagnostics.push(diagnostics);
+            }
+            Err(e) => {
+                eprintln!("扫描第 {} 张图像时出错: {}", screen.index + 1, e);
+            }
+        }
+    }
+
+    // 结构化追加的各部分可能分布在不同屏幕上，汇总后统一拼接
+    Ok(ScanReport {
+        results: structured_append::merge(group_occurrences(all_results, pipeline.keep_duplicates)),
+        diagnostics: all_diagnostics,
+    })
+}
+
+/// 逐帧扫描多帧图像（动画 GIF、多页 TIFF）
+///
+/// 多帧时结果标记所在帧；同一内容只计入最早出现的一帧（同一帧内不同位置的相同码分别计入）
+pub fn scan_frames(frames: &[ImageFrame], pipeline: &ScanPipeline) -> Result<ScanReport, Error> {
+    pipeline.validate()?;
+
+    let mut all_results: Vec<QrCodeResult> = Vec::new();
+    let mut all_diagnostics = Vec::with_capacity(frames.len());
+    let multi_frame = frames.len() > 1;
+
+    for frame in frames {
+        let (results, diagnostics) = scan_symbols(&frame.image, pipeline)?;
+        // all_results 中只有之前各帧的结果
+        let mut new_results: Vec<QrCodeResult> = results
+            .into_iter()
+            .filter(|result| !all_results.iter().any(|r| r.same_content(result)))
+            .collect();
+        for result in &mut new_results {
+            result.frame = multi_frame.then_some(frame.index);
+        }
+        all_results.append(&mut new_results);
+        all_diagnostics.push(diagnostics);
+    }
+
+    debug_println!("[DEBUG] 逐帧扫描完成，共 {} 帧，找到 {} 个码", frames.len(), all_results.len());
+
+    // 结构化追加的各部分可能分布在不同帧中，汇总后统一拼接
+    Ok(ScanReport {
+        results: structured_append::merge(group_occurrences(all_results, pipeline.keep_duplicates)),
+        diagnostics: all_diagnostics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qr_type_detection() {
+        assert!(matches!(
+            QrCodeType::from_content("https://example.com"),
+            QrCodeType::Url
+        ));
+        assert!(matches!(
+            QrCodeType::from_content("mailto:test@example.com"),
+            QrCodeType::Email
+        ));
+        assert!(matches!(
+            QrCodeType::from_content("tel:+1234567890"),
+            QrCodeType::Phone
+        ));
+        assert!(matches!(
+            QrCodeType::from_content("这是普通文本"),
+            QrCodeType::Text
+        ));
+        assert!(matches!(
+            QrCodeType::from_content("WIFI:T:WPA;S:office;P:secret;;"),
+            QrCodeType::Wifi
+        ));
+        assert!(matches!(
+            QrCodeType::from_content("BEGIN:VCARD\nFN:Jane\nEND:VCARD"),
+            QrCodeType::Contact
+        ));
+        assert!(matches!(
+            QrCodeType::from_content("geo:31.23,121.47"),
+            QrCodeType::Geo
+        ));
+        assert!(matches!(
+            QrCodeType::from_content("SMSTO:10086:hi"),
+            QrCodeType::Sms
+        ));
+    }
+
+    #[test]
+    fn test_push_unique_keeps_same_content_with_different_format() {
+        let mut results = Vec::new();
+        let ean13 = QrCodeResult::new("4006381333931".to_string(), BarcodeFormat::Ean13, vec![]);
+        push_unique(&mut results, ean13.clone());
+        push_unique(&mut results, ean13);
+        push_unique(&mut results, QrCodeResult::new("4006381333931".to_string(), BarcodeFormat::Code128, vec![]));
+        assert_eq!(results.len(), 2);
+    }
+
+    /// 以 (x, y) 为左上角、边长为 size 的码
+    fn code_at(content: &str, x: f32, y: f32, size: f32) -> QrCodeResult {
+        let points = vec![
+            Point { x, y },
+            Point { x: x + size, y },
+            Point { x: x + size, y: y + size },
+            Point { x, y: y + size },
+        ];
+        QrCodeResult::new(content.to_string(), BarcodeFormat::QrCode, points)
+    }
+
+    #[test]
+    fn test_push_unique_by_location() {
+        let mut results = Vec::new();
+        push_unique(&mut results, code_at("sku-1", 0.0, 0.0, 100.0));
+        // 不同缩放比例识别到的同一个码，角点略有偏差
+        push_unique(&mut results, code_at("sku-1", 4.0, 3.0, 96.0));
+        assert_eq!(results.len(), 1);
+
+        // 商品网格中另一个相同内容的码
+        push_unique(&mut results, code_at("sku-1", 200.0, 0.0, 100.0));
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_group_occurrences() {
+        let results = vec![
+            code_at("sku-1", 0.0, 0.0, 100.0),
+            code_at("other", 0.0, 200.0, 100.0),
+            code_at("sku-1", 200.0, 0.0, 100.0),
+        ];
+
+        let grouped = group_occurrences(results.clone(), false);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!((grouped[0].content.as_str(), grouped[0].count), ("sku-1", 2));
+        assert_eq!(grouped[0].locations.len(), 2);
+        assert_eq!(grouped[0].locations[0].points, grouped[0].points);
+        assert_eq!(grouped[0].locations[1].points[0], Point { x: 200.0, y: 0.0 });
+        assert_eq!((grouped[1].count, grouped[1].locations.len()), (1, 1));
+
+        let kept = group_occurrences(results, true);
+        assert_eq!(kept.len(), 3);
+        assert!(kept.iter().all(|r| r.count == 1 && r.locations.len() == 1));
+    }
+
+    #[test]
+    fn test_same_location_without_points() {
+        // 没有角点时无法区分位置
+        assert!(same_location(&[], &[Point { x: 0.0, y: 0.0 }]));
+        // 一维码的角点可能只是一条线
+        let line = |y: f32| [Point { x: 0.0, y }, Point { x: 120.0, y }];
+        assert!(same_location(&line(10.0), &line(14.0)));
+        assert!(!same_location(&line(10.0), &line(300.0)));
+    }
+
+    #[test]
+    fn test_translate_points() {
+        let points = vec![Point { x: 1.0, y: 2.0 }, Point { x: 10.0, y: 20.0 }];
+        let mut result = QrCodeResult::new("hello".to_string(), BarcodeFormat::QrCode, points);
+        result.translate(100.0, 50.0);
+        assert_eq!(result.points[0], Point { x: 101.0, y: 52.0 });
+        assert_eq!(result.points[1], Point { x: 110.0, y: 70.0 });
+
+        let mut grouped = group_occurrences(vec![code_at("a", 0.0, 0.0, 10.0), code_at("a", 50.0, 0.0, 10.0)], false);
+        grouped[0].translate(5.0, 5.0);
+        assert_eq!(grouped[0].locations[1].points[0], Point { x: 55.0, y: 5.0 });
+    }
+
+    #[test]
+    fn test_scan_counts_repeated_codes() {
+        use crate::qr_generator::{generate_qr, QrGenerateOptions};
+
+        let generated = generate_qr("grid-item", &QrGenerateOptions::default()).unwrap();
+        let code = image::load_from_memory(&generated.png).unwrap();
+        let mut grid = DynamicImage::new_rgb8(code.width() * 2, code.height());
+        grid.as_mut_rgb8().unwrap().fill(255);
+        image::imageops::replace(&mut grid, &code, 0, 0);
+        image::imageops::replace(&mut grid, &code, code.width() as i64, 0);
+
+        let report = scan_qr_codes(&grid, &ScanPipeline::default()).unwrap();
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.results[0].count, 2);
+        assert_eq!(report.results[0].locations.len(), 2);
+
+        let pipeline = ScanPipeline { keep_duplicates: true, ..ScanPipeline::default() };
+        let report = scan_qr_codes(&grid, &pipeline).unwrap();
+        assert_eq!(report.results.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_frames_tags_frame_index() {
+        use crate::image_file::ImageFrame;
+        use crate::qr_generator::{generate_qr, QrGenerateOptions};
+
+        let generated = generate_qr("frame-two", &QrGenerateOptions::default()).unwrap();
+        let code = image::load_from_memory(&generated.png).unwrap();
+        let blank = DynamicImage::new_rgb8(code.width(), code.height());
+        let frames: Vec<ImageFrame> = [blank, code.clone(), code]
+            .into_iter()
+            .enumerate()
+            .map(|(index, image)| ImageFrame { index, image })
+            .collect();
+
+        let report = scan_frames(&frames, &ScanPipeline::default()).unwrap();
+        assert_eq!(report.diagnostics.len(), 3);
+        // 重复出现的码只保留最早的一帧
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.results[0].content, "frame-two");
+        assert_eq!(report.results[0].frame, Some(1));
+    }
+
+    #[test]
+    fn test_upscale_strategies_skipped_after_hit() {
+        use crate::qr_generator::{generate_qr, QrGenerateOptions};
+
+        let generated = generate_qr("cheap-first", &QrGenerateOptions::default()).unwrap();
+        let code = image::load_from_memory(&generated.png).unwrap();
+        let pipeline = ScanPipeline::default();
+        let report = scan_qr_codes(&code, &pipeline).unwrap();
+        assert_eq!(report.results[0].content, "cheap-first");
+
+        // 不放大的策略先执行并命中，放大策略不再缩放图像；诊断信息仍按流水线顺序排列
+        let diagnostics = &report.diagnostics[0];
+        let indices: Vec<usize> = diagnostics.strategies.iter().map(|s| s.index).collect();
+        assert_eq!(indices, (0..pipeline.strategies.len()).collect::<Vec<_>>());
+        for strategy in &diagnostics.strategies {
+            if strategy.scale > 1.0 {
+                assert!(strategy.cancelled, "策略 {} 未被跳过", strategy.index + 1);
+            }
+        }
+    }
+
+    /// 将模块矩阵绘制为图像（静区 2 个模块，每个模块 8 像素）
+    fn modules_image(width: u32, height: u32, dark: impl Fn(u32, u32) -> bool) -> DynamicImage {
+        let (quiet, scale) = (2, 8);
+        let image = GrayImage::from_fn((width + 2 * quiet) * scale, (height + 2 * quiet) * scale, |x, y| {
+            let (col, row) = ((x / scale).wrapping_sub(quiet), (y / scale).wrapping_sub(quiet));
+            let dark = row < height && col < width && dark(col, row);
+            image::Luma([if dark { 0 } else { 255 }])
+        });
+        DynamicImage::ImageLuma8(image)
+    }
+
+    /// 生成 Micro QR 图像
+    fn micro_qr_image(text: &str, version: i16) -> DynamicImage {
+        use qrcode::{Color, EcLevel, QrCode, Version};
+
+        // qrcode 0.14 把 M3-L 的半字节数据码字 D11 按整字节绘制，生成的符号无法通过纠错校验，
+        // M3 改用 M 级纠错（半字节码字位于数据末尾，绘制正确）
+        let ec_level = if version == 3 { EcLevel::M } else { EcLevel::L };
+        let code = QrCode::with_version(text, Version::Micro(version), ec_level).unwrap();
+        let (modules, colors) = (code.width() as u32, code.to_colors());
+        modules_image(modules, modules, |col, row| colors[(row * modules + col) as usize] == Color::Dark)
+    }
+
+    #[test]
+    fn test_scan_micro_qr() {
+        let pipeline = ScanPipeline::default();
+        for (text, version) in [("01234567", 2), ("glimpse", 3), ("https://x.cn/a", 4)] {
+            let report = scan_qr_codes(&micro_qr_image(text, version), &pipeline).unwrap();
+            assert_eq!(report.results.len(), 1, "M{} 未识别", version);
+            let result = &report.results[0];
+            assert_eq!(result.content, text);
+            assert_eq!(result.format, BarcodeFormat::MicroQrCode);
+            assert!(result.symbol.is_none());
+            assert!(!result.points.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_scan_rmqr() {
+        // ISO/IEC 23941 R7x43 M 级示例符号，内容为 ABCDEFG（qrcode 不支持生成 rMQR）
+        let rows = [
+            "XXXXXXX X X X X X X XXX X X X X X X X X XXX",
+            "X     X  X XXX  XXXXX XXX      X X XX   X X",
+            "X XXX X X XXX X X X XXXX XXXX X  X XXXXXXXX",
+            "X XXX X  XX    XXXXX   XXXXXX   X X   X   X",
+            "X XXX X   XX  XXX   XXXXXXX  X X  XX  X X X",
+            "X     X XXXXX XXX XXX XXXXX    XXXXXX X   X",
+            "XXXXXXX X X X X X X XXX X X X X X X X XXXXX",
+        ];
+        let image = modules_image(rows[0].len() as u32, rows.len() as u32, |col, row| {
+            rows[row as usize].as_bytes()[col as usize] == b'X'
+        });
+        let report = scan_qr_codes(&image, &ScanPipeline::default()).unwrap();
+        assert_eq!(report.results.len(), 1);
+        let result = &report.results[0];
+        assert_eq!(result.content, "ABCDEFG");
+        assert_eq!(result.format, BarcodeFormat::RectangularMicroQrCode);
+        assert!(!result.points.is_empty());
+    }
+
+    #[test]
+    fn test_micro_qr_format_mapping() {
+        use rxing::BarcodeFormat as Rx;
+        assert_eq!(BarcodeFormat::from_rxing(&Rx::MICRO_QR_CODE), BarcodeFormat::MicroQrCode);
+        assert_eq!(
+            BarcodeFormat::from_rxing(&Rx::RECTANGULAR_MICRO_QR_CODE),
+            BarcodeFormat::RectangularMicroQrCode
+        );
+        assert_eq!(serde_json::to_string(&BarcodeFormat::RectangularMicroQrCode).unwrap(), "\"RectangularMicroQrCode\"");
+    }
+
+    #[test]
+    fn test_url_risk_attached() {
+        let result = QrCodeResult::new("https://bank.com@evil.tld/".to_string(), BarcodeFormat::QrCode, vec![]);
+        assert_eq!(result.risk.map(|r| r.level), Some(url_risk::RiskLevel::High));
+
+        let text = QrCodeResult::new("hello".to_string(), BarcodeFormat::QrCode, vec![]);
+        assert!(text.risk.is_none());
+    }
+
+    #[test]
+    fn test_screen_origin_to_desktop() {
+        // 150% 缩放的副屏位于主屏右侧：物理像素先按缩放比例换算，再加上屏幕的逻辑偏移
+        let origin = ScreenOrigin { index: 1, x: 1920, y: 0, scale_factor: 1.5 };
+        let point = origin.to_desktop(Point { x: 300.0, y: 150.0 });
+        assert_eq!((point.x, point.y), (2120.0, 100.0));
+
+        let legacy: ScreenOrigin = serde_json::from_str(r#"{"index": 0, "x": 0, "y": 0}"#).unwrap();
+        assert_eq!(legacy.scale_factor, 1.0);
+    }
+}
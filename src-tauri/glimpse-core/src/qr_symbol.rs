@@ -0,0 +1,862 @@
+//! 二维码符号解析模块
+//! 直接从 rqrr 检测到的模块网格中读取格式信息、数据码字并进行纠错，
+//! 按数据段解析原始字节流（保留 ECI、结构化追加等 rqrr 丢弃的信息）
+
+use serde::{Deserialize, Serialize};
+
+/// 纠错等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EccLevel {
+    L,
+    M,
+    Q,
+    H,
+}
+
+impl EccLevel {
+    /// 从格式信息中的两位纠错等级转换（01=L, 00=M, 11=Q, 10=H）
+    pub fn from_format_bits(bits: u16) -> Self {
+        match bits & 0b11 {
+            0b01 => EccLevel::L,
+            0b00 => EccLevel::M,
+            0b11 => EccLevel::Q,
+            _ => EccLevel::H,
+        }
+    }
+
+    /// 在纠错表中的索引
+    fn table_index(self) -> usize {
+        match self {
+            EccLevel::L => 0,
+            EccLevel::M => 1,
+            EccLevel::Q => 2,
+            EccLevel::H => 3,
+        }
+    }
+}
+
+/// 数据段模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SegmentMode {
+    Numeric,
+    Alphanumeric,
+    Byte,
+    Kanji,
+    Hanzi,
+}
+
+/// 数据段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub mode: SegmentMode,
+    /// 段数据（数字/字母数字模式为 ASCII，汉字模式为 Shift-JIS / GB2312 字节）
+    pub data: Vec<u8>,
+    /// 该段生效的 ECI 字符集指示符
+    pub eci: Option<u32>,
+}
+
+/// 结构化追加头（多个二维码拼接为一条消息）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructuredAppend {
+    /// 当前符号的序号（从 0 开始）
+    pub index: u8,
+    /// 符号总数
+    pub total: u8,
+    /// 整条消息的奇偶校验字节
+    pub parity: u8,
+}
+
+/// 解析后的二维码符号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSymbol {
+    pub version: usize,
+    pub ecc_level: EccLevel,
+    pub mask: u8,
+    pub segments: Vec<Segment>,
+    pub structured_append: Option<StructuredAppend>,
+}
+
+impl DecodedSymbol {
+    /// 所有数据段拼接后的原始字节流
+    pub fn raw_bytes(&self) -> Vec<u8> {
+        self.segments.iter().flat_map(|s| s.data.iter().copied()).collect()
+    }
+
+    /// 第一个 ECI 字符集指示符
+    pub fn eci(&self) -> Option<u32> {
+        self.segments.iter().find_map(|s| s.eci)
+    }
+}
+
+/// 从模块网格解析二维码符号
+///
+/// # 参数
+/// * `size` - 网格边长（模块数）
+/// * `bit` - 读取模块颜色，参数为（行, 列），深色为 true
+pub fn decode_modules(size: usize, bit: impl Fn(usize, usize) -> bool) -> Result<DecodedSymbol, String> {
+    if !(21..=177).contains(&size) || !(size - 17).is_multiple_of(4) {
+        return Err(format!("无效的二维码尺寸: {}", size));
+    }
+    let version = (size - 17) / 4;
+
+    let (ecc_level, mask) = read_format(size, &bit)?;
+    let raw = read_codewords(size, version, mask, &bit);
+    let data = correct_blocks(&raw, version, ecc_level)?;
+    let (segments, structured_append) = parse_segments(&data, version)?;
+
+    Ok(DecodedSymbol {
+        version,
+        ecc_level,
+        mask,
+        segments,
+        structured_append,
+    })
+}
+
+// ============ 格式信息 ============
+
+/// 计算 5 位格式数据对应的 15 位 BCH 格式码（已异或掩码 0x5412）
+fn format_codeword(data: u16) -> u16 {
+    let mut rem = data;
+    for _ in 0..10 {
+        rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+    }
+    ((data << 10) | (rem & 0x3FF)) ^ 0x5412
+}
+
+/// 读取格式信息，返回（纠错等级, 掩码）
+fn read_format(size: usize, bit: &impl Fn(usize, usize) -> bool) -> Result<(EccLevel, u8), String> {
+    // 左上角的格式信息
+    let mut first = 0u16;
+    for col in 0..6 {
+        first = (first << 1) | bit(8, col) as u16;
+    }
+    first = (first << 1) | bit(8, 7) as u16;
+    first = (first << 1) | bit(8, 8) as u16;
+    first = (first << 1) | bit(7, 8) as u16;
+    for row in (0..6).rev() {
+        first = (first << 1) | bit(row, 8) as u16;
+    }
+
+    // 右上角和左下角的冗余格式信息
+    let mut second = 0u16;
+    for row in (size - 7..size).rev() {
+        second = (second << 1) | bit(row, 8) as u16;
+    }
+    for col in size - 8..size {
+        second = (second << 1) | bit(8, col) as u16;
+    }
+
+    // 选择汉明距离最小的合法格式码（最多纠正 3 位错误）
+    let mut best: Option<(u32, u16)> = None;
+    for data in 0..32u16 {
+        let code = format_codeword(data);
+        let distance = (code ^ first).count_ones().min((code ^ second).count_ones());
+        if best.is_none_or(|(d, _)| distance < d) {
+            best = Some((distance, data));
+        }
+    }
+
+    match best {
+        Some((distance, data)) if distance <= 3 => {
+            Ok((EccLevel::from_format_bits(data >> 3), (data & 0b111) as u8))
+        }
+        _ => Err("格式信息无法识别".to_string()),
+    }
+}
+
+// ============ 码字读取 ============
+
+/// 对齐图案中心坐标
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let size = version * 4 + 17;
+    let count = version / 7 + 2;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+/// 标记功能图案区域（定位、分隔、格式、时序、对齐和版本信息）
+fn function_pattern_mask(version: usize) -> Vec<bool> {
+    let size = version * 4 + 17;
+    let mut mask = vec![false; size * size];
+    let mut set_region = |top: usize, left: usize, height: usize, width: usize| {
+        for row in top..top + height {
+            for col in left..left + width {
+                mask[row * size + col] = true;
+            }
+        }
+    };
+
+    // 定位图案、分隔符和格式信息
+    set_region(0, 0, 9, 9);
+    set_region(0, size - 8, 9, 8);
+    set_region(size - 8, 0, 8, 9);
+
+    // 时序图案
+    set_region(6, 9, 1, size - 17);
+    set_region(9, 6, size - 17, 1);
+
+    // 对齐图案（跳过与定位图案重叠的位置）
+    let positions = alignment_positions(version);
+    let last = positions.len().saturating_sub(1);
+    for (i, &row) in positions.iter().enumerate() {
+        for (j, &col) in positions.iter().enumerate() {
+            if (i == 0 && (j == 0 || j == last)) || (i == last && j == 0) {
+                continue;
+            }
+            set_region(row - 2, col - 2, 5, 5);
+        }
+    }
+
+    // 版本信息
+    if version >= 7 {
+        set_region(0, size - 11, 6, 3);
+        set_region(size - 11, 0, 3, 6);
+    }
+
+    mask
+}
+
+/// 判断数据掩码是否翻转该模块
+fn mask_applies(mask: u8, row: usize, col: usize) -> bool {
+    match mask {
+        0 => (row + col).is_multiple_of(2),
+        1 => row.is_multiple_of(2),
+        2 => col.is_multiple_of(3),
+        3 => (row + col).is_multiple_of(3),
+        4 => (row / 2 + col / 3).is_multiple_of(2),
+        5 => (row * col) % 2 + (row * col) % 3 == 0,
+        6 => ((row * col) % 2 + (row * col) % 3).is_multiple_of(2),
+        _ => ((row + col) % 2 + (row * col) % 3).is_multiple_of(2),
+    }
+}
+
+/// 按之字形顺序读取所有码字（已去除掩码）
+fn read_codewords(size: usize, version: usize, mask: u8, bit: &impl Fn(usize, usize) -> bool) -> Vec<u8> {
+    let function = function_pattern_mask(version);
+    let mut codewords = Vec::with_capacity(size * size / 8);
+    let mut current = 0u8;
+    let mut bits_read = 0;
+    let mut reading_up = true;
+
+    let mut right = size - 1;
+    while right > 0 {
+        // 跳过垂直时序图案所在列
+        if right == 6 {
+            right -= 1;
+        }
+        for count in 0..size {
+            let row = if reading_up { size - 1 - count } else { count };
+            for col in [right, right - 1] {
+                if function[row * size + col] {
+                    continue;
+                }
+                let dark = bit(row, col) ^ mask_applies(mask, row, col);
+                current = (current << 1) | dark as u8;
+                bits_read += 1;
+                if bits_read == 8 {
+                    codewords.push(current);
+                    current = 0;
+                    bits_read = 0;
+                }
+            }
+        }
+        reading_up = !reading_up;
+        if right < 2 {
+            break;
+        }
+        right -= 2;
+    }
+
+    codewords
+}
+
+// ============ 分块与纠错 ============
+
+/// 每块纠错码字数，按 [L, M, Q, H][版本] 索引
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28],
+    [0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+];
+
+/// 纠错块数，按 [L, M, Q, H][版本] 索引
+const NUM_ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+    [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25],
+    [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49],
+    [0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68],
+    [0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81],
+];
+
+/// 符号中的码字总数
+fn total_codewords(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let count = version / 7 + 2;
+        modules -= (25 * count - 10) * count - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules / 8
+}
+
+/// 解交织并逐块纠错，返回按块顺序拼接的数据码字
+fn correct_blocks(raw: &[u8], version: usize, ecc_level: EccLevel) -> Result<Vec<u8>, String> {
+    let total = total_codewords(version);
+    if raw.len() < total {
+        return Err(format!("码字数量不足: {} < {}", raw.len(), total));
+    }
+
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[ecc_level.table_index()][version] as usize;
+    let num_blocks = NUM_ERROR_CORRECTION_BLOCKS[ecc_level.table_index()][version] as usize;
+    let num_short_blocks = num_blocks - total % num_blocks;
+    let short_len = total / num_blocks;
+    let short_data_len = short_len - ecc_len;
+
+    let mut blocks: Vec<Vec<u8>> = (0..num_blocks)
+        .map(|i| Vec::with_capacity(short_len + usize::from(i >= num_short_blocks)))
+        .collect();
+
+    // 数据码字交织排列，长块多出的一个数据码字排在最后
+    let mut offset = 0;
+    for _ in 0..short_data_len {
+        for block in blocks.iter_mut() {
+            block.push(raw[offset]);
+            offset += 1;
+        }
+    }
+    for block in blocks.iter_mut().skip(num_short_blocks) {
+        block.push(raw[offset]);
+        offset += 1;
+    }
+    for _ in 0..ecc_len {
+        for block in blocks.iter_mut() {
+            block.push(raw[offset]);
+            offset += 1;
+        }
+    }
+
+    let mut data = Vec::with_capacity(total);
+    for mut block in blocks {
+        reed_solomon::correct(&mut block, ecc_len)?;
+        let data_len = block.len() - ecc_len;
+        data.extend_from_slice(&block[..data_len]);
+    }
+
+    Ok(data)
+}
+
+/// GF(256) 上的 Reed-Solomon 纠错（本原多项式 0x11D，生成多项式根从 α^0 开始）
+mod reed_solomon {
+    const EXP: [u8; 512] = {
+        let mut table = [0u8; 512];
+        let mut x: u16 = 1;
+        let mut i = 0;
+        while i < 255 {
+            table[i] = x as u8;
+            x <<= 1;
+            if x & 0x100 != 0 {
+                x ^= 0x11D;
+            }
+            i += 1;
+        }
+        while i < 512 {
+            table[i] = table[i - 255];
+            i += 1;
+        }
+        table
+    };
+
+    const LOG: [u8; 256] = {
+        let mut table = [0u8; 256];
+        let mut i = 0;
+        while i < 255 {
+            table[EXP[i] as usize] = i as u8;
+            i += 1;
+        }
+        table
+    };
+
+    pub(super) fn mul(a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            EXP[LOG[a as usize] as usize + LOG[b as usize] as usize]
+        }
+    }
+
+    fn div(a: u8, b: u8) -> u8 {
+        if a == 0 {
+            0
+        } else {
+            EXP[LOG[a as usize] as usize + 255 - LOG[b as usize] as usize]
+        }
+    }
+
+    pub(super) fn pow_alpha(exp: usize) -> u8 {
+        EXP[exp % 255]
+    }
+
+    /// 计算多项式在 x 处的值（系数按高次在前排列）
+    fn eval(poly: &[u8], x: u8) -> u8 {
+        poly.iter().fold(0, |acc, &c| mul(acc, x) ^ c)
+    }
+
+    /// 原地纠正码字块（数据在前，纠错码字在后）
+    pub fn correct(block: &mut [u8], ecc_len: usize) -> Result<(), String> {
+        // 伴随式 S_j = C(α^j)
+        let syndromes: Vec<u8> = (0..ecc_len).map(|j| eval(block, pow_alpha(j))).collect();
+        if syndromes.iter().all(|&s| s == 0) {
+            return Ok(());
+        }
+
+        // Berlekamp-Massey 求错误位置多项式 Λ(x)，系数按低次在前排列
+        let mut locator = vec![1u8];
+        let mut previous = vec![1u8];
+        for i in 0..ecc_len {
+            previous.insert(0, 0);
+            let mut delta = syndromes[i];
+            for j in 1..locator.len().min(i + 1) {
+                delta ^= mul(locator[j], syndromes[i - j]);
+            }
+            if delta != 0 {
+                if previous.len() > locator.len() {
+                    let scaled: Vec<u8> = previous.iter().map(|&c| mul(c, delta)).collect();
+                    previous = locator.iter().map(|&c| div(c, delta)).collect();
+                    locator = scaled;
+                }
+                for (j, &c) in previous.iter().enumerate() {
+                    if j >= locator.len() {
+                        locator.push(0);
+                    }
+                    locator[j] ^= mul(c, delta);
+                }
+            }
+        }
+        while locator.len() > 1 && locator[locator.len() - 1] == 0 {
+            locator.pop();
+        }
+        let num_errors = locator.len() - 1;
+        if num_errors * 2 > ecc_len {
+            return Err("错误过多，无法纠正".to_string());
+        }
+
+        // Chien 搜索：位置 p（从末尾计数 i = n-1-p）出错当且仅当 Λ(α^-i) = 0
+        let n = block.len();
+        let mut positions = Vec::with_capacity(num_errors);
+        for i in 0..n {
+            let x_inv = pow_alpha(255 - i % 255);
+            let value = locator.iter().rev().fold(0, |acc, &c| mul(acc, x_inv) ^ c);
+            if value == 0 {
+                positions.push(i);
+            }
+        }
+        if positions.len() != num_errors {
+            return Err("无法定位错误位置".to_string());
+        }
+
+        // 错误值多项式 Ω(x) = S(x)Λ(x) mod x^ecc_len
+        let mut evaluator = vec![0u8; ecc_len];
+        for (i, &s) in syndromes.iter().enumerate() {
+            for (j, &l) in locator.iter().enumerate() {
+                if i + j < ecc_len {
+                    evaluator[i + j] ^= mul(s, l);
+                }
+            }
+        }
+
+        // Forney 算法：e = X·Ω(X^-1) / Λ'(X^-1)（首个根为 α^0）
+        for &i in &positions {
+            let x = pow_alpha(i);
+            let x_inv = pow_alpha(255 - i % 255);
+            let omega = evaluator.iter().rev().fold(0, |acc, &c| mul(acc, x_inv) ^ c);
+            let mut derivative = 0u8;
+            let mut x_inv_pow = 1u8;
+            for (j, &l) in locator.iter().enumerate().skip(1) {
+                if j % 2 == 1 {
+                    derivative ^= mul(l, x_inv_pow);
+                }
+                x_inv_pow = mul(x_inv_pow, x_inv);
+            }
+            if derivative == 0 {
+                return Err("纠错计算失败".to_string());
+            }
+            block[n - 1 - i] ^= mul(x, div(omega, derivative));
+        }
+
+        if (0..ecc_len).any(|j| eval(block, pow_alpha(j)) != 0) {
+            return Err("纠错校验失败".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ============ 数据段解析 ============
+
+/// 按位读取器
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.position
+    }
+
+    fn read(&mut self, count: usize) -> Result<u32, String> {
+        if count > self.remaining() {
+            return Err("数据段意外结束".to_string());
+        }
+        let mut value = 0u32;
+        for _ in 0..count {
+            let byte = self.data[self.position / 8];
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u32;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+}
+
+/// 字符计数指示符的位数
+fn count_bits(mode: SegmentMode, version: usize) -> usize {
+    let group = match version {
+        1..=9 => 0,
+        10..=26 => 1,
+        _ => 2,
+    };
+    match mode {
+        SegmentMode::Numeric => [10, 12, 14][group],
+        SegmentMode::Alphanumeric => [9, 11, 13][group],
+        SegmentMode::Byte => [8, 16, 16][group],
+        SegmentMode::Kanji | SegmentMode::Hanzi => [8, 10, 12][group],
+    }
+}
+
+const ALPHANUMERIC_CHARS: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// 解析数据码字中的所有数据段
+fn parse_segments(data: &[u8], version: usize) -> Result<(Vec<Segment>, Option<StructuredAppend>), String> {
+    let mut reader = BitReader::new(data);
+    let mut segments = Vec::new();
+    let mut structured_append = None;
+    let mut eci = None;
+
+    while reader.remaining() >= 4 {
+        let mode = match reader.read(4)? {
+            0b0000 => break,
+            0b0001 => SegmentMode::Numeric,
+            0b0010 => SegmentMode::Alphanumeric,
+            0b0100 => SegmentMode::Byte,
+            0b1000 => SegmentMode::Kanji,
+            0b1101 => {
+                // GB/T 18284 汉字模式，子集 1 为 GB2312
+                let subset = reader.read(4)?;
+                if subset != 1 {
+                    return Err(format!("不支持的汉字子集: {}", subset));
+                }
+                SegmentMode::Hanzi
+            }
+            0b0111 => {
+                eci = Some(read_eci_designator(&mut reader)?);
+                continue;
+            }
+            0b0011 => {
+                let index = reader.read(4)? as u8;
+                let total = reader.read(4)? as u8 + 1;
+                let parity = reader.read(8)? as u8;
+                structured_append = Some(StructuredAppend { index, total, parity });
+                continue;
+            }
+            // FNC1 第一位置无附加数据，第二位置带 8 位应用标识
+            0b0101 => continue,
+            0b1001 => {
+                reader.read(8)?;
+                continue;
+            }
+            other => return Err(format!("未知的数据段模式: {:04b}", other)),
+        };
+
+        let count = reader.read(count_bits(mode, version))? as usize;
+        let data = match mode {
+            SegmentMode::Numeric => read_numeric(&mut reader, count)?,
+            SegmentMode::Alphanumeric => read_alphanumeric(&mut reader, count)?,
+            SegmentMode::Byte => (0..count).map(|_| reader.read(8).map(|b| b as u8)).collect::<Result<_, _>>()?,
+            SegmentMode::Kanji => read_double_byte(&mut reader, count, kanji_to_shift_jis)?,
+            SegmentMode::Hanzi => read_double_byte(&mut reader, count, hanzi_to_gb2312)?,
+        };
+        segments.push(Segment { mode, data, eci });
+    }
+
+    Ok((segments, structured_append))
+}
+
+/// 读取 ECI 指示符（1~3 字节变长编码）
+fn read_eci_designator(reader: &mut BitReader) -> Result<u32, String> {
+    let first = reader.read(8)?;
+    if first & 0x80 == 0 {
+        Ok(first & 0x7F)
+    } else if first & 0xC0 == 0x80 {
+        Ok(((first & 0x3F) << 8) | reader.read(8)?)
+    } else if first & 0xE0 == 0xC0 {
+        Ok(((first & 0x1F) << 16) | reader.read(16)?)
+    } else {
+        Err(format!("无效的 ECI 指示符: {:#04x}", first))
+    }
+}
+
+/// 读取数字模式数据（每 3 位数字 10 比特）
+fn read_numeric(reader: &mut BitReader, count: usize) -> Result<Vec<u8>, String> {
+    let mut digits = String::with_capacity(count);
+    let mut left = count;
+    while left > 0 {
+        let (bits, width) = match left {
+            1 => (4, 1),
+            2 => (7, 2),
+            _ => (10, 3),
+        };
+        let value = reader.read(bits)?;
+        digits.push_str(&format!("{:0width$}", value, width = width));
+        left -= width;
+    }
+    Ok(digits.into_bytes())
+}
+
+/// 读取字母数字模式数据（每 2 个字符 11 比特）
+fn read_alphanumeric(reader: &mut BitReader, count: usize) -> Result<Vec<u8>, String> {
+    let lookup = |value: u32| {
+        ALPHANUMERIC_CHARS
+            .get(value as usize)
+            .copied()
+            .ok_or_else(|| format!("无效的字母数字值: {}", value))
+    };
+
+    let mut data = Vec::with_capacity(count);
+    let mut left = count;
+    while left >= 2 {
+        let value = reader.read(11)?;
+        data.push(lookup(value / 45)?);
+        data.push(lookup(value % 45)?);
+        left -= 2;
+    }
+    if left == 1 {
+        data.push(lookup(reader.read(6)?)?);
+    }
+    Ok(data)
+}
+
+/// 读取双字节模式数据（每个字符 13 比特）
+fn read_double_byte(reader: &mut BitReader, count: usize, convert: fn(u32) -> u16) -> Result<Vec<u8>, String> {
+    let mut data = Vec::with_capacity(count * 2);
+    for _ in 0..count {
+        let code = convert(reader.read(13)?);
+        data.extend_from_slice(&code.to_be_bytes());
+    }
+    Ok(data)
+}
+
+/// 13 比特汉字值转换为 Shift-JIS 编码
+fn kanji_to_shift_jis(value: u32) -> u16 {
+    let assembled = ((value / 0xC0) << 8) | (value % 0xC0);
+    let code = if assembled < 0x1F00 { assembled + 0x8140 } else { assembled + 0xC140 };
+    code as u16
+}
+
+/// 13 比特汉字值转换为 GB2312 编码
+fn hanzi_to_gb2312(value: u32) -> u16 {
+    let assembled = ((value / 0x60) << 8) | (value % 0x60);
+    let code = if assembled < 0x0A00 { assembled + 0xA1A1 } else { assembled + 0xA6A1 };
+    code as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按位写入器（用于构造测试数据）
+    struct BitWriter {
+        bits: Vec<bool>,
+    }
+
+    impl BitWriter {
+        fn new() -> Self {
+            Self { bits: Vec::new() }
+        }
+
+        fn push(&mut self, value: u32, count: usize) {
+            for i in (0..count).rev() {
+                self.bits.push((value >> i) & 1 == 1);
+            }
+        }
+
+        fn into_bytes(self) -> Vec<u8> {
+            self.bits
+                .chunks(8)
+                .map(|chunk| chunk.iter().enumerate().fold(0u8, |acc, (i, &b)| acc | ((b as u8) << (7 - i))))
+                .collect()
+        }
+    }
+
+    /// 计算 Reed-Solomon 纠错码字
+    fn rs_encode(data: &[u8], ecc_len: usize) -> Vec<u8> {
+        // 生成多项式 g(x) = ∏(x - α^i)，系数按高次在前排列
+        let mut generator = vec![1u8];
+        for i in 0..ecc_len {
+            let root = reed_solomon::pow_alpha(i);
+            let mut next = vec![0u8; generator.len() + 1];
+            for (j, &g) in generator.iter().enumerate() {
+                next[j] ^= g;
+                next[j + 1] ^= reed_solomon::mul(g, root);
+            }
+            generator = next;
+        }
+
+        let mut remainder = data.to_vec();
+        remainder.resize(data.len() + ecc_len, 0);
+        for i in 0..data.len() {
+            let coef = remainder[i];
+            if coef != 0 {
+                for (j, &g) in generator.iter().enumerate() {
+                    remainder[i + j] ^= reed_solomon::mul(g, coef);
+                }
+            }
+        }
+        remainder[data.len()..].to_vec()
+    }
+
+    #[test]
+    fn test_format_codeword_known_value() {
+        // ECC 等级 M（00）、掩码 0
+        assert_eq!(format_codeword(0b00000), 0x5412);
+        // ECC 等级 L（01）、掩码 0
+        assert_eq!(format_codeword(0b01000), 0x77C4);
+        // ECC 等级 H（10）、掩码 7
+        assert_eq!(format_codeword(0b10111), 0x083B);
+    }
+
+    #[test]
+    fn test_total_codewords() {
+        assert_eq!(total_codewords(1), 26);
+        assert_eq!(total_codewords(7), 196);
+        assert_eq!(total_codewords(40), 3706);
+    }
+
+    #[test]
+    fn test_alignment_positions() {
+        assert!(alignment_positions(1).is_empty());
+        assert_eq!(alignment_positions(2), vec![6, 18]);
+        assert_eq!(alignment_positions(7), vec![6, 22, 38]);
+        assert_eq!(alignment_positions(32), vec![6, 34, 60, 86, 112, 138]);
+        assert_eq!(alignment_positions(40), vec![6, 30, 58, 86, 114, 142, 170]);
+    }
+
+    #[test]
+    fn test_reed_solomon_corrects_errors() {
+        let data: Vec<u8> = (0..19u8).map(|i| i.wrapping_mul(37).wrapping_add(5)).collect();
+        let mut block = data.clone();
+        block.extend(rs_encode(&data, 7));
+
+        block[0] ^= 0xFF;
+        block[10] ^= 0x01;
+        block[25] ^= 0x5A;
+        reed_solomon::correct(&mut block, 7).unwrap();
+        assert_eq!(&block[..19], &data[..]);
+    }
+
+    #[test]
+    fn test_parse_mixed_segments() {
+        let mut writer = BitWriter::new();
+        // ECI 26（UTF-8）
+        writer.push(0b0111, 4);
+        writer.push(26, 8);
+        // 数字 "01234"
+        writer.push(0b0001, 4);
+        writer.push(5, 10);
+        writer.push(12, 10);
+        writer.push(34, 7);
+        // 字母数字 "AC-"
+        writer.push(0b0010, 4);
+        writer.push(3, 9);
+        writer.push(10 * 45 + 12, 11);
+        writer.push(41, 6);
+        // 字节 "é"（UTF-8）
+        writer.push(0b0100, 4);
+        writer.push(2, 8);
+        writer.push(0xC3, 8);
+        writer.push(0xA9, 8);
+        writer.push(0, 4);
+
+        let (segments, structured_append) = parse_segments(&writer.into_bytes(), 1).unwrap();
+        assert_eq!(structured_append, None);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].data, b"01234");
+        assert_eq!(segments[1].data, b"AC-");
+        assert_eq!(segments[2].data, vec![0xC3, 0xA9]);
+        assert!(segments.iter().all(|s| s.eci == Some(26)));
+    }
+
+    #[test]
+    fn test_parse_structured_append_and_kanji() {
+        let mut writer = BitWriter::new();
+        writer.push(0b0011, 4);
+        writer.push(2, 4);
+        writer.push(4, 4);
+        writer.push(0x5A, 8);
+        // 汉字 "点"（Shift-JIS 0x935F）
+        writer.push(0b1000, 4);
+        writer.push(1, 8);
+        let assembled = 0x935F - 0x8140;
+        writer.push((assembled >> 8) * 0xC0 + (assembled & 0xFF), 13);
+        writer.push(0, 4);
+
+        let (segments, structured_append) = parse_segments(&writer.into_bytes(), 1).unwrap();
+        assert_eq!(
+            structured_append,
+            Some(StructuredAppend { index: 2, total: 5, parity: 0x5A })
+        );
+        assert_eq!(segments[0].mode, SegmentMode::Kanji);
+        assert_eq!(segments[0].data, vec![0x93, 0x5F]);
+    }
+
+    #[test]
+    fn test_parse_hanzi_segment() {
+        let mut writer = BitWriter::new();
+        // 汉字 "啊"（GB2312 0xB0A1）
+        writer.push(0b1101, 4);
+        writer.push(1, 4);
+        writer.push(1, 8);
+        let assembled = 0xB0A1 - 0xA6A1;
+        writer.push((assembled >> 8) * 0x60 + (assembled & 0xFF), 13);
+
+        let (segments, _) = parse_segments(&writer.into_bytes(), 1).unwrap();
+        assert_eq!(segments[0].mode, SegmentMode::Hanzi);
+        assert_eq!(segments[0].data, vec![0xB0, 0xA1]);
+    }
+
+    #[test]
+    fn test_read_eci_designator_multi_byte() {
+        let mut writer = BitWriter::new();
+        writer.push(0b10_000011, 8);
+        writer.push(0xE8, 8);
+        let bytes = writer.into_bytes();
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(read_eci_designator(&mut reader).unwrap(), 1000);
+    }
+}
@@ -0,0 +1,171 @@
+//! 二维码识别预处理流水线配置与诊断信息
+//! 流水线由若干 (缩放, 预处理) 策略组成，可由用户调整顺序、启用状态和缩放比例
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::qr_scanner::PreprocessMethod;
+
+/// 允许的最小缩放比例
+pub const MIN_SCALE: f32 = 0.1;
+/// 允许的最大缩放比例（放大过多会占用大量内存）
+pub const MAX_SCALE: f32 = 4.0;
+
+/// 单个识别策略
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScanStrategy {
+    /// 缩放比例（1.0 为原图）
+    pub scale: f32,
+    /// 预处理方法
+    pub method: PreprocessMethod,
+    /// 是否启用
+    pub enabled: bool,
+}
+
+impl ScanStrategy {
+    pub const fn new(scale: f32, method: PreprocessMethod) -> Self {
+        Self { scale, method, enabled: true }
+    }
+}
+
+/// 识别流水线：策略按优先级排列，按缩放比例分组并行执行，找到结果后取消其余策略
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanPipeline {
+    pub strategies: Vec<ScanStrategy>,
+    /// 保留相同码的每一处位置（默认不同位置的相同码合并为一条结果，记录出现次数和所有位置）
+    #[serde(default)]
+    pub keep_duplicates: bool,
+}
+
+impl Default for ScanPipeline {
+    fn default() -> Self {
+        Self {
+            strategies: vec![
+                ScanStrategy::new(1.0, PreprocessMethod::None),          // 1. 原图
+                ScanStrategy::new(1.0, PreprocessMethod::ContrastBoost), // 2. 增强对比度
+                ScanStrategy::new(1.0, PreprocessMethod::Brightness),    // 3. 增加亮度
+                ScanStrategy::new(2.0, PreprocessMethod::None),          // 4. 放大2倍
+                ScanStrategy::new(1.0, PreprocessMethod::Sharpen),       // 5. 锐化
+                ScanStrategy::new(1.5, PreprocessMethod::ContrastBoost), // 6. 放大1.5倍+对比度
+                ScanStrategy::new(0.5, PreprocessMethod::ContrastBoost), // 7. 缩小0.5倍+对比度
+                ScanStrategy::new(3.0, PreprocessMethod::ContrastBoost), // 8. 放大3倍+对比度
+                ScanStrategy::new(1.0, PreprocessMethod::Invert),        // 9. 反色（处理白色二维码）
+                ScanStrategy::new(1.0, PreprocessMethod::Sauvola),       // 10. 自适应二值化（光照不均）
+                ScanStrategy::new(1.0, PreprocessMethod::Otsu),          // 11. Otsu 二值化
+                ScanStrategy::new(1.0, PreprocessMethod::Median),        // 12. 中值去噪
+                ScanStrategy::new(1.0, PreprocessMethod::MorphClose),    // 13. 闭运算（模块断裂）
+                ScanStrategy::new(1.0, PreprocessMethod::RedChannel),    // 14~16. 单色通道（彩色二维码）
+                ScanStrategy::new(1.0, PreprocessMethod::GreenChannel),
+                ScanStrategy::new(1.0, PreprocessMethod::BlueChannel),
+                ScanStrategy::new(1.0, PreprocessMethod::Mirror),        // 17. 水平镜像
+            ],
+            keep_duplicates: false,
+        }
+    }
+}
+
+impl ScanPipeline {
+    /// 校验流水线配置
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.strategies.iter().any(|s| s.enabled) {
+            return Err(Error::InvalidPipeline("至少需要启用一个识别策略".to_string()));
+        }
+        for (index, strategy) in self.strategies.iter().enumerate() {
+            if !(MIN_SCALE..=MAX_SCALE).contains(&strategy.scale) {
+                return Err(Error::InvalidPipeline(format!(
+                    "策略 {} 的缩放比例 {} 超出范围（{}~{}）",
+                    index + 1,
+                    strategy.scale,
+                    MIN_SCALE,
+                    MAX_SCALE
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 已启用的策略及其在流水线中的序号
+    pub fn enabled(&self) -> impl Iterator<Item = (usize, &ScanStrategy)> {
+        self.strategies.iter().enumerate().filter(|(_, s)| s.enabled)
+    }
+}
+
+/// 单个策略的执行情况
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyDiagnostics {
+    /// 策略在流水线中的序号（从 0 开始）
+    pub index: usize,
+    pub scale: f32,
+    pub method: PreprocessMethod,
+    /// 耗时（毫秒）
+    pub elapsed_ms: u64,
+    /// 检测到的二维码网格数
+    pub grids_detected: usize,
+    /// 成功解码的码数（含其他码制）
+    pub decoded: usize,
+    /// 是否因其他策略已找到结果而提前取消
+    pub cancelled: bool,
+}
+
+/// 单张图像的扫描诊断信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanDiagnostics {
+    pub image_width: u32,
+    pub image_height: u32,
+    /// 按流水线顺序排列的策略执行情况（未启用的策略不包含在内）
+    pub strategies: Vec<StrategyDiagnostics>,
+    /// 第一个找到结果的策略序号
+    pub succeeded: Option<usize>,
+    /// 总耗时（毫秒）
+    pub total_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_pipeline_is_valid() {
+        let pipeline = ScanPipeline::default();
+        assert!(pipeline.validate().is_ok());
+        assert_eq!(pipeline.enabled().count(), pipeline.strategies.len());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_config() {
+        let mut pipeline = ScanPipeline::default();
+        pipeline.strategies[3].scale = 10.0;
+        assert!(pipeline.validate().is_err());
+
+        let mut pipeline = ScanPipeline::default();
+        pipeline.strategies.iter_mut().for_each(|s| s.enabled = false);
+        assert!(pipeline.validate().is_err());
+    }
+
+    #[test]
+    fn test_enabled_keeps_pipeline_index() {
+        let mut pipeline = ScanPipeline::default();
+        pipeline.strategies[0].enabled = false;
+        let first = pipeline.enabled().next().unwrap();
+        assert_eq!(first.0, 1);
+        assert_eq!(first.1.method, PreprocessMethod::ContrastBoost);
+    }
+
+    #[test]
+    fn test_pipeline_serde_roundtrip() {
+        let pipeline = ScanPipeline::default();
+        let json = serde_json::to_string(&pipeline).unwrap();
+        assert!(json.contains("\"ContrastBoost\""));
+        let parsed: ScanPipeline = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, pipeline);
+    }
+
+    #[test]
+    fn test_pipeline_without_keep_duplicates() {
+        // 旧版本保存的配置没有 keep_duplicates 字段
+        let json = r#"{"strategies":[{"scale":1.0,"method":"None","enabled":true}]}"#;
+        let parsed: ScanPipeline = serde_json::from_str(json).unwrap();
+        assert!(!parsed.keep_duplicates);
+        assert!(parsed.validate().is_ok());
+    }
+}
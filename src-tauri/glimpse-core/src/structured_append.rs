@@ -0,0 +1,259 @@
+//! 结构化追加（Structured Append）拼接模块
+//! 将同一条消息拆分出的多个二维码按序号拼接，并校验奇偶校验字节
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::charset;
+use crate::qr_scanner::{BarcodeFormat, QrCodeResult};
+use crate::qr_symbol::Segment;
+
+/// 结构化追加拼接状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructuredAppendInfo {
+    /// 符号总数
+    pub total: u8,
+    /// 已找到的符号序号（从 0 开始，升序）
+    pub found: Vec<u8>,
+    /// 奇偶校验是否通过（仅在所有部分都找到时校验）
+    pub parity_ok: Option<bool>,
+}
+
+impl StructuredAppendInfo {
+    /// 是否找到了所有部分
+    pub fn is_complete(&self) -> bool {
+        self.found.len() == self.total as usize
+    }
+}
+
+/// 不完整序列中缺失部分的占位符
+pub const MISSING_PART_MARKER: &str = "[…]";
+
+/// 分组键：同一条消息的各部分具有相同的奇偶校验字节和符号总数
+type GroupKey = (u8, u8);
+
+/// 读取结果的结构化追加头（只有一个符号的序列视为普通二维码）
+fn group_key(result: &QrCodeResult) -> Option<(GroupKey, u8)> {
+    let header = result.symbol.as_ref()?.structured_append?;
+    (header.total > 1).then_some(((header.parity, header.total), header.index))
+}
+
+/// 检查是否存在尚未找齐的结构化追加序列
+pub fn has_missing_parts(results: &[QrCodeResult]) -> bool {
+    let mut groups: Vec<(GroupKey, Vec<u8>)> = Vec::new();
+    for (key, index) in results.iter().filter_map(group_key) {
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, indices)) if !indices.contains(&index) => indices.push(index),
+            Some(_) => {}
+            None => groups.push((key, vec![index])),
+        }
+    }
+    groups.iter().any(|((_, total), indices)| indices.len() < *total as usize)
+}
+
+/// 拼接结构化追加序列，其他结果保持原样
+///
+/// 每个序列合并为一条结果：内容按序号拼接，角点为各部分角点依次排列；
+/// 序列不完整时只拼接序号相邻的部分，缺失处插入 [`MISSING_PART_MARKER`]，`found` 记录已找到的序号
+pub fn merge(results: Vec<QrCodeResult>) -> Vec<QrCodeResult> {
+    let mut merged = Vec::with_capacity(results.len());
+    let mut groups: Vec<(GroupKey, Vec<(u8, QrCodeResult)>)> = Vec::new();
+
+    for result in results {
+        match group_key(&result) {
+            Some((key, index)) => match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, parts)) => parts.push((index, result)),
+                None => groups.push((key, vec![(index, result)])),
+            },
+            None => merged.push(result),
+        }
+    }
+
+    for ((parity, total), mut parts) in groups {
+        parts.sort_by_key(|(index, _)| *index);
+        parts.dedup_by_key(|(index, _)| *index);
+        merged.push(combine_parts(parity, total, parts));
+    }
+
+    merged
+}
+
+/// 合并同一序列的各部分
+fn combine_parts(parity: u8, total: u8, parts: Vec<(u8, QrCodeResult)>) -> QrCodeResult {
+    let found: Vec<u8> = parts.iter().map(|(index, _)| *index).collect();
+    let segments: Vec<Segment> = parts
+        .iter()
+        .filter_map(|(_, part)| part.symbol.as_ref())
+        .flat_map(|symbol| symbol.segments.iter().cloned())
+        .collect();
+    let raw_bytes: Vec<u8> = segments.iter().flat_map(|s| s.data.iter().copied()).collect();
+
+    // 奇偶校验字节为整条消息所有字节的异或
+    let complete = found.len() == total as usize;
+    let parity_ok = complete.then(|| raw_bytes.iter().fold(0u8, |acc, b| acc ^ b) == parity);
+
+    debug_println!(
+        "[DEBUG] 结构化追加: 找到 {}/{} 部分，奇偶校验: {:?}",
+        found.len(),
+        total,
+        parity_ok
+    );
+
+    // 不相邻的部分之间缺少数据，直接拼接字节可能截断多字节字符，因此按连续序号分段解码
+    let decoded = charset::decode_segments(&segments);
+    let text = if complete { decoded.text } else { join_runs(total, &parts) };
+    let points = parts.iter().flat_map(|(_, part)| part.points.iter().copied()).collect();
+
+    let first = &parts[0].1;
+    let mut result = QrCodeResult::new(text, BarcodeFormat::QrCode, points);
+    result.screen = first.screen;
+    result.frame = first.frame;
+    // 各部分的位置依次排列；完整出现的次数取各部分出现次数的最小值
+    result.count = parts.iter().map(|(_, part)| part.count).min().unwrap_or(1);
+    result.locations = parts.iter().flat_map(|(_, part)| part.locations.iter().cloned()).collect();
+    result.symbol = first.symbol.clone().map(|mut symbol| {
+        symbol.charset = decoded.charset.to_string();
+        symbol.raw_bytes = base64::engine::general_purpose::STANDARD.encode(&raw_bytes);
+        symbol.segments = segments;
+        symbol
+    });
+    result.structured_append = Some(StructuredAppendInfo { total, found, parity_ok });
+    result
+}
+
+/// 拼接不完整序列：序号连续的部分合并解码，缺失的部分（含开头和结尾）以占位符表示
+fn join_runs(total: u8, parts: &[(u8, QrCodeResult)]) -> String {
+    let mut text = String::new();
+    let mut run: Vec<Segment> = Vec::new();
+    let mut expected = 0u8;
+
+    for (index, part) in parts {
+        if *index != expected {
+            text.push_str(&charset::decode_segments(&run).text);
+            text.push_str(MISSING_PART_MARKER);
+            run.clear();
+        }
+        if let Some(symbol) = &part.symbol {
+            run.extend(symbol.segments.iter().cloned());
+        }
+        expected = index + 1;
+    }
+    text.push_str(&charset::decode_segments(&run).text);
+    if expected < total {
+        text.push_str(MISSING_PART_MARKER);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_scanner::{Point, QrSymbolInfo};
+    use crate::qr_symbol::{EccLevel, SegmentMode, StructuredAppend};
+
+    /// 构造结构化追加序列中的一个部分
+    fn part(text: &str, index: u8, total: u8, parity: u8) -> QrCodeResult {
+        let segments = vec![Segment {
+            mode: SegmentMode::Byte,
+            data: text.as_bytes().to_vec(),
+            eci: None,
+        }];
+        let points = vec![Point { x: index as f32, y: 0.0 }];
+        let mut result = QrCodeResult::new(text.to_string(), BarcodeFormat::QrCode, points);
+        result.symbol = Some(QrSymbolInfo {
+            version: 1,
+            ecc_level: EccLevel::M,
+            mask: 0,
+            eci: None,
+            charset: "UTF-8".to_string(),
+            raw_bytes: String::new(),
+            structured_append: Some(StructuredAppend { index, total, parity }),
+            segments,
+        });
+        result
+    }
+
+    fn parity_of(message: &str) -> u8 {
+        message.bytes().fold(0, |acc, b| acc ^ b)
+    }
+
+    #[test]
+    fn test_merge_complete_sequence_out_of_order() {
+        let message = "https://example.com/very/long/path";
+        let parity = parity_of(message);
+        let results = vec![
+            part("/long/path", 2, 3, parity),
+            part("https://exa", 0, 3, parity),
+            part("mple.com/very", 1, 3, parity),
+        ];
+        assert!(!has_missing_parts(&results));
+
+        let merged = merge(results);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].content, message);
+        let info = merged[0].structured_append.as_ref().unwrap();
+        assert!(info.is_complete());
+        assert_eq!(info.found, vec![0, 1, 2]);
+        assert_eq!(info.parity_ok, Some(true));
+        assert_eq!(merged[0].points.len(), 3);
+        assert_eq!(merged[0].points[0].x, 0.0);
+    }
+
+    #[test]
+    fn test_merge_detects_parity_mismatch() {
+        let parity = parity_of("abcdef") ^ 0xFF;
+        let merged = merge(vec![part("abc", 0, 2, parity), part("def", 1, 2, parity)]);
+        assert_eq!(merged[0].content, "abcdef");
+        assert_eq!(merged[0].structured_append.as_ref().unwrap().parity_ok, Some(false));
+    }
+
+    #[test]
+    fn test_merge_incomplete_sequence() {
+        let parity = 0x42;
+        let results = vec![
+            part("A", 0, 5, parity),
+            part("C", 2, 5, parity),
+            part("C", 2, 5, parity),
+            part("E", 4, 5, parity),
+        ];
+        assert!(has_missing_parts(&results));
+
+        let merged = merge(results);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].content, "A[…]C[…]E");
+        let info = merged[0].structured_append.as_ref().unwrap();
+        assert_eq!(info.found, vec![0, 2, 4]);
+        assert_eq!(info.total, 5);
+        assert_eq!(info.parity_ok, None);
+    }
+
+    #[test]
+    fn test_merge_incomplete_sequence_keeps_characters_intact() {
+        // "你好" 的 UTF-8 字节跨越第 0、1 部分，缺失第 1 部分时不能把第 0、2 部分的字节直接拼接
+        let bytes = "你好世界".as_bytes();
+        let segment = |data: &[u8]| Segment { mode: SegmentMode::Byte, data: data.to_vec(), eci: None };
+        let mut first = part("", 0, 4, 0x42);
+        first.symbol.as_mut().unwrap().segments = vec![segment(&bytes[..3])];
+        let mut third = part("", 2, 4, 0x42);
+        third.symbol.as_mut().unwrap().segments = vec![segment(&bytes[6..9])];
+
+        let merged = merge(vec![third, first]);
+        assert_eq!(merged[0].content, "你[…]世[…]");
+    }
+
+    #[test]
+    fn test_merge_keeps_independent_results() {
+        let plain = QrCodeResult::new("hello".to_string(), BarcodeFormat::QrCode, vec![]);
+        let merged = merge(vec![
+            plain,
+            part("x", 0, 2, 0x10),
+            part("y", 1, 2, 0x10),
+            part("z", 0, 2, 0x20),
+        ]);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].content, "hello");
+        assert!(merged[0].structured_append.is_none());
+        assert_eq!(merged[1].content, "xy");
+        assert_eq!(merged[2].content, "z[…]");
+    }
+}
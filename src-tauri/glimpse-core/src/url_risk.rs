@@ -0,0 +1,392 @@
+//! 二维码链接钓鱼风险分析（离线）
+//! 不访问网络，仅根据链接本身的特征判断：危险协议、@ 前缀伪装、IP 主机、
+//! 国际化域名（Punycode）仿冒、混用文字、短链接、过深的子域名和非标准端口
+
+use serde::{Deserialize, Serialize};
+use url::{Host, Url};
+
+use crate::payload::percent_decode;
+
+/// 可注册域名之前最多允许的子域名层数，超过视为子域名过深（如 login.secure.bank.com.evil.tld）
+pub const MAX_SUBDOMAIN_LABELS: usize = 3;
+
+/// 常见的两级公共后缀，其下一级才是可注册域名（不查完整的公共后缀列表）
+const MULTI_LABEL_SUFFIXES: &[&str] = &[
+    "co.uk", "org.uk", "ac.uk", "gov.uk", "com.cn", "net.cn", "org.cn", "gov.cn", "edu.cn", "com.hk",
+    "com.tw", "com.au", "net.au", "org.au", "co.jp", "ne.jp", "or.jp", "co.kr", "co.nz", "co.in",
+    "com.br", "com.sg", "com.my", "co.za", "com.mx", "com.tr",
+];
+
+/// 常见短链接服务（真实地址被隐藏）
+const SHORTENER_DOMAINS: &[&str] = &[
+    "bit.ly", "t.co", "tinyurl.com", "goo.gl", "ow.ly", "is.gd", "v.gd", "buff.ly", "rebrand.ly",
+    "cutt.ly", "t.ly", "rb.gy", "shorturl.at", "tiny.cc", "s.id", "bl.ink", "t.cn", "url.cn",
+    "dwz.cn", "suo.im", "mrw.so", "u.nu",
+];
+
+/// 形似拉丁字母的西里尔字母和希腊字母（域名经 IDNA 处理后均为小写）
+const LATIN_LOOKALIKES: &[char] = &[
+    'а', 'в', 'е', 'к', 'м', 'н', 'о', 'р', 'с', 'т', 'у', 'х', 'і', 'ј', 'ѕ', 'һ', 'ԁ', 'ԛ', 'ԝ',
+    'ӏ', 'ү', 'α', 'ε', 'ι', 'κ', 'ν', 'ο', 'ρ', 'τ', 'υ', 'χ',
+];
+
+/// 风险等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RiskLevel {
+    Safe,
+    Low,
+    Medium,
+    High,
+}
+
+/// 风险类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RiskKind {
+    /// javascript:、data: 等可执行脚本或读取本地内容的协议
+    DangerousScheme,
+    /// 链接无法解析
+    Malformed,
+    /// 主机名前带用户信息（https://bank.com@evil.tld）
+    UserInfo,
+    /// 主机为 IP 地址
+    IpAddress,
+    /// 国际化域名（Punycode 编码）
+    Punycode,
+    /// 同一段域名混用多种文字
+    MixedScript,
+    /// 全部由形似拉丁字母的其他文字组成
+    Confusable,
+    /// 短链接
+    Shortener,
+    /// 子域名层级过深
+    DeepSubdomain,
+    /// 非标准端口
+    NonStandardPort,
+}
+
+/// 单条风险原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskReason {
+    pub kind: RiskKind,
+    pub level: RiskLevel,
+    /// 面向用户的说明
+    pub message: String,
+}
+
+/// 链接风险分析结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlRisk {
+    /// 综合风险等级（各原因中的最高等级）
+    pub level: RiskLevel,
+    /// 实际访问的主机（国际化域名显示为 Unicode 形式）
+    pub host: Option<String>,
+    pub reasons: Vec<RiskReason>,
+}
+
+/// 分析链接的钓鱼风险，内容不是链接时返回 None
+pub fn analyze_url(content: &str) -> Option<UrlRisk> {
+    let text = content.trim();
+    let scheme = scheme_of(text)?;
+    let mut reasons = Vec::new();
+
+    match scheme.as_str() {
+        "javascript" | "vbscript" | "data" | "file" => {
+            reasons.push(reason(
+                RiskKind::DangerousScheme,
+                RiskLevel::High,
+                format!("{}: 链接可直接执行脚本或读取本地内容", scheme),
+            ));
+            return Some(UrlRisk::new(None, reasons));
+        }
+        "http" | "https" => {}
+        _ => return None,
+    }
+
+    let url = match Url::parse(text) {
+        Ok(url) => url,
+        Err(e) => {
+            reasons.push(reason(RiskKind::Malformed, RiskLevel::Medium, format!("链接格式异常: {}", e)));
+            return Some(UrlRisk::new(None, reasons));
+        }
+    };
+
+    let host = match url.host() {
+        Some(Host::Domain(domain)) => {
+            check_domain(domain, &mut reasons);
+            Some(idna::domain_to_unicode(domain).0)
+        }
+        Some(Host::Ipv4(ip)) => {
+            reasons.push(ip_reason(&ip.to_string()));
+            Some(ip.to_string())
+        }
+        Some(Host::Ipv6(ip)) => {
+            reasons.push(ip_reason(&ip.to_string()));
+            Some(ip.to_string())
+        }
+        None => None,
+    };
+
+    // 浏览器会忽略 @ 之前的内容，常用于伪装成可信域名
+    if !url.username().is_empty() || url.password().is_some() {
+        let userinfo = match url.password() {
+            Some(password) => format!("{}:{}", url.username(), password),
+            None => url.username().to_string(),
+        };
+        reasons.push(reason(
+            RiskKind::UserInfo,
+            RiskLevel::High,
+            format!(
+                "链接中的“{}@”会被浏览器忽略，实际访问的是 {}",
+                percent_decode(&userinfo, false),
+                host.as_deref().unwrap_or("未知主机")
+            ),
+        ));
+    }
+
+    // url 对默认端口（80/443）返回 None
+    if let Some(port) = url.port() {
+        reasons.push(reason(RiskKind::NonStandardPort, RiskLevel::Low, format!("使用非标准端口 {}", port)));
+    }
+
+    Some(UrlRisk::new(host, reasons))
+}
+
+impl UrlRisk {
+    fn new(host: Option<String>, reasons: Vec<RiskReason>) -> Self {
+        let level = reasons.iter().map(|r| r.level).max().unwrap_or(RiskLevel::Safe);
+        Self { level, host, reasons }
+    }
+}
+
+fn reason(kind: RiskKind, level: RiskLevel, message: String) -> RiskReason {
+    RiskReason { kind, level, message }
+}
+
+fn ip_reason(ip: &str) -> RiskReason {
+    reason(RiskKind::IpAddress, RiskLevel::Medium, format!("直接使用 IP 地址 {} 而不是域名", ip))
+}
+
+/// 提取协议名（小写），不是 `协议:` 开头时返回 None
+pub(crate) fn scheme_of(text: &str) -> Option<String> {
+    let (scheme, _) = text.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then(|| scheme.to_ascii_lowercase())
+}
+
+/// 检查域名（ASCII 形式，国际化域名已编码为 xn--）
+fn check_domain(domain: &str, reasons: &mut Vec<RiskReason>) {
+    let domain = domain.trim_end_matches('.');
+    let labels: Vec<&str> = domain.split('.').collect();
+
+    for label in &labels {
+        let Some(encoded) = label.strip_prefix("xn--") else {
+            continue;
+        };
+        let Some(unicode) = idna::punycode::decode_to_string(encoded) else {
+            reasons.push(reason(RiskKind::Malformed, RiskLevel::Medium, format!("域名“{}”编码无效", label)));
+            continue;
+        };
+        reasons.push(script_reason(&unicode).unwrap_or_else(|| {
+            reason(
+                RiskKind::Punycode,
+                RiskLevel::Low,
+                format!("域名包含国际化字符“{}”（{}），请确认与预期一致", unicode, label),
+            )
+        }));
+    }
+
+    if let Some(shortener) = SHORTENER_DOMAINS
+        .iter()
+        .find(|s| domain.eq_ignore_ascii_case(s) || domain.to_ascii_lowercase().ends_with(&format!(".{}", s)))
+    {
+        reasons.push(reason(
+            RiskKind::Shortener,
+            RiskLevel::Low,
+            format!("{} 是短链接服务，真实地址被隐藏", shortener),
+        ));
+    }
+
+    let registrable = registrable_labels(&labels);
+    let subdomains = labels.len().saturating_sub(registrable);
+    if subdomains > MAX_SUBDOMAIN_LABELS {
+        reasons.push(reason(
+            RiskKind::DeepSubdomain,
+            RiskLevel::Medium,
+            format!(
+                "子域名层级过深（{} 级），实际访问的域名以 {} 结尾",
+                subdomains,
+                labels[labels.len() - registrable..].join(".")
+            ),
+        ));
+    }
+}
+
+/// 可注册域名占末尾几级：常见两级公共后缀下为三级，否则为两级
+fn registrable_labels(labels: &[&str]) -> usize {
+    let suffix = labels[labels.len().saturating_sub(2)..].join(".");
+    let levels = if MULTI_LABEL_SUFFIXES.contains(&suffix.as_str()) { 3 } else { 2 };
+    levels.min(labels.len())
+}
+
+/// 文字类别（仅区分域名仿冒中常见的几类）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Han,
+    Kana,
+    Hangul,
+    Other,
+}
+
+impl Script {
+    /// 数字、连字符等通用字符返回 None
+    fn of(c: char) -> Option<Self> {
+        match c {
+            '0'..='9' | '-' | '_' => None,
+            'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' => Some(Script::Latin),
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Some(Script::Greek),
+            '\u{0400}'..='\u{052F}' => Some(Script::Cyrillic),
+            '\u{0530}'..='\u{058F}' => Some(Script::Armenian),
+            '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => Some(Script::Han),
+            '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' => Some(Script::Kana),
+            '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' => Some(Script::Hangul),
+            _ => Some(Script::Other),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Script::Latin => "拉丁",
+            Script::Greek => "希腊",
+            Script::Cyrillic => "西里尔",
+            Script::Armenian => "亚美尼亚",
+            Script::Han => "汉字",
+            Script::Kana => "假名",
+            Script::Hangul => "韩文",
+            Script::Other => "其他",
+        }
+    }
+}
+
+/// 检查国际化域名标签的文字组合，混用文字或全部为拉丁形似字母时返回风险原因
+fn script_reason(label: &str) -> Option<RiskReason> {
+    let mut scripts: Vec<Script> = Vec::new();
+    for script in label.chars().filter_map(Script::of) {
+        if !scripts.contains(&script) {
+            scripts.push(script);
+        }
+    }
+
+    // 参照 Unicode TR39 “高度限制”级别：拉丁字母只允许与中日韩文字组合
+    let allowed = |set: &[Script]| scripts.iter().all(|s| set.contains(s));
+    let compatible = scripts.len() <= 1
+        || allowed(&[Script::Latin, Script::Han, Script::Kana])
+        || allowed(&[Script::Latin, Script::Han, Script::Hangul]);
+
+    if !compatible {
+        let names: Vec<&str> = scripts.iter().map(|s| s.name()).collect();
+        return Some(reason(
+            RiskKind::MixedScript,
+            RiskLevel::High,
+            format!("域名“{}”混用了{}字符，可能仿冒相似域名", label, names.join("、")),
+        ));
+    }
+
+    let all_lookalikes = label.chars().filter(|c| Script::of(*c).is_some()).all(|c| LATIN_LOOKALIKES.contains(&c));
+    if matches!(scripts.as_slice(), [Script::Cyrillic] | [Script::Greek]) && all_lookalikes {
+        return Some(reason(
+            RiskKind::Confusable,
+            RiskLevel::High,
+            format!("域名“{}”全部由形似拉丁字母的{}字母组成，可能仿冒英文域名", label, scripts[0].name()),
+        ));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(content: &str) -> (RiskLevel, Vec<RiskKind>) {
+        let risk = analyze_url(content).expect("应识别为链接");
+        (risk.level, risk.reasons.iter().map(|r| r.kind).collect())
+    }
+
+    #[test]
+    fn test_safe_and_non_urls() {
+        assert_eq!(kinds("https://example.com/login?next=/"), (RiskLevel::Safe, vec![]));
+        assert_eq!(kinds("HTTPS://WWW.EXAMPLE.COM/A"), (RiskLevel::Safe, vec![]));
+        assert!(analyze_url("WIFI:T:WPA;S:office;P:secret;;").is_none());
+        assert!(analyze_url("mailto:a@example.com").is_none());
+        assert!(analyze_url("hello world").is_none());
+    }
+
+    #[test]
+    fn test_dangerous_schemes() {
+        assert_eq!(kinds("javascript:alert(1)"), (RiskLevel::High, vec![RiskKind::DangerousScheme]));
+        assert_eq!(kinds("DATA:text/html,<script>"), (RiskLevel::High, vec![RiskKind::DangerousScheme]));
+    }
+
+    #[test]
+    fn test_userinfo() {
+        let risk = analyze_url("https://bank.com@evil.tld/login").unwrap();
+        assert_eq!(risk.level, RiskLevel::High);
+        assert_eq!(risk.host.as_deref(), Some("evil.tld"));
+        assert_eq!(risk.reasons[0].kind, RiskKind::UserInfo);
+        assert!(risk.reasons[0].message.contains("bank.com@"));
+    }
+
+    #[test]
+    fn test_ip_hosts_and_ports() {
+        assert_eq!(kinds("http://192.168.1.1/admin"), (RiskLevel::Medium, vec![RiskKind::IpAddress]));
+        // 十进制整数形式的 IP 会被规范化为点分形式
+        let risk = analyze_url("http://3232235777/").unwrap();
+        assert_eq!(risk.host.as_deref(), Some("192.168.1.1"));
+        assert_eq!(kinds("http://[::1]:8080/"), (RiskLevel::Medium, vec![RiskKind::IpAddress, RiskKind::NonStandardPort]));
+        assert_eq!(kinds("https://example.com:8443/"), (RiskLevel::Low, vec![RiskKind::NonStandardPort]));
+        assert_eq!(kinds("https://example.com:443/"), (RiskLevel::Safe, vec![]));
+    }
+
+    #[test]
+    fn test_homographs() {
+        // 第一个字母为西里尔字母 а
+        let risk = analyze_url("https://\u{0430}pple.com/").unwrap();
+        assert_eq!(risk.level, RiskLevel::High);
+        assert_eq!(risk.reasons[0].kind, RiskKind::MixedScript);
+        assert_eq!(risk.host.as_deref(), Some("\u{0430}pple.com"));
+
+        // 全部为西里尔字母的 аррӏе
+        assert_eq!(kinds("https://\u{0430}\u{0440}\u{0440}\u{04CF}\u{0435}.com/"), (RiskLevel::High, vec![RiskKind::Confusable]));
+
+        // 正常的中文域名只提示确认
+        assert_eq!(kinds("https://例子.中国/"), (RiskLevel::Low, vec![RiskKind::Punycode, RiskKind::Punycode]));
+    }
+
+    #[test]
+    fn test_shortener_and_depth() {
+        assert_eq!(kinds("https://bit.ly/3xYz"), (RiskLevel::Low, vec![RiskKind::Shortener]));
+        assert_eq!(kinds("https://T.CN/abc"), (RiskLevel::Low, vec![RiskKind::Shortener]));
+        assert_eq!(kinds("https://notbit.ly/"), (RiskLevel::Safe, vec![]));
+
+        let risk = analyze_url("https://login.secure.bank.com.evil.tld/").unwrap();
+        assert_eq!(risk.level, RiskLevel::Medium);
+        assert_eq!(risk.reasons[0].kind, RiskKind::DeepSubdomain);
+        assert!(risk.reasons[0].message.contains("evil.tld"));
+    }
+
+    #[test]
+    fn test_multi_label_suffix_depth() {
+        assert_eq!(kinds("https://www.news.bbc.co.uk/"), (RiskLevel::Safe, vec![]));
+        assert_eq!(kinds("https://bucket.s3.us-east-1.amazonaws.com/key"), (RiskLevel::Safe, vec![]));
+
+        let risk = analyze_url("https://a.b.c.d.bank.co.uk/").unwrap();
+        assert_eq!(risk.reasons[0].kind, RiskKind::DeepSubdomain);
+        assert!(risk.reasons[0].message.contains("bank.co.uk"));
+    }
+}
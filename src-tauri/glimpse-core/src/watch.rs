@@ -0,0 +1,251 @@
+//! 持续监视模式
+//! 按固定间隔重新截取屏幕或指定区域，画面未变化时跳过识别，
+//! 只报告本次监视期间首次出现的码（适用于仪表盘、视频会议中短暂出现的二维码）
+
+use image::imageops::{self, FilterType};
+use image::GrayImage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::error::{Error, Result};
+use crate::qr_scanner::{scan_multiple_images, BarcodeFormat, QrCodeResult};
+use crate::scan_pipeline::ScanPipeline;
+use crate::screenshot::ScreenCapture;
+
+/// 最小截取间隔（毫秒）
+pub const MIN_INTERVAL_MS: u64 = 200;
+/// 最大截取间隔（毫秒）
+pub const MAX_INTERVAL_MS: u64 = 60_000;
+
+/// 画面指纹的边长：缩小为 64x64 灰度图后逐格比较
+const SIGNATURE_SIZE: u32 = 64;
+/// 任一格亮度变化超过该值时视为画面已变化（过滤视频压缩、抗锯齿等细微噪声）
+const CHANGE_THRESHOLD: u8 = 6;
+
+/// 监视目标
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum WatchTarget {
+    /// 所有屏幕
+    AllScreens,
+    /// 单个屏幕
+    Screen { index: usize },
+    /// 屏幕上的矩形区域（物理像素，相对于该屏幕左上角）
+    Region { screen: usize, x: u32, y: u32, width: u32, height: u32 },
+}
+
+/// 监视配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchOptions {
+    pub target: WatchTarget,
+    /// 截取间隔（毫秒）
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_interval_ms() -> u64 {
+    1000
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self { target: WatchTarget::AllScreens, interval_ms: default_interval_ms() }
+    }
+}
+
+impl WatchOptions {
+    /// 校验监视配置
+    pub fn validate(&self) -> Result<()> {
+        if !(MIN_INTERVAL_MS..=MAX_INTERVAL_MS).contains(&self.interval_ms) {
+            return Err(Error::Capture(format!(
+                "监视间隔 {}ms 超出范围（{}~{}ms）",
+                self.interval_ms, MIN_INTERVAL_MS, MAX_INTERVAL_MS
+            )));
+        }
+        if let WatchTarget::Region { width, height, .. } = self.target {
+            if width == 0 || height == 0 {
+                return Err(Error::Capture("监视区域不能为空".to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 按监视目标选取的截图
+struct Selection {
+    captures: Vec<ScreenCapture>,
+    /// 区域左上角在屏幕中的偏移
+    offset: (f32, f32),
+}
+
+/// 监视状态：上一帧的画面指纹和已报告过的码
+pub struct Watcher {
+    target: WatchTarget,
+    signatures: Vec<GrayImage>,
+    seen: HashSet<(BarcodeFormat, String)>,
+}
+
+impl Watcher {
+    pub fn new(target: WatchTarget) -> Self {
+        Self { target, signatures: Vec::new(), seen: HashSet::new() }
+    }
+
+    /// 处理一次截图（`screens` 为所有屏幕的截图）
+    ///
+    /// 画面与上一次相比没有变化时返回 None；否则识别并返回首次出现的码（可能为空）
+    pub fn tick(&mut self, screens: Vec<ScreenCapture>, pipeline: &ScanPipeline) -> Result<Option<Vec<QrCodeResult>>> {
+        let Selection { captures, offset } = self.select(screens)?;
+
+        let signatures: Vec<GrayImage> = captures.iter().map(|c| signature(&c.image)).collect();
+        if !is_changed(&self.signatures, &signatures) {
+            return Ok(None);
+        }
+        self.signatures = signatures;
+
+        let report = scan_multiple_images(&captures, pipeline)?;
+        let mut new_codes = Vec::new();
+        for mut result in report.results {
+            if self.seen.insert((result.format, result.content.clone())) {
+                // 区域坐标换算回整屏坐标
+                result.translate(offset.0, offset.1);
+                new_codes.push(result);
+            }
+        }
+
+        debug_println!("[DEBUG] 监视: 画面已变化，新发现 {} 个码（累计 {} 个）", new_codes.len(), self.seen.len());
+        Ok(Some(new_codes))
+    }
+
+    /// 按监视目标选取屏幕并裁剪区域
+    fn select(&self, mut screens: Vec<ScreenCapture>) -> Result<Selection> {
+        let mut take = |index: usize| {
+            let count = screens.len();
+            (index < count)
+                .then(|| screens.swap_remove(index))
+                .ok_or_else(|| Error::Capture(format!("屏幕 {} 不存在（共 {} 个屏幕）", index + 1, count)))
+        };
+
+        match self.target {
+            WatchTarget::AllScreens => Ok(Selection { captures: screens, offset: (0.0, 0.0) }),
+            WatchTarget::Screen { index } => {
+                Ok(Selection { captures: vec![take(index)?], offset: (0.0, 0.0) })
+            }
+            WatchTarget::Region { screen, x, y, width, height } => {
+                let capture = take(screen)?;
+                let (image_width, image_height) = (capture.image.width(), capture.image.height());
+                if x >= image_width || y >= image_height {
+                    return Err(Error::Capture("监视区域超出屏幕范围".to_string()));
+                }
+                let width = width.min(image_width - x);
+                let height = height.min(image_height - y);
+                let image = capture.image.crop_imm(x, y, width, height);
+                Ok(Selection {
+                    captures: vec![ScreenCapture { image, ..capture }],
+                    offset: (x as f32, y as f32),
+                })
+            }
+        }
+    }
+}
+
+/// 画面指纹：缩小后的灰度图
+fn signature(image: &image::DynamicImage) -> GrayImage {
+    imageops::resize(&image.to_luma8(), SIGNATURE_SIZE, SIGNATURE_SIZE, FilterType::Triangle)
+}
+
+/// 比较两组画面指纹，屏幕数量变化或任一格亮度变化超过阈值时视为已变化
+fn is_changed(previous: &[GrayImage], current: &[GrayImage]) -> bool {
+    previous.len() != current.len()
+        || previous.iter().zip(current).any(|(a, b)| {
+            a.as_raw().iter().zip(b.as_raw()).any(|(p, q)| p.abs_diff(*q) > CHANGE_THRESHOLD)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Rgb, RgbImage};
+
+    fn screen(image: RgbImage) -> Vec<ScreenCapture> {
+        vec![ScreenCapture { image: DynamicImage::ImageRgb8(image), index: 0, x: 0, y: 0, scale_factor: 1.0 }]
+    }
+
+    #[test]
+    fn test_unchanged_frames_are_skipped() {
+        let mut watcher = Watcher::new(WatchTarget::AllScreens);
+        let pipeline = ScanPipeline::default();
+        let blank = RgbImage::from_pixel(320, 240, Rgb([255, 255, 255]));
+
+        assert!(watcher.tick(screen(blank.clone()), &pipeline).unwrap().is_some());
+        assert!(watcher.tick(screen(blank.clone()), &pipeline).unwrap().is_none());
+
+        // 细微噪声不视为变化
+        let mut noisy = blank.clone();
+        noisy.put_pixel(10, 10, Rgb([250, 250, 250]));
+        assert!(watcher.tick(screen(noisy), &pipeline).unwrap().is_none());
+
+        // 出现一块明显的深色区域
+        let mut changed = blank;
+        for x in 100..140 {
+            for y in 100..140 {
+                changed.put_pixel(x, y, Rgb([0, 0, 0]));
+            }
+        }
+        assert!(watcher.tick(screen(changed), &pipeline).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_only_unseen_codes_are_reported() {
+        use crate::qr_generator::{generate_qr, QrGenerateOptions};
+
+        let code = |text: &str| {
+            let generated = generate_qr(text, &QrGenerateOptions::default()).unwrap();
+            image::load_from_memory(&generated.png).unwrap().to_rgb8()
+        };
+        let first = code("meeting-link-1");
+        let mut canvas = RgbImage::from_pixel(first.width() * 2, first.height(), Rgb([255, 255, 255]));
+        imageops::replace(&mut canvas, &first, 0, 0);
+
+        let mut watcher = Watcher::new(WatchTarget::AllScreens);
+        let pipeline = ScanPipeline::default();
+        let found = watcher.tick(screen(canvas.clone()), &pipeline).unwrap().unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].content, "meeting-link-1");
+
+        // 第二个码出现后只报告新的码
+        imageops::replace(&mut canvas, &code("meeting-link-2"), first.width() as i64, 0);
+        let found = watcher.tick(screen(canvas), &pipeline).unwrap().unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].content, "meeting-link-2");
+    }
+
+    #[test]
+    fn test_select_region() {
+        let watcher = Watcher::new(WatchTarget::Region { screen: 1, x: 10, y: 20, width: 500, height: 30 });
+        let screens = vec![
+            ScreenCapture { image: DynamicImage::new_rgb8(100, 100), index: 0, x: 0, y: 0, scale_factor: 1.0 },
+            ScreenCapture { image: DynamicImage::new_rgb8(200, 100), index: 1, x: 100, y: 0, scale_factor: 2.0 },
+        ];
+        let selection = watcher.select(screens.clone()).unwrap();
+        let captures = &selection.captures;
+        assert_eq!(captures.len(), 1);
+        assert_eq!((captures[0].image.width(), captures[0].image.height()), (190, 30));
+        assert_eq!((captures[0].index, captures[0].x), (1, 100));
+        assert_eq!(selection.offset, (10.0, 20.0));
+
+        assert!(Watcher::new(WatchTarget::Screen { index: 2 }).select(screens.clone()).is_err());
+        let outside = Watcher::new(WatchTarget::Region { screen: 0, x: 100, y: 0, width: 10, height: 10 });
+        assert!(outside.select(screens).is_err());
+    }
+
+    #[test]
+    fn test_validate_options() {
+        assert!(WatchOptions::default().validate().is_ok());
+        assert!(WatchOptions { interval_ms: 50, ..Default::default() }.validate().is_err());
+        let empty = WatchTarget::Region { screen: 0, x: 0, y: 0, width: 0, height: 10 };
+        assert!(WatchOptions { target: empty, interval_ms: 1000 }.validate().is_err());
+
+        let parsed: WatchOptions = serde_json::from_str(r#"{"target":{"type":"Screen","index":1}}"#).unwrap();
+        assert_eq!(parsed, WatchOptions { target: WatchTarget::Screen { index: 1 }, interval_ms: 1000 });
+    }
+}
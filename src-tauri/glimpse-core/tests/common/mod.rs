@@ -0,0 +1,277 @@
+//! 确定性的二维码测试语料
+//! 由固定种子生成不同尺寸、旋转、模糊、JPEG 压缩、低对比度、反色、复杂背景等样本，
+//! 供识别率测试（tests/recall.rs）和性能基准（benches/scan.rs）共用
+
+#![allow(dead_code)]
+
+use glimpse_core::image::codecs::jpeg::JpegEncoder;
+use glimpse_core::image::{self, imageops, DynamicImage, Rgb, RgbImage};
+use glimpse_core::{generate_qr, QrGenerateOptions};
+
+/// 样本的退化类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Degradation {
+    /// 标准尺寸（每模块 6 像素）
+    Clean,
+    /// 每模块 3 像素
+    Small,
+    /// 每模块 12 像素
+    Large,
+    /// 旋转 15°、30°、45°
+    Rotated,
+    /// 高斯模糊
+    Blurred,
+    /// JPEG 低质量压缩
+    Jpeg,
+    /// 低对比度（灰底深灰码）
+    LowContrast,
+    /// 反色（黑底白码）
+    Inverted,
+    /// 水平镜像
+    Mirrored,
+    /// 彩色前景和背景
+    Colored,
+    /// 光照不均（从左到右逐渐变暗）
+    UnevenLight,
+    /// 嵌入杂乱背景
+    BusyBackground,
+}
+
+/// 一个测试样本
+pub struct Sample {
+    pub name: String,
+    pub degradation: Degradation,
+    /// 期望解码出的内容
+    pub content: String,
+    pub image: DynamicImage,
+}
+
+/// 样本内容：短链接、Wi-Fi 配置和较长的文本（对应不同的版本）
+pub const CONTENTS: [&str; 3] = [
+    "https://glimpse.example/s/42",
+    "WIFI:T:WPA;S:Glimpse-Office;P:correct horse battery;;",
+    "灵视测试语料 The quick brown fox jumps over the lazy dog 0123456789 \
+     Pack my box with five dozen liquor jugs.",
+];
+
+/// 生成完整的测试语料（每次生成的图像完全相同）
+pub fn corpus() -> Vec<Sample> {
+    let mut samples = Vec::new();
+    for (index, content) in CONTENTS.iter().enumerate() {
+        let seed = index as u64 + 1;
+        let mut push = |degradation: Degradation, suffix: &str, image: RgbImage| {
+            samples.push(Sample {
+                name: format!("{}-{:?}{}", index, degradation, suffix),
+                degradation,
+                content: content.to_string(),
+                image: DynamicImage::ImageRgb8(image),
+            });
+        };
+
+        let clean = render_code(content, 6);
+        push(Degradation::Clean, "", clean.clone());
+        push(Degradation::Small, "", render_code(content, 3));
+        push(Degradation::Large, "", render_code(content, 12));
+        for angle in [15.0, 30.0, 45.0] {
+            push(Degradation::Rotated, &format!("-{}", angle), rotate(&clean, angle));
+        }
+        push(Degradation::Blurred, "", imageops::blur(&clean, 1.2));
+        push(Degradation::Jpeg, "", jpeg(&render_code(content, 4), 25));
+        push(Degradation::LowContrast, "", low_contrast(&clean, 90, 170));
+        push(Degradation::Inverted, "", invert(&clean));
+        push(Degradation::Mirrored, "", imageops::flip_horizontal(&clean));
+        push(Degradation::Colored, "", render_colored(content, 6, "#1565c0", "#fff59d"));
+        push(Degradation::UnevenLight, "", uneven_light(&clean, 0.35));
+        push(Degradation::BusyBackground, "", embed_in_background(&clean, 640, 480, seed));
+    }
+    samples
+}
+
+/// 只包含指定退化类型的样本
+pub fn corpus_of(degradations: &[Degradation]) -> Vec<Sample> {
+    corpus().into_iter().filter(|s| degradations.contains(&s.degradation)).collect()
+}
+
+/// 模拟屏幕截图：杂乱背景上分布 `codes` 个二维码，返回图像和码的内容
+pub fn screen_with_codes(width: u32, height: u32, codes: usize, seed: u64) -> (DynamicImage, Vec<String>) {
+    let mut rng = Rng::new(seed);
+    let mut screen = busy_background(width, height, &mut rng);
+    let mut contents = Vec::with_capacity(codes);
+
+    // 屏幕按列均分，每列中放一个码，避免相互重叠
+    let column = width / codes.max(1) as u32;
+    for i in 0..codes {
+        let content = format!("https://glimpse.example/screen/{}/{}", seed, i);
+        let code = render_code(&content, 4);
+        let max_x = column.saturating_sub(code.width()).max(1);
+        let max_y = height.saturating_sub(code.height()).max(1);
+        let x = i as u32 * column + rng.below(max_x);
+        let y = rng.below(max_y);
+        imageops::replace(&mut screen, &code, x as i64, y as i64);
+        contents.push(content);
+    }
+
+    (DynamicImage::ImageRgb8(screen), contents)
+}
+
+/// 生成黑白二维码（含 4 个模块的静区）
+pub fn render_code(content: &str, module_size: u32) -> RgbImage {
+    render_colored(content, module_size, "#000000", "#ffffff")
+}
+
+/// 以指定前景色、背景色生成二维码
+fn render_colored(content: &str, module_size: u32, foreground: &str, background: &str) -> RgbImage {
+    let options = QrGenerateOptions {
+        module_size,
+        foreground: foreground.to_string(),
+        background: background.to_string(),
+        ..Default::default()
+    };
+    let generated = generate_qr(content, &options).expect("生成样本二维码失败");
+    image::load_from_memory(&generated.png).expect("解码样本 PNG 失败").to_rgb8()
+}
+
+/// 绕中心旋转（双线性插值），画布扩大以容纳旋转后的图像，空白处填充白色
+fn rotate(image: &RgbImage, degrees: f32) -> RgbImage {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (w, h) = (image.width() as f32, image.height() as f32);
+    let side = ((w * cos.abs() + h * sin.abs()).max(w * sin.abs() + h * cos.abs())).ceil() as u32;
+    let (cx, cy, half) = (w / 2.0, h / 2.0, side as f32 / 2.0);
+
+    RgbImage::from_fn(side, side, |x, y| {
+        // 反向映射：目标像素旋转回原图坐标
+        let (dx, dy) = (x as f32 + 0.5 - half, y as f32 + 0.5 - half);
+        let sx = dx * cos + dy * sin + cx - 0.5;
+        let sy = -dx * sin + dy * cos + cy - 0.5;
+        sample_bilinear(image, sx, sy)
+    })
+}
+
+/// 双线性采样，超出图像范围时返回白色
+fn sample_bilinear(image: &RgbImage, x: f32, y: f32) -> Rgb<u8> {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let pixel = |px: f32, py: f32| {
+        if px < 0.0 || py < 0.0 || px >= image.width() as f32 || py >= image.height() as f32 {
+            [255.0; 3]
+        } else {
+            image.get_pixel(px as u32, py as u32).0.map(f32::from)
+        }
+    };
+    let (a, b, c, d) = (pixel(x0, y0), pixel(x0 + 1.0, y0), pixel(x0, y0 + 1.0), pixel(x0 + 1.0, y0 + 1.0));
+    Rgb(std::array::from_fn(|i| {
+        let top = a[i] * (1.0 - fx) + b[i] * fx;
+        let bottom = c[i] * (1.0 - fx) + d[i] * fx;
+        (top * (1.0 - fy) + bottom * fy).round() as u8
+    }))
+}
+
+/// JPEG 编码后再解码，引入压缩块效应
+fn jpeg(image: &RgbImage, quality: u8) -> RgbImage {
+    let mut bytes = Vec::new();
+    JpegEncoder::new_with_quality(&mut bytes, quality)
+        .encode_image(image)
+        .expect("JPEG 编码失败");
+    image::load_from_memory(&bytes).expect("JPEG 解码失败").to_rgb8()
+}
+
+/// 将亮度压缩到 [low, high] 区间
+fn low_contrast(image: &RgbImage, low: u8, high: u8) -> RgbImage {
+    let range = f32::from(high - low) / 255.0;
+    let mut output = image.clone();
+    for pixel in output.pixels_mut() {
+        pixel.0 = pixel.0.map(|v| low + (f32::from(v) * range).round() as u8);
+    }
+    output
+}
+
+/// 反色
+fn invert(image: &RgbImage) -> RgbImage {
+    let mut output = image.clone();
+    imageops::invert(&mut output);
+    output
+}
+
+/// 从左到右亮度逐渐降低到 `darkest` 倍
+fn uneven_light(image: &RgbImage, darkest: f32) -> RgbImage {
+    let width = image.width().max(2) as f32 - 1.0;
+    let mut output = image.clone();
+    for (x, _, pixel) in output.enumerate_pixels_mut() {
+        let factor = 1.0 - (1.0 - darkest) * (x as f32 / width);
+        pixel.0 = pixel.0.map(|v| (f32::from(v) * factor).round() as u8);
+    }
+    output
+}
+
+/// 将码放在杂乱背景中央
+fn embed_in_background(code: &RgbImage, width: u32, height: u32, seed: u64) -> RgbImage {
+    let mut rng = Rng::new(seed);
+    let mut background = busy_background(width.max(code.width()), height.max(code.height()), &mut rng);
+    let x = (background.width() - code.width()) / 2;
+    let y = (background.height() - code.height()) / 2;
+    imageops::replace(&mut background, code, x as i64, y as i64);
+    background
+}
+
+/// 杂乱背景：渐变底色上叠加随机色块和类似文字行的细条纹
+fn busy_background(width: u32, height: u32, rng: &mut Rng) -> RgbImage {
+    let mut image = RgbImage::from_fn(width, height, |x, y| {
+        Rgb([
+            (x * 255 / width.max(1)) as u8,
+            (y * 255 / height.max(1)) as u8,
+            ((x + y) * 127 / (width + height).max(1)) as u8 + 64,
+        ])
+    });
+
+    let area = u64::from(width) * u64::from(height);
+    let blocks = (area / 20_000).max(8);
+    for _ in 0..blocks {
+        let color = Rgb([rng.below(256) as u8, rng.below(256) as u8, rng.below(256) as u8]);
+        let (w, h) = (rng.below(width / 6 + 1) + 4, rng.below(height / 6 + 1) + 4);
+        fill_rect(&mut image, rng.below(width), rng.below(height), w, h, color);
+    }
+
+    // 文字行：高 2 像素、长短不一的深色横条
+    let lines = (area / 8_000).max(16);
+    for _ in 0..lines {
+        let shade = rng.below(80) as u8;
+        fill_rect(&mut image, rng.below(width), rng.below(height), rng.below(160) + 20, 2, Rgb([shade; 3]));
+    }
+
+    image
+}
+
+/// 填充矩形（超出图像的部分忽略）
+fn fill_rect(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// 确定性伪随机数生成器（SplitMix64），保证每次生成的语料相同
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, bound) 内的整数（bound 为 0 时返回 0）
+    pub fn below(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            0
+        } else {
+            (self.next_u64() % u64::from(bound)) as u32
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! 识别率测试：在生成的语料上统计默认流水线和每种预处理方法的解码召回率
+
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{corpus, corpus_of, screen_with_codes, Degradation, Sample};
+use glimpse_core::debug_log::{self, LogTarget};
+use glimpse_core::{scan_qr_codes, PreprocessMethod, ScanPipeline, ScanStrategy};
+
+/// 所有预处理方法
+const METHODS: [PreprocessMethod; 13] = [
+    PreprocessMethod::None,
+    PreprocessMethod::ContrastBoost,
+    PreprocessMethod::Brightness,
+    PreprocessMethod::Sharpen,
+    PreprocessMethod::Invert,
+    PreprocessMethod::Otsu,
+    PreprocessMethod::Sauvola,
+    PreprocessMethod::MorphClose,
+    PreprocessMethod::Median,
+    PreprocessMethod::RedChannel,
+    PreprocessMethod::GreenChannel,
+    PreprocessMethod::BlueChannel,
+    PreprocessMethod::Mirror,
+];
+
+/// 只包含一个策略的流水线
+fn single_strategy(method: PreprocessMethod) -> ScanPipeline {
+    ScanPipeline { strategies: vec![ScanStrategy::new(1.0, method)], keep_duplicates: false }
+}
+
+/// 样本是否被正确解码
+fn decoded(sample: &Sample, pipeline: &ScanPipeline) -> bool {
+    let report = scan_qr_codes(&sample.image, pipeline).expect("扫描失败");
+    report.results.iter().any(|r| r.content == sample.content)
+}
+
+/// 按退化类型统计召回率，并打印结果便于对比
+fn recall_by_degradation(samples: &[Sample], pipeline: &ScanPipeline, label: &str) -> BTreeMap<Degradation, f32> {
+    let mut counts: BTreeMap<Degradation, (usize, usize)> = BTreeMap::new();
+    for sample in samples {
+        let hit = decoded(sample, pipeline);
+        if !hit {
+            println!("[{}] 未识别: {}", label, sample.name);
+        }
+        let entry = counts.entry(sample.degradation).or_default();
+        entry.0 += usize::from(hit);
+        entry.1 += 1;
+    }
+
+    counts
+        .into_iter()
+        .map(|(degradation, (hits, total))| {
+            let recall = hits as f32 / total as f32;
+            println!("[{}] {:?}: {}/{} ({:.0}%)", label, degradation, hits, total, recall * 100.0);
+            (degradation, recall)
+        })
+        .collect()
+}
+
+#[test]
+fn corpus_is_deterministic() {
+    let (first, second) = (corpus(), corpus());
+    assert_eq!(first.len(), second.len());
+    for (a, b) in first.iter().zip(&second) {
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.image.as_bytes(), b.image.as_bytes(), "{} 两次生成的图像不同", a.name);
+    }
+}
+
+#[test]
+fn default_pipeline_recall() {
+    debug_log::set_target(LogTarget::Off);
+    let samples = corpus();
+    let recall = recall_by_degradation(&samples, &ScanPipeline::default(), "默认流水线");
+
+    // 默认流水线应覆盖语料中的所有退化类型
+    for (degradation, value) in &recall {
+        assert!(*value >= 1.0, "默认流水线对 {:?} 的召回率下降到 {:.0}%", degradation, value * 100.0);
+    }
+}
+
+/// 每种预处理方法至少应能解码的退化类型（新增方法时需在此补充，并加入 [`METHODS`]）
+///
+/// 反色和镜像会破坏正常的码，不要求识别干净样本
+fn targets(method: PreprocessMethod) -> &'static [Degradation] {
+    match method {
+        PreprocessMethod::None => &[Degradation::Clean, Degradation::Large, Degradation::Colored],
+        PreprocessMethod::ContrastBoost => &[Degradation::Clean, Degradation::LowContrast],
+        PreprocessMethod::Brightness => &[Degradation::Clean],
+        PreprocessMethod::Sharpen => &[Degradation::Clean, Degradation::Blurred],
+        PreprocessMethod::Invert => &[Degradation::Inverted],
+        PreprocessMethod::Otsu => &[Degradation::Clean, Degradation::LowContrast],
+        PreprocessMethod::Sauvola => &[Degradation::Clean, Degradation::UnevenLight],
+        PreprocessMethod::MorphClose => &[Degradation::Clean],
+        PreprocessMethod::Median => &[Degradation::Clean, Degradation::Jpeg],
+        PreprocessMethod::RedChannel | PreprocessMethod::GreenChannel => &[Degradation::Clean, Degradation::Colored],
+        PreprocessMethod::BlueChannel => &[Degradation::Clean],
+        PreprocessMethod::Mirror => &[Degradation::Mirrored],
+    }
+}
+
+#[test]
+fn preprocess_method_recall() {
+    debug_log::set_target(LogTarget::Off);
+
+    for method in METHODS {
+        let degradations = targets(method);
+        let samples = corpus_of(degradations);
+        let recall = recall_by_degradation(&samples, &single_strategy(method), &format!("{:?}", method));
+        for degradation in degradations {
+            let value = recall[degradation];
+            assert!(value >= 1.0, "{:?} 对 {:?} 的召回率下降到 {:.0}%", method, degradation, value * 100.0);
+        }
+    }
+}
+
+#[test]
+fn full_screen_recall() {
+    debug_log::set_target(LogTarget::Off);
+    let (screen, contents) = screen_with_codes(1920, 1080, 4, 7);
+    let report = scan_qr_codes(&screen, &ScanPipeline::default()).expect("扫描失败");
+    for content in &contents {
+        assert!(report.results.iter().any(|r| &r.content == content), "整屏截图中未识别: {}", content);
+    }
+}
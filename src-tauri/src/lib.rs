@@ -1,22 +1,43 @@
-mod qr_scanner;
-mod screenshot;
 mod tray;
 mod selector_window;
-mod ocr;
 
-use qr_scanner::{QrCodeResult, scan_qr_codes, scan_multiple_images};
-use screenshot::{capture_full_screen, capture_region};
-use ocr::OcrResult;
+use glimpse_core::debug_println;
+use glimpse_core::ocr::{self, models, FrameText, OcrResult};
+use glimpse_core::screenshot::{self, capture_full_screen, capture_region, ScreenCapture};
+use glimpse_core::image::DynamicImage;
+use glimpse_core::image_file;
+use glimpse_core::{scan_frames, scan_multiple_images, scan_qr_codes, CompiledPolicy, ExportFormat, GeneratedQr, History, HistoryEntry, HistoryQuery, HistorySource, ModelPack, NewEntry, OpenAction, OpenDecision, OpenPolicy, QrCodeResult, QrGenerateOptions, ScanDiagnostics, ScanPipeline, ScanReport, ScreenOrigin, Table, TableFormat, WatchOptions, WatchTarget, Watcher};
 use tauri::{AppHandle, Emitter, Manager};
 use selector_window::{create_selector_window, create_selector_window_with_mode, close_selector_window, preload_selector_window};
-use std::sync::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
-use image::DynamicImage;
+use serde::Serialize;
+use std::sync::{mpsc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 // 全局存储：预先截取的全屏图像和 base64 数据
-static CAPTURED_SCREENSHOT: Mutex<Option<DynamicImage>> = Mutex::new(None);
+static CAPTURED_SCREENSHOT: Mutex<Option<ScreenCapture>> = Mutex::new(None);
 static CAPTURED_SCREENSHOT_BASE64: Mutex<Option<String>> = Mutex::new(None);
 
+// 识别流水线配置（None 表示使用默认配置）
+static SCAN_PIPELINE: Mutex<Option<ScanPipeline>> = Mutex::new(None);
+
+// 链接打开策略（None 表示使用默认策略）
+static OPEN_POLICY: Mutex<Option<CompiledPolicy>> = Mutex::new(None);
+
+// 当前监视会话（None 表示未在监视）
+static WATCH_SESSION: Mutex<Option<WatchSession>> = Mutex::new(None);
+
+// 区域选择完成后开始监视时使用的截取间隔（毫秒）
+static PENDING_WATCH_INTERVAL: AtomicU64 = AtomicU64::new(1000);
+
+// 识别历史记录（None 表示尚未打开或打开失败）
+static HISTORY: Mutex<Option<History>> = Mutex::new(None);
+
+// 是否保存识别历史
+static HISTORY_ENABLED: AtomicBool = AtomicBool::new(true);
+
+// 最近一次扫描的诊断信息
+static LAST_SCAN_DIAGNOSTICS: Mutex<Vec<ScanDiagnostics>> = Mutex::new(Vec::new());
+
 // 退出行为设置：true = 最小化到托盘，false = 直接退出
 static MINIMIZE_TO_TRAY: AtomicBool = AtomicBool::new(false);
 
@@ -24,21 +45,26 @@ static MINIMIZE_TO_TRAY: AtomicBool = AtomicBool::new(false);
 static CURRENT_FULLSCREEN_SHORTCUT: Mutex<String> = Mutex::new(String::new());
 static CURRENT_REGION_SHORTCUT: Mutex<String> = Mutex::new(String::new());
 static CURRENT_OCR_SHORTCUT: Mutex<String> = Mutex::new(String::new());
+static CURRENT_CLIPBOARD_SHORTCUT: Mutex<String> = Mutex::new(String::new());
 
 /// 全屏扫描命令
 #[tauri::command]
 async fn scan_full_screen() -> Result<Vec<QrCodeResult>, String> {
-    println!("[DEBUG] 开始全屏扫描");
+    debug_println!("[DEBUG] 开始全屏扫描");
 
     // 捕获所有屏幕
-    println!("[DEBUG] 正在捕获屏幕...");
+    debug_println!("[DEBUG] 正在捕获屏幕...");
     let images = capture_full_screen()?;
-    println!("[DEBUG] 成功捕获 {} 个屏幕", images.len());
+    debug_println!("[DEBUG] 成功捕获 {} 个屏幕", images.len());
 
     // 扫描所有图像中的二维码
-    println!("[DEBUG] 正在扫描二维码...");
-    let results = scan_multiple_images(&images)?;
-    println!("[DEBUG] 扫描完成，找到 {} 个二维码", results.len());
+    debug_println!("[DEBUG] 正在扫描二维码...");
+    let results = record_diagnostics(scan_multiple_images(&images, &current_pipeline()?)?);
+    debug_println!("[DEBUG] 扫描完成，找到 {} 个二维码", results.len());
+
+    record_code_history(HistorySource::FullScreen, &results, |result| {
+        images.get(result.screen.map_or(0, |screen| screen.index)).map(|capture| &capture.image)
+    });
 
     Ok(results)
 }
@@ -46,8 +72,18 @@ async fn scan_full_screen() -> Result<Vec<QrCodeResult>, String> {
 /// 开始区域选择 - 先截图再显示选择窗口
 #[tauri::command]
 async fn start_region_selection(app: AppHandle) -> Result<(), String> {
-    println!("[DEBUG] ======== 开始区域选择 ========");
+    debug_println!("[DEBUG] ======== 开始区域选择 ========");
+
+    capture_for_selection(&app).await?;
+    create_selector_window(&app)?;
 
+    debug_println!("[DEBUG] 选择器窗口已显示");
+
+    Ok(())
+}
+
+/// 隐藏主窗口后截取全屏，保存截图及其 base64 数据供选择器窗口使用（各区域选择模式共用）
+async fn capture_for_selection(app: &AppHandle) -> Result<(), String> {
     // 1. 隐藏主窗口（确保截图不包含主窗口）
     // 使用 minimize + hide 组合，比单独 hide 更快完成
     if let Some(window) = app.get_webview_window("main") {
@@ -59,19 +95,17 @@ async fn start_region_selection(app: AppHandle) -> Result<(), String> {
     tokio::time::sleep(std::time::Duration::from_millis(30)).await;
 
     // 2. 截取全屏
-    println!("[DEBUG] 正在截取全屏...");
+    debug_println!("[DEBUG] 正在截取全屏...");
     let images = capture_full_screen()?;
     let screenshot = images.into_iter().next().ok_or("未找到可用屏幕")?;
 
-    println!("[DEBUG] 截图尺寸: {}x{}", screenshot.width(), screenshot.height());
+    debug_println!("[DEBUG] 截图尺寸: {}x{}", screenshot.image.width(), screenshot.image.height());
 
     // 3. 将截图转换为 base64
-    use screenshot::image_to_png_bytes;
-    let png_bytes = image_to_png_bytes(&screenshot)?;
-    let base64 = base64_encode(&png_bytes);
-    let data_url = format!("data:image/png;base64,{}", base64);
+    let png_bytes = screenshot::image_to_png_bytes(&screenshot.image)?;
+    let data_url = format!("data:image/png;base64,{}", base64_encode(&png_bytes));
 
-    println!("[DEBUG] 截图已转换为 base64，长度: {} 字节", data_url.len());
+    debug_println!("[DEBUG] 截图已转换为 base64，长度: {} 字节", data_url.len());
 
     // 4. 保存截图和 base64 到全局变量
     {
@@ -82,26 +116,33 @@ async fn start_region_selection(app: AppHandle) -> Result<(), String> {
         let mut guard = CAPTURED_SCREENSHOT_BASE64.lock().map_err(|e| format!("锁定失败: {}", e))?;
         *guard = Some(data_url);
     }
-
-    // 5. 显示选择器窗口
-    create_selector_window(&app)?;
-
-    println!("[DEBUG] 选择器窗口已显示");
-
     Ok(())
 }
 
+/// 取出预先截取的截图并清理 base64 数据
+fn take_captured_screenshot() -> Result<ScreenCapture, String> {
+    let screenshot = {
+        let mut guard = CAPTURED_SCREENSHOT.lock().map_err(|e| format!("锁定失败: {}", e))?;
+        guard.take().ok_or("未找到预先截取的截图")?
+    };
+    {
+        let mut guard = CAPTURED_SCREENSHOT_BASE64.lock().map_err(|e| format!("锁定失败: {}", e))?;
+        *guard = None;
+    }
+    Ok(screenshot)
+}
+
 /// 获取预先截取的截图数据（供选择器窗口调用）
 #[tauri::command]
 async fn get_screenshot_data() -> Result<String, String> {
-    println!("[DEBUG] 选择器窗口请求截图数据");
+    debug_println!("[DEBUG] 选择器窗口请求截图数据");
 
     let data_url = {
         let guard = CAPTURED_SCREENSHOT_BASE64.lock().map_err(|e| format!("锁定失败: {}", e))?;
         guard.clone().ok_or("未找到截图数据")?
     };
 
-    println!("[DEBUG] 返回截图数据，长度: {} 字节", data_url.len());
+    debug_println!("[DEBUG] 返回截图数据，长度: {} 字节", data_url.len());
     Ok(data_url)
 }
 
@@ -114,20 +155,11 @@ async fn complete_region_selection(
     width: u32,
     height: u32,
 ) -> Result<(), String> {
-    println!("[DEBUG] ======== 区域选择完成 ========");
-    println!("[DEBUG] 前端传来的坐标: x={}, y={}, width={}, height={}", x, y, width, height);
+    debug_println!("[DEBUG] ======== 区域选择完成 ========");
+    debug_println!("[DEBUG] 前端传来的坐标: x={}, y={}, width={}, height={}", x, y, width, height);
 
     // 从全局变量获取预先截取的图像（在关闭窗口前获取）
-    let screenshot = {
-        let mut guard = CAPTURED_SCREENSHOT.lock().map_err(|e| format!("锁定失败: {}", e))?;
-        guard.take().ok_or("未找到预先截取的截图")?
-    };
-
-    // 清理 base64 数据
-    {
-        let mut guard = CAPTURED_SCREENSHOT_BASE64.lock().map_err(|e| format!("锁定失败: {}", e))?;
-        *guard = None;
-    }
+    let screenshot = take_captured_screenshot()?;
 
     // 关闭选择窗口
     close_selector_window(&app)?;
@@ -135,11 +167,11 @@ async fn complete_region_selection(
     // 等待窗口完全关闭
     std::thread::sleep(std::time::Duration::from_millis(50));
 
-    println!("[DEBUG] 使用预先截取的截图，尺寸: {}x{}", screenshot.width(), screenshot.height());
+    debug_println!("[DEBUG] 使用预先截取的截图，尺寸: {}x{}", screenshot.image.width(), screenshot.image.height());
 
     // 直接裁剪预先截取的图像（无需重新截图）
-    let img_width = screenshot.width();
-    let img_height = screenshot.height();
+    let img_width = screenshot.image.width();
+    let img_height = screenshot.image.height();
 
     // 确保裁剪区域不超出图像边界
     let safe_x = x.min(img_width.saturating_sub(1));
@@ -147,20 +179,31 @@ async fn complete_region_selection(
     let safe_width = width.min(img_width.saturating_sub(safe_x));
     let safe_height = height.min(img_height.saturating_sub(safe_y));
 
-    println!("[DEBUG] 安全裁剪区域: x={}, y={}, w={}, h={}", safe_x, safe_y, safe_width, safe_height);
+    debug_println!("[DEBUG] 安全裁剪区域: x={}, y={}, w={}, h={}", safe_x, safe_y, safe_width, safe_height);
 
     // 裁剪图像
-    let cropped = screenshot.crop_imm(safe_x, safe_y, safe_width, safe_height);
+    let cropped = screenshot.image.crop_imm(safe_x, safe_y, safe_width, safe_height);
 
-    println!("[DEBUG] 裁剪完成，结果尺寸: {}x{}", cropped.width(), cropped.height());
+    debug_println!("[DEBUG] 裁剪完成，结果尺寸: {}x{}", cropped.width(), cropped.height());
 
     // 扫描二维码
-    let results = scan_qr_codes(&cropped).unwrap_or_else(|e| {
-        println!("[DEBUG] 二维码识别失败: {}，返回空结果", e);
-        vec![]
-    });
+    let mut results = current_pipeline()
+        .and_then(|pipeline| scan_qr_codes(&cropped, &pipeline).map_err(String::from))
+        .map(record_diagnostics)
+        .unwrap_or_else(|e| {
+            debug_println!("[DEBUG] 二维码识别失败: {}，返回空结果", e);
+            vec![]
+        });
+
+    // 将坐标从裁剪区域换算回整屏坐标
+    let origin = ScreenOrigin::of(&screenshot);
+    for result in &mut results {
+        result.translate(safe_x as f32, safe_y as f32);
+        result.screen = Some(origin);
+    }
 
-    println!("[DEBUG] 扫描完成，找到 {} 个二维码", results.len());
+    debug_println!("[DEBUG] 扫描完成，找到 {} 个二维码", results.len());
+    record_code_history(HistorySource::Region, &results, |_| Some(&screenshot.image));
 
     // 显示主窗口
     if let Some(window) = app.get_webview_window("main") {
@@ -175,7 +218,7 @@ async fn complete_region_selection(
     // 发送结果事件
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.emit("region_scan_complete", &results);
-        println!("[DEBUG] 已向主窗口发送 region_scan_complete 事件");
+        debug_println!("[DEBUG] 已向主窗口发送 region_scan_complete 事件");
     }
 
     Ok(())
@@ -207,10 +250,10 @@ async fn start_region_scan() -> Result<String, String> {
     let images = capture_full_screen()?;
 
     // 对于简化实现,我们只返回第一个屏幕的截图信息
-    if let Some(first_image) = images.first() {
+    if let Some(first_screen) = images.first() {
         // 将图像转换为 base64 供前端显示
         use screenshot::image_to_png_bytes;
-        let png_bytes = image_to_png_bytes(first_image)?;
+        let png_bytes = image_to_png_bytes(&first_screen.image)?;
         let base64 = base64_encode(&png_bytes);
 
         Ok(format!("data:image/png;base64,{}", base64))
@@ -230,17 +273,41 @@ fn base64_encode(data: &[u8]) -> String {
     String::from_utf8(buf).unwrap()
 }
 
+/// 获取当前识别流水线配置
+fn current_pipeline() -> Result<ScanPipeline, String> {
+    let guard = SCAN_PIPELINE.lock().map_err(|e| format!("锁定失败: {}", e))?;
+    Ok(guard.clone().unwrap_or_default())
+}
+
+/// 获取当前链接打开策略（未设置时使用默认策略）
+fn current_open_policy() -> Result<CompiledPolicy, String> {
+    let guard = OPEN_POLICY.lock().map_err(|e| format!("锁定失败: {}", e))?;
+    match guard.as_ref() {
+        Some(policy) => Ok(policy.clone()),
+        None => Ok(OpenPolicy::default().compile()?),
+    }
+}
+
+/// 保存扫描诊断信息并返回识别结果
+fn record_diagnostics(report: ScanReport) -> Vec<QrCodeResult> {
+    if let Ok(mut guard) = LAST_SCAN_DIAGNOSTICS.lock() {
+        *guard = report.diagnostics;
+    }
+    report.results
+}
+
 /// 区域扫描命令(基于坐标)
 #[tauri::command]
 async fn scan_region_coords(x: u32, y: u32, width: u32, height: u32) -> Result<Vec<QrCodeResult>, String> {
-    println!("[DEBUG] 区域扫描 - 坐标: x={}, y={}, width={}, height={}", x, y, width, height);
+    debug_println!("[DEBUG] 区域扫描 - 坐标: x={}, y={}, width={}, height={}", x, y, width, height);
 
     // 捕获指定区域
     let image = capture_region(x, y, width, height)?;
-    println!("[DEBUG] 成功捕获区域，图像尺寸: {}x{}", image.width(), image.height());
+    debug_println!("[DEBUG] 成功捕获区域，图像尺寸: {}x{}", image.width(), image.height());
 
     // 扫描二维码
-    let results = scan_qr_codes(&image)?;
+    let results = record_diagnostics(scan_qr_codes(&image, &current_pipeline()?)?);
+    record_code_history(HistorySource::Region, &results, |_| Some(&image));
 
     Ok(results)
 }
@@ -289,7 +356,7 @@ async fn hide_window(app: AppHandle) -> Result<(), String> {
 async fn set_close_behavior(behavior: String) -> Result<(), String> {
     let minimize_to_tray = behavior == "tray";
     MINIMIZE_TO_TRAY.store(minimize_to_tray, Ordering::SeqCst);
-    println!("[DEBUG] 退出行为已设置为: {}", if minimize_to_tray { "最小化到托盘" } else { "直接退出" });
+    debug_println!("[DEBUG] 退出行为已设置为: {}", if minimize_to_tray { "最小化到托盘" } else { "直接退出" });
     Ok(())
 }
 
@@ -300,6 +367,331 @@ async fn get_close_behavior() -> Result<String, String> {
     Ok(if minimize_to_tray { "tray".to_string() } else { "exit".to_string() })
 }
 
+/// 设置识别流水线（策略顺序、启用状态、缩放比例）
+#[tauri::command]
+async fn set_scan_pipeline(pipeline: ScanPipeline) -> Result<(), String> {
+    pipeline.validate()?;
+    debug_println!("[DEBUG] 识别流水线已更新，启用 {} 个策略", pipeline.enabled().count());
+    let mut guard = SCAN_PIPELINE.lock().map_err(|e| format!("锁定失败: {}", e))?;
+    *guard = Some(pipeline);
+    Ok(())
+}
+
+/// 获取当前识别流水线
+#[tauri::command]
+async fn get_scan_pipeline() -> Result<ScanPipeline, String> {
+    current_pipeline()
+}
+
+/// 获取默认识别流水线（用于恢复默认设置）
+#[tauri::command]
+async fn get_default_scan_pipeline() -> Result<ScanPipeline, String> {
+    Ok(ScanPipeline::default())
+}
+
+/// 获取最近一次扫描的诊断信息
+#[tauri::command]
+async fn get_scan_diagnostics() -> Result<Vec<ScanDiagnostics>, String> {
+    let guard = LAST_SCAN_DIAGNOSTICS.lock().map_err(|e| format!("锁定失败: {}", e))?;
+    Ok(guard.clone())
+}
+
+/// 生成二维码，返回 PNG 数据和 SVG 文本
+#[tauri::command]
+async fn generate_qr(text: String, options: Option<QrGenerateOptions>) -> Result<GeneratedQr, String> {
+    let options = options.unwrap_or_default();
+    debug_println!("[DEBUG] 生成二维码 - 内容长度: {}, 纠错等级: {:?}", text.len(), options.ecc_level);
+    Ok(glimpse_core::generate_qr(&text, &options)?)
+}
+
+/// 设置链接打开策略（域名、协议、正则规则）
+#[tauri::command]
+async fn set_open_policy(policy: OpenPolicy) -> Result<(), String> {
+    let compiled = policy.compile()?;
+    debug_println!("[DEBUG] 链接打开策略已更新，共 {} 条规则，默认动作: {:?}", policy.rules.len(), policy.default_action);
+    let mut guard = OPEN_POLICY.lock().map_err(|e| format!("锁定失败: {}", e))?;
+    *guard = Some(compiled);
+    Ok(())
+}
+
+/// 获取当前链接打开策略
+#[tauri::command]
+async fn get_open_policy() -> Result<OpenPolicy, String> {
+    Ok(current_open_policy()?.policy().clone())
+}
+
+/// 获取默认链接打开策略（用于恢复默认设置）
+#[tauri::command]
+async fn get_default_open_policy() -> Result<OpenPolicy, String> {
+    Ok(OpenPolicy::default())
+}
+
+/// 判定链接的打开动作（前端据此决定是否自动打开、显示确认或禁用打开按钮）
+#[tauri::command]
+async fn evaluate_open_policy(url: String) -> Result<OpenDecision, String> {
+    Ok(current_open_policy()?.evaluate(&url))
+}
+
+/// 按策略打开链接
+///
+/// 禁止打开的链接返回错误；需确认的链接在 `confirmed` 为 false 时不打开，
+/// 仅返回判定结果，由前端确认后再次调用
+#[tauri::command]
+async fn open_url(app: AppHandle, url: String, confirmed: bool) -> Result<OpenDecision, String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let decision = current_open_policy()?.evaluate(&url);
+    match decision.action {
+        OpenAction::Block => return Err(format!("链接已被禁止打开: {}", decision.reason)),
+        OpenAction::Confirm if !confirmed => return Ok(decision),
+        OpenAction::Open | OpenAction::Confirm => {}
+    }
+
+    debug_println!("[DEBUG] 打开链接: {}", url);
+    app.opener()
+        .open_url(url.trim(), None::<&str>)
+        .map_err(|e| format!("打开链接失败: {}", e))?;
+    Ok(decision)
+}
+
+/// 监视会话：配置及停止信号（丢弃发送端即通知监视线程退出）
+struct WatchSession {
+    options: WatchOptions,
+    _stop: mpsc::Sender<()>,
+}
+
+/// 监视状态（发送给前端）
+#[derive(Debug, Clone, Serialize)]
+struct WatchStatus {
+    running: bool,
+    options: Option<WatchOptions>,
+}
+
+/// 开始监视屏幕或区域，新出现的码通过 `qr_detected` 事件发送
+#[tauri::command]
+async fn start_watch(app: AppHandle, options: WatchOptions) -> Result<(), String> {
+    start_watching(&app, options)
+}
+
+/// 停止监视
+#[tauri::command]
+async fn stop_watch(app: AppHandle) -> Result<(), String> {
+    stop_watching(&app)
+}
+
+/// 获取当前监视状态
+#[tauri::command]
+async fn get_watch_status() -> Result<WatchStatus, String> {
+    current_watch_status()
+}
+
+/// 开始选择监视区域 - 先截图再显示选择窗口（监视模式）
+#[tauri::command]
+async fn start_watch_region_selection(app: AppHandle, interval_ms: u64) -> Result<(), String> {
+    debug_println!("[DEBUG] ======== 开始选择监视区域 ========");
+    WatchOptions { target: WatchTarget::AllScreens, interval_ms }.validate()?;
+    PENDING_WATCH_INTERVAL.store(interval_ms, Ordering::SeqCst);
+
+    capture_for_selection(&app).await?;
+    create_selector_window_with_mode(&app, "watch")?;
+    Ok(())
+}
+
+/// 完成监视区域选择并开始监视（坐标为选择时截图中的物理像素）
+#[tauri::command]
+async fn complete_watch_region_selection(
+    app: AppHandle,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<(), String> {
+    debug_println!("[DEBUG] 监视区域: x={}, y={}, width={}, height={}", x, y, width, height);
+
+    // 截图只用于选择区域和确定所在屏幕，监视时会重新截取
+    let screen = take_captured_screenshot()?.index;
+
+    close_selector_window(&app)?;
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+
+    let target = WatchTarget::Region { screen, x, y, width, height };
+    start_watching(&app, WatchOptions { target, interval_ms: PENDING_WATCH_INTERVAL.load(Ordering::SeqCst) })
+}
+
+/// 启动监视线程（已在监视时先停止旧的监视）
+fn start_watching(app: &AppHandle, options: WatchOptions) -> Result<(), String> {
+    options.validate()?;
+
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+    let interval = std::time::Duration::from_millis(options.interval_ms);
+    let mut watcher = Watcher::new(options.target.clone());
+    let app_handle = app.clone();
+
+    std::thread::spawn(move || {
+        loop {
+            let scanned = capture_full_screen()
+                .map_err(String::from)
+                .and_then(|screens| watcher.tick(screens, &current_pipeline()?).map_err(String::from));
+            match scanned {
+                Ok(Some(codes)) if !codes.is_empty() => {
+                    debug_println!("[DEBUG] 监视: 发现 {} 个新的码", codes.len());
+                    record_code_history(HistorySource::Watch, &codes, |_| None);
+                    let _ = app_handle.emit("qr_detected", &codes);
+                }
+                Ok(_) => {}
+                Err(e) => debug_println!("[DEBUG] 监视识别失败: {}", e),
+            }
+
+            // 等待下一次截取；收到停止信号或发送端被丢弃时退出
+            if !matches!(stop_rx.recv_timeout(interval), Err(mpsc::RecvTimeoutError::Timeout)) {
+                break;
+            }
+        }
+        debug_println!("[DEBUG] 监视线程已退出");
+    });
+
+    debug_println!("[DEBUG] 开始监视: {:?}，间隔 {}ms", options.target, options.interval_ms);
+    {
+        let mut guard = WATCH_SESSION.lock().map_err(|e| format!("锁定失败: {}", e))?;
+        // 替换旧会话时丢弃其发送端，旧的监视线程随之退出
+        *guard = Some(WatchSession { options, _stop: stop_tx });
+    }
+    notify_watch_state(app)
+}
+
+/// 停止监视
+fn stop_watching(app: &AppHandle) -> Result<(), String> {
+    let stopped = {
+        let mut guard = WATCH_SESSION.lock().map_err(|e| format!("锁定失败: {}", e))?;
+        guard.take().is_some()
+    };
+    if stopped {
+        debug_println!("[DEBUG] 停止监视");
+        notify_watch_state(app)?;
+    }
+    Ok(())
+}
+
+/// 获取当前监视状态
+fn current_watch_status() -> Result<WatchStatus, String> {
+    let guard = WATCH_SESSION.lock().map_err(|e| format!("锁定失败: {}", e))?;
+    Ok(WatchStatus {
+        running: guard.is_some(),
+        options: guard.as_ref().map(|session| session.options.clone()),
+    })
+}
+
+/// 通知前端和托盘监视状态已变化
+fn notify_watch_state(app: &AppHandle) -> Result<(), String> {
+    let status = current_watch_status()?;
+    tray::set_watch_indicator(app, status.running);
+    app.emit("watch_state_changed", &status)
+        .map_err(|e| format!("发送事件失败: {}", e))
+}
+
+/// 将二维码识别结果写入历史记录，`image_of` 返回结果所在的图像（用于截取缩略图）
+fn record_code_history<'a>(
+    source: HistorySource,
+    results: &[QrCodeResult],
+    image_of: impl Fn(&QrCodeResult) -> Option<&'a DynamicImage>,
+) {
+    record_history(results.iter().map(|result| NewEntry::from_code(source, result, image_of(result))));
+}
+
+/// 写入历史记录（未开启或历史记录不可用时跳过，写入失败只记录日志）
+fn record_history(entries: impl IntoIterator<Item = NewEntry>) {
+    if !HISTORY_ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+    let Ok(mut guard) = HISTORY.lock() else {
+        return;
+    };
+    let Some(history) = guard.as_mut() else {
+        return;
+    };
+    for entry in entries {
+        if let Err(e) = history.add(entry) {
+            debug_println!("[WARN] 写入历史记录失败: {}", e);
+        }
+    }
+}
+
+/// 在已打开的历史记录上执行操作
+fn with_history<T>(f: impl FnOnce(&mut History) -> glimpse_core::Result<T>) -> Result<T, String> {
+    let mut guard = HISTORY.lock().map_err(|e| format!("锁定失败: {}", e))?;
+    let history = guard.as_mut().ok_or("历史记录不可用")?;
+    Ok(f(history)?)
+}
+
+/// 查询历史记录
+#[tauri::command]
+async fn query_history(query: HistoryQuery) -> Result<Vec<HistoryEntry>, String> {
+    with_history(|history| Ok(history.query(&query)))
+}
+
+/// 置顶或取消置顶历史记录
+#[tauri::command]
+async fn set_history_pinned(id: u64, pinned: bool) -> Result<(), String> {
+    with_history(|history| history.set_pinned(id, pinned))
+}
+
+/// 删除一条历史记录
+#[tauri::command]
+async fn delete_history_entry(id: u64) -> Result<(), String> {
+    with_history(|history| history.delete(id))
+}
+
+/// 清空历史记录，返回删除的条数
+#[tauri::command]
+async fn clear_history(keep_pinned: bool) -> Result<usize, String> {
+    debug_println!("[DEBUG] 清空历史记录，保留置顶: {}", keep_pinned);
+    with_history(|history| history.clear(keep_pinned))
+}
+
+/// 弹出保存对话框，将符合条件的历史记录导出到用户选择的文件，返回导出的条数（取消时返回 None）
+///
+/// 路径只来自后端弹出的对话框，不接受前端传入，避免网页脚本写入任意文件
+#[tauri::command]
+async fn export_history(app: AppHandle, query: HistoryQuery, format: ExportFormat) -> Result<Option<usize>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let extension = match format {
+        ExportFormat::Json => "json",
+        ExportFormat::Csv => "csv",
+        ExportFormat::Markdown => "md",
+    };
+    let Some(file) = app
+        .dialog()
+        .file()
+        .set_file_name(format!("glimpse-history.{}", extension))
+        .add_filter(format!("{:?}", format), &[extension])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = file.into_path().map_err(|e| format!("无效的导出路径: {}", e))?;
+
+    let (count, content) = with_history(|history| {
+        let entries = history.query(&query);
+        Ok((entries.len(), glimpse_core::history::export(&entries, format)?))
+    })?;
+    std::fs::write(&path, content).map_err(|e| format!("写入导出文件失败: {}", e))?;
+    debug_println!("[DEBUG] 已导出 {} 条历史记录到 {}", count, path.display());
+    Ok(Some(count))
+}
+
+/// 设置是否保存识别历史
+#[tauri::command]
+async fn set_history_enabled(enabled: bool) -> Result<(), String> {
+    HISTORY_ENABLED.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
 /// 更新全局快捷键
 #[tauri::command]
 async fn update_shortcuts(
@@ -307,17 +699,22 @@ async fn update_shortcuts(
     fullscreen_shortcut: String,
     region_shortcut: String,
     ocr_shortcut: String,
+    clipboard_shortcut: String,
 ) -> Result<(), String> {
     use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-    println!("[DEBUG] 更新快捷键: 全屏={}, 区域={}, OCR={}", fullscreen_shortcut, region_shortcut, ocr_shortcut);
+    debug_println!(
+        "[DEBUG] 更新快捷键: 全屏={}, 区域={}, OCR={}, 剪贴板={}",
+        fullscreen_shortcut, region_shortcut, ocr_shortcut, clipboard_shortcut
+    );
 
     // 获取当前快捷键
-    let (old_fullscreen, old_region, old_ocr) = {
+    let (old_fullscreen, old_region, old_ocr, old_clipboard) = {
         let fs = CURRENT_FULLSCREEN_SHORTCUT.lock().map_err(|e| format!("锁定失败: {}", e))?;
         let rg = CURRENT_REGION_SHORTCUT.lock().map_err(|e| format!("锁定失败: {}", e))?;
         let ocr = CURRENT_OCR_SHORTCUT.lock().map_err(|e| format!("锁定失败: {}", e))?;
-        (fs.clone(), rg.clone(), ocr.clone())
+        let cb = CURRENT_CLIPBOARD_SHORTCUT.lock().map_err(|e| format!("锁定失败: {}", e))?;
+        (fs.clone(), rg.clone(), ocr.clone(), cb.clone())
     };
 
     // 注销旧的快捷键（如果存在）
@@ -336,6 +733,11 @@ async fn update_shortcuts(
             let _ = app.global_shortcut().unregister(shortcut);
         }
     }
+    if !old_clipboard.is_empty() {
+        if let Ok(shortcut) = old_clipboard.parse::<Shortcut>() {
+            let _ = app.global_shortcut().unregister(shortcut);
+        }
+    }
 
     // 注册新的全屏扫描快捷键
     let app_handle_full = app.clone();
@@ -379,6 +781,22 @@ async fn update_shortcuts(
     })
     .map_err(|e| format!("注册 OCR 快捷键失败: {}", e))?;
 
+    // 注册新的剪贴板识别快捷键
+    let app_handle_clipboard = app.clone();
+    let shortcut_clipboard: Shortcut = clipboard_shortcut.parse()
+        .map_err(|e| format!("解析剪贴板快捷键失败: {:?}", e))?;
+
+    app.global_shortcut().on_shortcut(shortcut_clipboard, move |_app, _shortcut, event| {
+        if event.state == ShortcutState::Pressed {
+            if let Some(window) = app_handle_clipboard.get_webview_window("main") {
+                let _ = window.emit("trigger_scan_clipboard", ());
+            }
+        }
+    })
+    .map_err(|e| format!("注册剪贴板识别快捷键失败: {}", e))?;
+
+    tray::set_shortcut_labels(&app, &fullscreen_shortcut, &region_shortcut, &clipboard_shortcut);
+
     // 更新存储的快捷键
     {
         let mut fs = CURRENT_FULLSCREEN_SHORTCUT.lock().map_err(|e| format!("锁定失败: {}", e))?;
@@ -392,8 +810,12 @@ async fn update_shortcuts(
         let mut ocr = CURRENT_OCR_SHORTCUT.lock().map_err(|e| format!("锁定失败: {}", e))?;
         *ocr = ocr_shortcut;
     }
+    {
+        let mut cb = CURRENT_CLIPBOARD_SHORTCUT.lock().map_err(|e| format!("锁定失败: {}", e))?;
+        *cb = clipboard_shortcut;
+    }
 
-    println!("[DEBUG] 快捷键更新成功");
+    debug_println!("[DEBUG] 快捷键更新成功");
     Ok(())
 }
 
@@ -405,102 +827,72 @@ pub fn should_minimize_to_tray() -> bool {
 /// 开始 OCR 区域选择
 #[tauri::command]
 async fn start_ocr_region_selection(app: AppHandle) -> Result<(), String> {
-    println!("[DEBUG] ======== 开始 OCR 区域选择 ========");
-
-    // 1. 隐藏主窗口
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.minimize();
-        let _ = window.hide();
-    }
-
-    // 等待窗口隐藏
-    tokio::time::sleep(std::time::Duration::from_millis(30)).await;
-
-    // 2. 截取全屏
-    println!("[DEBUG] 正在截取全屏...");
-    let images = capture_full_screen()?;
-    let screenshot = images.into_iter().next().ok_or("未找到可用屏幕")?;
-
-    println!("[DEBUG] 截图尺寸: {}x{}", screenshot.width(), screenshot.height());
-
-    // 3. 将截图转换为 base64
-    use screenshot::image_to_png_bytes;
-    let png_bytes = image_to_png_bytes(&screenshot)?;
-    let base64 = base64_encode(&png_bytes);
-    let data_url = format!("data:image/png;base64,{}", base64);
-
-    // 4. 保存截图和 base64 到全局变量
-    {
-        let mut guard = CAPTURED_SCREENSHOT.lock().map_err(|e| format!("锁定失败: {}", e))?;
-        *guard = Some(screenshot);
-    }
-    {
-        let mut guard = CAPTURED_SCREENSHOT_BASE64.lock().map_err(|e| format!("锁定失败: {}", e))?;
-        *guard = Some(data_url);
-    }
+    debug_println!("[DEBUG] ======== 开始 OCR 区域选择 ========");
 
-    // 5. 显示选择器窗口（OCR 模式）
+    capture_for_selection(&app).await?;
+    // 显示选择器窗口（OCR 模式）
     create_selector_window_with_mode(&app, "ocr")?;
 
-    println!("[DEBUG] OCR 选择器窗口已显示");
+    debug_println!("[DEBUG] OCR 选择器窗口已显示");
     Ok(())
 }
 
-/// 完成 OCR 区域选择并识别
-#[tauri::command]
-async fn complete_ocr_region_selection(
-    app: AppHandle,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
-) -> Result<(), String> {
-    println!("[DEBUG] ======== OCR 区域选择完成 ========");
-    println!("[DEBUG] 坐标: x={}, y={}, width={}, height={}", x, y, width, height);
+/// 取出预先截取的截图并关闭选择窗口，返回裁剪后的选中区域（OCR 和表格识别共用）
+fn take_selected_region(app: &AppHandle, x: u32, y: u32, width: u32, height: u32) -> Result<DynamicImage, String> {
+    debug_println!("[DEBUG] 坐标: x={}, y={}, width={}, height={}", x, y, width, height);
 
     // 从全局变量获取预先截取的图像
-    let screenshot = {
-        let mut guard = CAPTURED_SCREENSHOT.lock().map_err(|e| format!("锁定失败: {}", e))?;
-        guard.take().ok_or("未找到预先截取的截图")?
-    };
-
-    // 清理 base64 数据
-    {
-        let mut guard = CAPTURED_SCREENSHOT_BASE64.lock().map_err(|e| format!("锁定失败: {}", e))?;
-        *guard = None;
-    }
+    let screenshot = take_captured_screenshot()?;
 
     // 关闭选择窗口
-    close_selector_window(&app)?;
+    close_selector_window(app)?;
 
     // 等待窗口完全关闭
     std::thread::sleep(std::time::Duration::from_millis(50));
 
-    println!("[DEBUG] 使用预先截取的截图，尺寸: {}x{}", screenshot.width(), screenshot.height());
+    debug_println!("[DEBUG] 使用预先截取的截图，尺寸: {}x{}", screenshot.image.width(), screenshot.image.height());
 
     // 裁剪图像
-    let img_width = screenshot.width();
-    let img_height = screenshot.height();
+    let img_width = screenshot.image.width();
+    let img_height = screenshot.image.height();
 
     let safe_x = x.min(img_width.saturating_sub(1));
     let safe_y = y.min(img_height.saturating_sub(1));
     let safe_width = width.min(img_width.saturating_sub(safe_x));
     let safe_height = height.min(img_height.saturating_sub(safe_y));
 
-    let cropped = screenshot.crop_imm(safe_x, safe_y, safe_width, safe_height);
+    let cropped = screenshot.image.crop_imm(safe_x, safe_y, safe_width, safe_height);
 
-    println!("[DEBUG] 裁剪完成，结果尺寸: {}x{}", cropped.width(), cropped.height());
+    debug_println!("[DEBUG] 裁剪完成，结果尺寸: {}x{}", cropped.width(), cropped.height());
+    Ok(cropped)
+}
+
+/// 完成 OCR 区域选择并识别
+#[tauri::command]
+async fn complete_ocr_region_selection(
+    app: AppHandle,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<(), String> {
+    debug_println!("[DEBUG] ======== OCR 区域选择完成 ========");
+    let cropped = take_selected_region(&app, x, y, width, height)?;
 
     // 执行 OCR 识别
     let result = ocr::recognize_text(&cropped).unwrap_or_else(|e| {
-        println!("[DEBUG] OCR 识别失败: {}，返回空结果", e);
+        debug_println!("[DEBUG] OCR 识别失败: {}，返回空结果", e);
         OcrResult {
             text: String::new(),
             language: "unknown".to_string(),
+            lines: Vec::new(),
         }
     });
 
-    println!("[DEBUG] OCR 识别完成，文本长度: {}", result.text.len());
+    debug_println!("[DEBUG] OCR 识别完成，文本长度: {}", result.text.len());
+    if !result.text.trim().is_empty() {
+        record_history([NewEntry::from_text(HistorySource::Ocr, result.text.clone(), Some(&cropped))]);
+    }
 
     // 显示主窗口
     if let Some(window) = app.get_webview_window("main") {
@@ -518,17 +910,17 @@ async fn complete_ocr_region_selection(
         if let Some(window) = app.get_webview_window("main") {
             match window.emit("ocr_scan_complete", &result) {
                 Ok(_) => {
-                    println!("[DEBUG] 已向主窗口发送 ocr_scan_complete 事件");
+                    debug_println!("[DEBUG] 已向主窗口发送 ocr_scan_complete 事件");
                     break;
                 }
                 Err(e) => {
-                    println!("[WARN] 发送事件失败 (尝试 {}): {}", retry_count + 1, e);
+                    debug_println!("[WARN] 发送事件失败 (尝试 {}): {}", retry_count + 1, e);
                     retry_count += 1;
                     std::thread::sleep(std::time::Duration::from_millis(100));
                 }
             }
         } else {
-            println!("[WARN] 无法获取主窗口 (尝试 {})", retry_count + 1);
+            debug_println!("[WARN] 无法获取主窗口 (尝试 {})", retry_count + 1);
             retry_count += 1;
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
@@ -537,11 +929,208 @@ async fn complete_ocr_region_selection(
     Ok(())
 }
 
+/// 列出可用的 OCR 模型包（内置模型包在最前）
+#[tauri::command]
+async fn list_ocr_model_packs() -> Result<Vec<ModelPack>, String> {
+    Ok(models::list_packs())
+}
+
+/// 设置外部 OCR 模型目录（为空时只使用内置模型包），返回其中可用的模型包
+#[tauri::command]
+async fn set_ocr_models_dir(path: Option<String>) -> Result<Vec<ModelPack>, String> {
+    let dir = path.filter(|p| !p.trim().is_empty()).map(std::path::PathBuf::from);
+    if let Some(dir) = &dir {
+        if !dir.is_dir() {
+            return Err(format!("模型目录不存在: {}", dir.display()));
+        }
+    }
+    models::set_models_dir(dir);
+    Ok(models::list_packs())
+}
+
+/// 切换 OCR 模型包（下次识别时加载，加载失败时回退到内置模型包）
+#[tauri::command]
+async fn select_ocr_model_pack(id: String) -> Result<(), String> {
+    models::select_pack(&id).map_err(String::from)
+}
+
+/// 开始表格区域选择 - 先截图再显示选择窗口（表格模式）
+#[tauri::command]
+async fn start_table_region_selection(app: AppHandle) -> Result<(), String> {
+    debug_println!("[DEBUG] ======== 开始表格区域选择 ========");
+
+    capture_for_selection(&app).await?;
+    create_selector_window_with_mode(&app, "table")?;
+    Ok(())
+}
+
+/// 完成表格区域选择并重建表格，结果通过 `table_scan_complete` 事件发送
+#[tauri::command]
+async fn complete_table_region_selection(
+    app: AppHandle,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<(), String> {
+    debug_println!("[DEBUG] ======== 表格区域选择完成 ========");
+    let cropped = take_selected_region(&app, x, y, width, height)?;
+
+    let table = ocr::recognize_table(&cropped).unwrap_or_else(|e| {
+        debug_println!("[DEBUG] 表格识别失败: {}，返回空结果", e);
+        Table::default()
+    });
+    if !table.is_empty() {
+        record_history([NewEntry::from_text(HistorySource::Ocr, table.export(TableFormat::Tsv), Some(&cropped))]);
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        window.emit("table_scan_complete", &table).map_err(|e| format!("发送事件失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 将表格导出为 TSV/CSV/Markdown 文本（用于复制）
+#[tauri::command]
+async fn export_table(table: Table, format: TableFormat) -> Result<String, String> {
+    Ok(table.export(format))
+}
+
+/// 识别剪贴板中的图像（二维码和文字），结果通过与区域扫描相同的事件发送
+#[tauri::command]
+async fn scan_clipboard(app: AppHandle) -> Result<(), String> {
+    debug_println!("[DEBUG] ======== 剪贴板识别 ========");
+
+    let image = glimpse_core::read_clipboard_image()?;
+    debug_println!("[DEBUG] 剪贴板图像尺寸: {}x{}", image.width(), image.height());
+
+    // 扫描二维码（坐标为剪贴板图像内的像素坐标）
+    let results = current_pipeline()
+        .and_then(|pipeline| scan_qr_codes(&image, &pipeline).map_err(String::from))
+        .map(record_diagnostics)
+        .unwrap_or_else(|e| {
+            debug_println!("[DEBUG] 二维码识别失败: {}，返回空结果", e);
+            vec![]
+        });
+    debug_println!("[DEBUG] 剪贴板识别找到 {} 个二维码", results.len());
+
+    // 执行 OCR 识别
+    let ocr_result = ocr::recognize_text(&image).unwrap_or_else(|e| {
+        debug_println!("[DEBUG] OCR 识别失败: {}，返回空结果", e);
+        OcrResult {
+            text: String::new(),
+            language: "unknown".to_string(),
+            lines: Vec::new(),
+        }
+    });
+    debug_println!("[DEBUG] 剪贴板 OCR 完成，文本长度: {}", ocr_result.text.len());
+
+    record_code_history(HistorySource::Clipboard, &results, |_| Some(&image));
+    if !ocr_result.text.trim().is_empty() {
+        record_history([NewEntry::from_text(HistorySource::Clipboard, ocr_result.text.clone(), Some(&image))]);
+    }
+
+    // 显示主窗口并发送结果事件（先二维码，后文字）
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        let _ = window.emit("region_scan_complete", &results);
+        if !ocr_result.text.trim().is_empty() {
+            let _ = window.emit("ocr_scan_complete", &ocr_result);
+        }
+        debug_println!("[DEBUG] 已向主窗口发送剪贴板识别结果");
+    }
+
+    Ok(())
+}
+
+/// 单个图像文件的识别结果
+#[derive(Debug, Clone, Serialize)]
+struct FileScanResult {
+    /// 文件路径
+    path: String,
+    /// 帧数（动画 GIF、多页 TIFF 大于 1）
+    frames: usize,
+    /// 二维码识别结果（多帧时标记所在帧）
+    codes: Vec<QrCodeResult>,
+    /// 文字识别结果（跳过空白帧和与上一帧相同的文本）
+    texts: Vec<FrameText>,
+    /// 读取或识别失败的原因
+    error: Option<String>,
+}
+
+/// 识别图像文件（拖放到窗口或通过文件对话框选择）
+#[tauri::command]
+async fn scan_image_files(paths: Vec<String>, with_ocr: bool) -> Result<Vec<FileScanResult>, String> {
+    debug_println!("[DEBUG] 识别图像文件: {} 个, OCR: {}", paths.len(), with_ocr);
+
+    let pipeline = current_pipeline()?;
+    let mut all_diagnostics = Vec::new();
+    let mut file_results = Vec::with_capacity(paths.len());
+
+    for path in paths {
+        let mut file_result = FileScanResult { path, frames: 0, codes: Vec::new(), texts: Vec::new(), error: None };
+
+        let frames = match image_file::load_frames(std::path::Path::new(&file_result.path)) {
+            Ok(frames) => frames,
+            Err(e) => {
+                debug_println!("[DEBUG] 读取 {} 失败: {}", file_result.path, e);
+                file_result.error = Some(e.to_string());
+                file_results.push(file_result);
+                continue;
+            }
+        };
+        file_result.frames = frames.len();
+
+        match scan_frames(&frames, &pipeline) {
+            Ok(report) => {
+                file_result.codes = report.results;
+                all_diagnostics.extend(report.diagnostics);
+            }
+            Err(e) => file_result.error = Some(format!("二维码识别失败: {}", e)),
+        }
+
+        if with_ocr {
+            match ocr::recognize_frames(&frames) {
+                Ok(texts) => file_result.texts = texts,
+                Err(e) => debug_println!("[DEBUG] {} 文字识别失败: {}", file_result.path, e),
+            }
+        }
+
+        // 缩略图取自码或文字所在的帧
+        let frame_image = |frame: Option<usize>| {
+            frames.iter().find(|f| f.index == frame.unwrap_or(0)).map(|f| &f.image)
+        };
+        record_code_history(HistorySource::File, &file_result.codes, |result| frame_image(result.frame));
+        record_history(file_result.texts.iter().map(|text| {
+            NewEntry::from_text(HistorySource::File, text.result.text.clone(), frame_image(text.frame))
+        }));
+
+        debug_println!(
+            "[DEBUG] {}: {} 帧，{} 个二维码，{} 段文字",
+            file_result.path, file_result.frames, file_result.codes.len(), file_result.texts.len()
+        );
+        file_results.push(file_result);
+    }
+
+    if let Ok(mut guard) = LAST_SCAN_DIAGNOSTICS.lock() {
+        *guard = all_diagnostics;
+    }
+
+    Ok(file_results)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_dialog::init())
         .on_window_event(|window, event| {
             // 处理窗口关闭事件
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
@@ -555,11 +1144,24 @@ pub fn run() {
         })
         .setup(|app| {
             // 创建系统托盘
-            tray::create_tray(&app.handle())?;
+            tray::create_tray(app.handle())?;
+
+            // 打开识别历史记录、设置 OCR 模型缓存目录（位于应用数据目录）
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    models::set_cache_dir(dir.join("ocr_models"));
+                    std::thread::spawn(models::remove_legacy_cache);
+                    match History::open(dir.join("history.jsonl")) {
+                        Ok(history) => *HISTORY.lock().unwrap() = Some(history),
+                        Err(e) => debug_println!("[WARN] 打开历史记录失败: {}", e),
+                    }
+                }
+                Err(e) => debug_println!("[WARN] 获取应用数据目录失败: {}", e),
+            }
 
             // 预加载选择器窗口（加速区域扫描）
-            if let Err(e) = preload_selector_window(&app.handle()) {
-                println!("[WARN] 预加载选择器窗口失败: {}", e);
+            if let Err(e) = preload_selector_window(app.handle()) {
+                debug_println!("[WARN] 预加载选择器窗口失败: {}", e);
             }
 
             // 注册全局快捷键
@@ -605,6 +1207,20 @@ pub fn run() {
             })
             .map_err(|e| format!("注册 OCR 快捷键失败: {}", e))?;
 
+            // 注册剪贴板识别快捷键
+            let app_handle_clipboard = app.handle().clone();
+            let shortcut_clipboard: Shortcut = "Ctrl+Shift+X".parse()
+                .map_err(|e| format!("解析剪贴板快捷键失败: {:?}", e))?;
+
+            app.global_shortcut().on_shortcut(shortcut_clipboard, move |_app, _shortcut, event| {
+                if event.state == ShortcutState::Pressed {
+                    if let Some(window) = app_handle_clipboard.get_webview_window("main") {
+                        let _ = window.emit("trigger_scan_clipboard", ());
+                    }
+                }
+            })
+            .map_err(|e| format!("注册剪贴板识别快捷键失败: {}", e))?;
+
             // 初始化默认快捷键存储
             {
                 let mut fs = CURRENT_FULLSCREEN_SHORTCUT.lock().unwrap();
@@ -618,6 +1234,10 @@ pub fn run() {
                 let mut ocr = CURRENT_OCR_SHORTCUT.lock().unwrap();
                 *ocr = "ctrl+shift+d".to_string();
             }
+            {
+                let mut cb = CURRENT_CLIPBOARD_SHORTCUT.lock().unwrap();
+                *cb = "ctrl+shift+x".to_string();
+            }
 
             Ok(())
         })
@@ -634,9 +1254,38 @@ pub fn run() {
             get_screenshot_data,
             set_close_behavior,
             get_close_behavior,
+            set_scan_pipeline,
+            get_scan_pipeline,
+            get_default_scan_pipeline,
+            get_scan_diagnostics,
+            generate_qr,
+            set_open_policy,
+            get_open_policy,
+            get_default_open_policy,
+            evaluate_open_policy,
+            open_url,
+            start_watch,
+            stop_watch,
+            get_watch_status,
+            start_watch_region_selection,
+            complete_watch_region_selection,
+            query_history,
+            set_history_pinned,
+            delete_history_entry,
+            clear_history,
+            export_history,
+            set_history_enabled,
             update_shortcuts,
             start_ocr_region_selection,
-            complete_ocr_region_selection
+            complete_ocr_region_selection,
+            start_table_region_selection,
+            list_ocr_model_packs,
+            set_ocr_models_dir,
+            select_ocr_model_pack,
+            complete_table_region_selection,
+            export_table,
+            scan_clipboard,
+            scan_image_files
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -4,6 +4,38 @@ use tauri::{
     AppHandle, Emitter, Manager, Runtime,
 };
 
+/// 托盘图标提示文字
+const TRAY_TOOLTIP: &str = "灵视 Glimpse";
+
+/// 监视菜单项（用于切换显示文字）
+struct WatchMenuItem<R: Runtime>(MenuItem<R>);
+
+/// 显示快捷键的菜单项（快捷键修改后更新文字）
+struct ShortcutMenuItems<R: Runtime> {
+    scan_full: MenuItem<R>,
+    scan_region: MenuItem<R>,
+    scan_clipboard: MenuItem<R>,
+}
+
+const SCAN_FULL_LABEL: &str = "全屏扫描";
+const SCAN_REGION_LABEL: &str = "区域扫描";
+const SCAN_CLIPBOARD_LABEL: &str = "识别剪贴板图片";
+
+/// 菜单文字：名称后附上当前快捷键（如 "ctrl+shift+x" 显示为 "Ctrl+Shift+X"），未设置快捷键时只显示名称
+fn shortcut_label(name: &str, shortcut: &str) -> String {
+    if shortcut.trim().is_empty() {
+        return name.to_string();
+    }
+    let keys: Vec<String> = shortcut
+        .split('+')
+        .map(|key| {
+            let mut chars = key.trim().chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect();
+    format!("{} ({})", name, keys.join("+"))
+}
+
 /// 创建系统托盘
 pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     // 创建托盘菜单
@@ -13,7 +45,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     let scan_full_item = MenuItem::with_id(
         app,
         "scan_full",
-        "全屏扫描 (Ctrl+Shift+S)",
+        shortcut_label(SCAN_FULL_LABEL, "ctrl+shift+s"),
         true,
         None::<&str>,
     )
@@ -22,25 +54,44 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     let scan_region_item = MenuItem::with_id(
         app,
         "scan_region",
-        "区域扫描 (Ctrl+Shift+A)",
+        shortcut_label(SCAN_REGION_LABEL, "ctrl+shift+a"),
+        true,
+        None::<&str>,
+    )
+    .map_err(|e| format!("创建菜单项失败: {}", e))?;
+
+    let scan_clipboard_item = MenuItem::with_id(
+        app,
+        "scan_clipboard",
+        shortcut_label(SCAN_CLIPBOARD_LABEL, "ctrl+shift+x"),
         true,
         None::<&str>,
     )
     .map_err(|e| format!("创建菜单项失败: {}", e))?;
 
+    let watch_item = MenuItem::with_id(app, "toggle_watch", "开始监视屏幕", true, None::<&str>)
+        .map_err(|e| format!("创建菜单项失败: {}", e))?;
+
     let quit_item = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)
         .map_err(|e| format!("创建菜单项失败: {}", e))?;
 
     let menu = Menu::with_items(
         app,
-        &[&show_item, &scan_full_item, &scan_region_item, &quit_item],
+        &[
+            &show_item,
+            &scan_full_item,
+            &scan_region_item,
+            &scan_clipboard_item,
+            &watch_item,
+            &quit_item,
+        ],
     )
     .map_err(|e| format!("创建菜单失败: {}", e))?;
 
     // 构建托盘图标
     let _tray = TrayIconBuilder::with_id("main-tray")
         .icon(app.default_window_icon().unwrap().clone())
-        .tooltip("灵视 Glimpse")
+        .tooltip(TRAY_TOOLTIP)
         .menu(&menu)
         .on_menu_event(move |app, event| match event.id.as_ref() {
             "show" => {
@@ -61,6 +112,18 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
                     let _ = window.emit("trigger_scan_region", ());
                 }
             }
+            "scan_clipboard" => {
+                // 触发剪贴板识别
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.emit("trigger_scan_clipboard", ());
+                }
+            }
+            "toggle_watch" => {
+                // 由前端按当前设置开始或停止监视
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.emit("trigger_watch_toggle", ());
+                }
+            }
             "quit" => {
                 app.exit(0);
             }
@@ -88,5 +151,31 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
         .build(app)
         .map_err(|e| format!("构建托盘图标失败: {}", e))?;
 
+    app.manage(WatchMenuItem(watch_item));
+    app.manage(ShortcutMenuItems {
+        scan_full: scan_full_item,
+        scan_region: scan_region_item,
+        scan_clipboard: scan_clipboard_item,
+    });
     Ok(())
 }
+
+/// 快捷键修改后更新菜单项上显示的快捷键
+pub fn set_shortcut_labels<R: Runtime>(app: &AppHandle<R>, fullscreen: &str, region: &str, clipboard: &str) {
+    if let Some(items) = app.try_state::<ShortcutMenuItems<R>>() {
+        let _ = items.scan_full.set_text(shortcut_label(SCAN_FULL_LABEL, fullscreen));
+        let _ = items.scan_region.set_text(shortcut_label(SCAN_REGION_LABEL, region));
+        let _ = items.scan_clipboard.set_text(shortcut_label(SCAN_CLIPBOARD_LABEL, clipboard));
+    }
+}
+
+/// 更新托盘上的监视状态指示（提示文字和菜单项）
+pub fn set_watch_indicator<R: Runtime>(app: &AppHandle<R>, running: bool) {
+    if let Some(tray) = app.tray_by_id("main-tray") {
+        let tooltip = if running { format!("{} - 监视中", TRAY_TOOLTIP) } else { TRAY_TOOLTIP.to_string() };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+    if let Some(item) = app.try_state::<WatchMenuItem<R>>() {
+        let _ = item.0.set_text(if running { "停止监视" } else { "开始监视屏幕" });
+    }
+}
@@ -2,18 +2,46 @@ const { invoke } = window.__TAURI__.core;
 const { listen } = window.__TAURI__.event;
 
 // DOM 元素
-let scanFullBtn, scanRegionBtn, ocrRegionBtn, clearResultsBtn, themeToggleBtn, settingsBtn;
+let scanFullBtn, scanRegionBtn, ocrRegionBtn, ocrTableBtn, scanClipboardBtn, scanFileBtn, clearResultsBtn, themeToggleBtn, settingsBtn;
 let statusEl, statusTextEl, resultsContainer, resultsList, noResultsEl;
-let resultCountEl, selectionOverlay, selectionBox, selectionInfo;
+let resultCountEl, selectionOverlay, selectionBox, selectionInfo, dropOverlayEl;
+let watchBarEl, watchLabelEl, watchScreenBtn, watchRegionBtn, watchStopBtn, watchIntervalInput;
 
 // 设置弹窗元素
 let settingsModal, settingsClose, settingsSave, settingsCancel;
-let shortcutFullscreenInput, shortcutRegionInput, shortcutOcrInput;
+let shortcutFullscreenInput, shortcutRegionInput, shortcutOcrInput, shortcutClipboardInput;
+let pipelineListEl, pipelineResetBtn, pipelineKeepDuplicatesEl, scanDiagnosticsEl;
+let openRuleListEl, openRuleAddBtn, openPolicyResetBtn, openDefaultActionEl;
+
+// 二维码生成弹窗元素
+let generateBtn, generatorModal, generatorClose, generatorSubmit, generatorCopySvg, generatorCopyPng;
+let generatorTextEl, generatorPreviewEl, generatorInfoEl;
+
+// 识别历史弹窗元素
+let historyBtn, historyModal, historyClose, historyListEl, historySearchEl, historyTypeEl, historySourceEl;
+let historySinceEl, historyUntilEl, historyPinnedOnlyEl, historyExportFormatEl, historyExportBtn, historyClearBtn;
+let historyEnabledInput;
+let ocrModelsDirInput, ocrModelsDirBtn, ocrModelsDirClearBtn, ocrModelPackEl;
+
+// 历史搜索输入防抖定时器
+let historySearchTimer = null;
+
+// 最近一次生成的二维码
+let lastGeneratedQr = null;
+
+// 设置弹窗中正在编辑的识别策略
+let editingPipeline = null;
+
+// 设置弹窗中正在编辑的链接打开策略
+let editingOpenPolicy = null;
 
 // 区域选择状态
 let isSelecting = false;
 let startX = 0, startY = 0;
 
+// 监视模式是否正在运行
+let isWatching = false;
+
 // 快捷键录制状态
 let isRecordingShortcut = false;
 let currentRecordingTarget = null;
@@ -24,8 +52,54 @@ const defaultSettings = {
   shortcuts: {
     fullscreen: 'Ctrl + Shift + S',
     region: 'Ctrl + Shift + A',
-    ocr: 'Ctrl + Shift + D'
-  }
+    ocr: 'Ctrl + Shift + D',
+    clipboard: 'Ctrl + Shift + X'
+  },
+  // 监视模式截取间隔（毫秒）
+  watchIntervalMs: 1000,
+  // 是否保存识别历史
+  historyEnabled: true,
+  // 外部 OCR 模型目录（空字符串表示只使用内置模型包）和选中的模型包
+  ocrModelsDir: "",
+  ocrModelPack: "builtin-ch",
+  // 识别策略（null 表示使用后端默认配置）
+  pipeline: null,
+  // 链接打开规则（null 表示使用后端默认配置）
+  openPolicy: null
+};
+
+// 支持识别的图片文件扩展名（与后端 image_file::SUPPORTED_EXTENSIONS 一致）
+const IMAGE_EXTENSIONS = ["png", "jpg", "jpeg", "webp", "bmp", "gif", "tif", "tiff"];
+
+// 预处理方法显示名称
+const PREPROCESS_LABELS = {
+  None: "原图",
+  ContrastBoost: "增强对比度",
+  Brightness: "增加亮度",
+  Sharpen: "锐化",
+  Invert: "反色",
+  Otsu: "Otsu 二值化",
+  Sauvola: "自适应二值化",
+  MorphClose: "闭运算",
+  Median: "中值去噪",
+  RedChannel: "红色通道",
+  GreenChannel: "绿色通道",
+  BlueChannel: "蓝色通道",
+  Mirror: "水平镜像"
+};
+
+// 链接打开动作显示名称
+const OPEN_ACTION_LABELS = {
+  Open: "自动打开",
+  Confirm: "确认后打开",
+  Block: "禁止打开"
+};
+
+// 链接打开规则类型显示名称
+const RULE_PATTERN_LABELS = {
+  Domain: "域名",
+  Scheme: "协议",
+  Regex: "正则"
 };
 
 // 初始化
@@ -45,6 +119,10 @@ function initElements() {
   scanFullBtn = document.querySelector("#scan-full-btn");
   scanRegionBtn = document.querySelector("#scan-region-btn");
   ocrRegionBtn = document.querySelector("#ocr-region-btn");
+  ocrTableBtn = document.querySelector("#ocr-table-btn");
+  scanClipboardBtn = document.querySelector("#scan-clipboard-btn");
+  scanFileBtn = document.querySelector("#scan-file-btn");
+  dropOverlayEl = document.querySelector("#drop-overlay");
   clearResultsBtn = document.querySelector("#clear-results-btn");
   themeToggleBtn = document.querySelector("#theme-toggle");
   settingsBtn = document.querySelector("#settings-btn");
@@ -54,6 +132,11 @@ function initElements() {
   resultsList = document.querySelector("#results-list");
   noResultsEl = document.querySelector("#no-results");
   resultCountEl = document.querySelector("#result-count");
+  watchBarEl = document.querySelector("#watch-bar");
+  watchLabelEl = document.querySelector("#watch-label");
+  watchScreenBtn = document.querySelector("#watch-screen-btn");
+  watchRegionBtn = document.querySelector("#watch-region-btn");
+  watchStopBtn = document.querySelector("#watch-stop-btn");
 
   // 设置弹窗元素
   settingsModal = document.querySelector("#settings-modal");
@@ -63,6 +146,47 @@ function initElements() {
   shortcutFullscreenInput = document.querySelector("#shortcut-fullscreen");
   shortcutRegionInput = document.querySelector("#shortcut-region");
   shortcutOcrInput = document.querySelector("#shortcut-ocr");
+  shortcutClipboardInput = document.querySelector("#shortcut-clipboard");
+  watchIntervalInput = document.querySelector("#watch-interval");
+  historyEnabledInput = document.querySelector("#history-enabled");
+  ocrModelsDirInput = document.querySelector("#ocr-models-dir");
+  ocrModelsDirBtn = document.querySelector("#ocr-models-dir-btn");
+  ocrModelsDirClearBtn = document.querySelector("#ocr-models-dir-clear");
+  ocrModelPackEl = document.querySelector("#ocr-model-pack");
+  pipelineListEl = document.querySelector("#pipeline-list");
+  pipelineResetBtn = document.querySelector("#pipeline-reset");
+  pipelineKeepDuplicatesEl = document.querySelector("#pipeline-keep-duplicates");
+  scanDiagnosticsEl = document.querySelector("#scan-diagnostics");
+  openRuleListEl = document.querySelector("#open-rule-list");
+  openRuleAddBtn = document.querySelector("#open-rule-add");
+  openPolicyResetBtn = document.querySelector("#open-policy-reset");
+  openDefaultActionEl = document.querySelector("#open-default-action");
+
+  // 二维码生成弹窗元素
+  generateBtn = document.querySelector("#generate-btn");
+  generatorModal = document.querySelector("#generator-modal");
+  generatorClose = document.querySelector("#generator-close");
+  generatorSubmit = document.querySelector("#generator-submit");
+  generatorCopySvg = document.querySelector("#generator-copy-svg");
+  generatorCopyPng = document.querySelector("#generator-copy-png");
+  generatorTextEl = document.querySelector("#generator-text");
+  generatorPreviewEl = document.querySelector("#generator-preview");
+  generatorInfoEl = document.querySelector("#generator-info");
+
+  // 识别历史弹窗元素
+  historyBtn = document.querySelector("#history-btn");
+  historyModal = document.querySelector("#history-modal");
+  historyClose = document.querySelector("#history-close");
+  historyListEl = document.querySelector("#history-list");
+  historySearchEl = document.querySelector("#history-search");
+  historyTypeEl = document.querySelector("#history-type");
+  historySourceEl = document.querySelector("#history-source");
+  historySinceEl = document.querySelector("#history-since");
+  historyUntilEl = document.querySelector("#history-until");
+  historyPinnedOnlyEl = document.querySelector("#history-pinned-only");
+  historyExportFormatEl = document.querySelector("#history-export-format");
+  historyExportBtn = document.querySelector("#history-export");
+  historyClearBtn = document.querySelector("#history-clear");
 }
 
 // 初始化事件监听
@@ -70,7 +194,13 @@ function initEventListeners() {
   scanFullBtn.addEventListener("click", handleScanFull);
   scanRegionBtn.addEventListener("click", handleScanRegion);
   ocrRegionBtn.addEventListener("click", handleOcrRegion);
+  ocrTableBtn.addEventListener("click", handleOcrTable);
+  scanClipboardBtn.addEventListener("click", handleScanClipboard);
+  scanFileBtn.addEventListener("click", handleOpenFiles);
   clearResultsBtn.addEventListener("click", clearResults);
+  watchScreenBtn.addEventListener("click", handleWatchScreen);
+  watchRegionBtn.addEventListener("click", handleWatchRegion);
+  watchStopBtn.addEventListener("click", handleStopWatch);
   themeToggleBtn.addEventListener("click", toggleTheme);
 
   // 设置弹窗事件
@@ -78,10 +208,43 @@ function initEventListeners() {
   settingsClose.addEventListener("click", closeSettings);
   settingsCancel.addEventListener("click", closeSettings);
   settingsSave.addEventListener("click", saveSettings);
+  ocrModelsDirBtn.addEventListener("click", chooseOcrModelsDir);
+  ocrModelsDirClearBtn.addEventListener("click", () => changeOcrModelsDir(""));
+  pipelineResetBtn.addEventListener("click", resetPipeline);
+  pipelineKeepDuplicatesEl.addEventListener("change", () => {
+    editingPipeline.keep_duplicates = pipelineKeepDuplicatesEl.checked;
+  });
+  openRuleAddBtn.addEventListener("click", addOpenRule);
+  openPolicyResetBtn.addEventListener("click", resetOpenPolicy);
+  openDefaultActionEl.addEventListener("change", () => {
+    editingOpenPolicy.default_action = openDefaultActionEl.value;
+  });
 
   // 点击遮罩层关闭弹窗
   settingsModal.querySelector(".modal-backdrop").addEventListener("click", closeSettings);
 
+  // 二维码生成弹窗事件
+  generateBtn.addEventListener("click", openGenerator);
+  generatorClose.addEventListener("click", closeGenerator);
+  generatorSubmit.addEventListener("click", handleGenerate);
+  generatorCopySvg.addEventListener("click", () => copyToClipboard(lastGeneratedQr.svg, generatorCopySvg));
+  generatorCopyPng.addEventListener("click", copyGeneratedPng);
+  generatorModal.querySelector(".modal-backdrop").addEventListener("click", closeGenerator);
+
+  // 识别历史弹窗事件
+  historyBtn.addEventListener("click", openHistory);
+  historyClose.addEventListener("click", closeHistory);
+  historyModal.querySelector(".modal-backdrop").addEventListener("click", closeHistory);
+  historySearchEl.addEventListener("input", () => {
+    clearTimeout(historySearchTimer);
+    historySearchTimer = setTimeout(refreshHistory, 200);
+  });
+  [historyTypeEl, historySourceEl, historySinceEl, historyUntilEl, historyPinnedOnlyEl].forEach(el => {
+    el.addEventListener("change", refreshHistory);
+  });
+  historyExportBtn.addEventListener("click", exportHistory);
+  historyClearBtn.addEventListener("click", clearHistory);
+
   // 快捷键录制按钮
   document.querySelectorAll(".shortcut-record-btn").forEach(btn => {
     btn.addEventListener("click", () => startRecordingShortcut(btn.dataset.target));
@@ -96,6 +259,21 @@ async function initTauriListeners() {
   await listen("trigger_scan_full", handleScanFull);
   await listen("trigger_scan_region", handleScanRegion);
   await listen("trigger_ocr_region", handleOcrRegion);
+  await listen("trigger_scan_clipboard", handleScanClipboard);
+  await listen("trigger_watch_toggle", () => isWatching ? handleStopWatch() : handleWatchScreen());
+
+  // 监视模式发现新的二维码
+  await listen("qr_detected", (event) => {
+    console.log('[DEBUG] 收到 qr_detected 事件:', event.payload);
+    appendResults(event.payload);
+    showToast(`监视发现 ${event.payload.length} 个新二维码`, 'success');
+  });
+
+  // 监视状态变化（开始、停止）
+  await listen("watch_state_changed", (event) => updateWatchBar(event.payload));
+  invoke("get_watch_status").then(updateWatchBar).catch(error => {
+    console.error('[ERROR] 获取监视状态失败:', error);
+  });
 
   // 监听区域扫描完成事件
   await listen("region_scan_complete", (event) => {
@@ -109,6 +287,12 @@ async function initTauriListeners() {
     showOcrResult(event.payload);
   });
 
+  // 监听表格识别完成事件
+  await listen("table_scan_complete", (event) => {
+    console.log('[DEBUG] 收到 table_scan_complete 事件:', event.payload);
+    showTableResult(event.payload);
+  });
+
   // 监听区域扫描取消事件
   await listen("region_scan_cancelled", async () => {
     console.log('[DEBUG] 收到 region_scan_cancelled 事件');
@@ -122,6 +306,24 @@ async function initTauriListeners() {
     showError(`扫描失败: ${event.payload}`);
   });
 
+  // 监听文件拖放
+  await window.__TAURI__.webviewWindow.getCurrentWebviewWindow().onDragDropEvent((event) => {
+    const { type, paths } = event.payload;
+    if (type === "enter" || type === "over") {
+      dropOverlayEl.classList.remove("hidden");
+    } else {
+      dropOverlayEl.classList.add("hidden");
+    }
+    if (type === "drop") {
+      const images = paths.filter(isImageFile);
+      if (images.length > 0) {
+        handleScanFiles(images);
+      } else {
+        showToast("没有可识别的图片文件", "error");
+      }
+    }
+  });
+
   console.log('[DEBUG] Tauri 事件监听器已初始化');
 }
 
@@ -206,6 +408,94 @@ async function handleOcrRegion() {
   }
 }
 
+// 表格识别：框选区域后重建行列
+async function handleOcrTable() {
+  try {
+    const items = resultsList.querySelectorAll('.result-item');
+    items.forEach(item => item.remove());
+    resultCountEl.textContent = "0";
+    noResultsEl.classList.add("hidden");
+
+    await invoke("start_table_region_selection");
+  } catch (error) {
+    console.error('[ERROR] 准备表格识别失败:', error);
+    try {
+      await invoke("show_window");
+    } catch (e) {
+      console.error('[ERROR] 恢复窗口显示失败:', e);
+    }
+    showError(`准备表格识别失败: ${error}`);
+  }
+}
+
+// 剪贴板识别（二维码和文字）
+async function handleScanClipboard() {
+  try {
+    console.log('[DEBUG] 开始剪贴板识别流程');
+
+    // 先清空旧结果，二维码和文字结果会依次追加
+    const items = resultsList.querySelectorAll('.result-item');
+    items.forEach(item => item.remove());
+    resultCountEl.textContent = "0";
+
+    showStatus('正在识别剪贴板图片...');
+    await invoke("scan_clipboard");
+  } catch (error) {
+    console.error('[ERROR] 剪贴板识别失败:', error);
+    showError(`剪贴板识别失败: ${error}`);
+  }
+}
+
+// 是否为支持识别的图片文件（按扩展名判断）
+function isImageFile(path) {
+  const ext = path.split(".").pop().toLowerCase();
+  return IMAGE_EXTENSIONS.includes(ext);
+}
+
+// 通过文件对话框选择图片
+async function handleOpenFiles() {
+  try {
+    const selected = await window.__TAURI__.dialog.open({
+      multiple: true,
+      filters: [{ name: "图片", extensions: IMAGE_EXTENSIONS }]
+    });
+    if (selected && selected.length > 0) {
+      await handleScanFiles(selected);
+    }
+  } catch (error) {
+    console.error('[ERROR] 打开文件失败:', error);
+    showError(`打开文件失败: ${error}`);
+  }
+}
+
+// 识别图片文件（二维码和文字），动画 GIF 和多页 TIFF 逐帧识别
+async function handleScanFiles(paths) {
+  try {
+    console.log('[DEBUG] 识别图片文件:', paths);
+    showStatus(`正在识别 ${paths.length} 个文件...`);
+    const fileResults = await invoke("scan_image_files", { paths, withOcr: true });
+    console.log('[DEBUG] 文件识别结果:', fileResults);
+
+    // 多个文件时在结果上标注来源文件名
+    const codes = [];
+    const texts = [];
+    for (const file of fileResults) {
+      const source = paths.length > 1 ? file.path.split(/[\\/]/).pop() : null;
+      if (file.error) {
+        showToast(`${file.path.split(/[\\/]/).pop()}: ${file.error}`, "error");
+      }
+      file.codes.forEach(code => codes.push({ ...code, source }));
+      file.texts.forEach(text => texts.push({ ...text, source }));
+    }
+
+    showResults(codes);
+    texts.forEach(text => showOcrResult(text));
+  } catch (error) {
+    console.error('[ERROR] 文件识别失败:', error);
+    showError(`文件识别失败: ${error}`);
+  }
+}
+
 // 显示状态
 function showStatus(message) {
   statusTextEl.textContent = message;
@@ -220,6 +510,7 @@ function hideStatus() {
 // 显示结果
 function showResults(results) {
   hideStatus();
+  showScanDiagnostics();
 
   if (!results || results.length === 0) {
     // 显示空状态（未找到二维码）
@@ -250,20 +541,33 @@ function showResults(results) {
   });
 }
 
+// 追加识别结果（监视模式中陆续发现的二维码，不清空已有结果）
+function appendResults(results) {
+  if (!results || results.length === 0) return;
+
+  noResultsEl.classList.add("hidden");
+  const existingCount = resultsList.querySelectorAll('.result-item').length;
+  const total = existingCount + results.length;
+  resultCountEl.textContent = total;
+
+  results.forEach((result, index) => {
+    const item = createResultItem(result, existingCount + index, total);
+    resultsList.appendChild(item);
+  });
+}
+
 // 显示 OCR 识别结果
 function showOcrResult(result) {
   console.log('[DEBUG] showOcrResult 被调用，参数:', result);
   hideStatus();
 
-  // 强制清空所有旧结果
-  const items = resultsList.querySelectorAll('.result-item');
-  console.log('[DEBUG] 清空旧结果数量:', items.length);
-  items.forEach(item => item.remove());
-
-  // 重置结果计数
-  resultCountEl.textContent = "0";
+  // 旧结果已在发起识别时清空；剪贴板识别会先显示二维码结果，文字结果追加在其后
+  const existingCount = resultsList.querySelectorAll('.result-item').length;
 
   if (!result || !result.text || result.text.trim() === "") {
+    if (existingCount > 0) {
+      return;
+    }
     console.log('[DEBUG] 未识别到文字，显示空状态');
     // 显示空状态（未识别到文字）
     noResultsEl.innerHTML = `
@@ -282,13 +586,83 @@ function showOcrResult(result) {
 
   console.log('[DEBUG] 识别到文字，长度:', result.text.length, '引擎:', result.language);
   noResultsEl.classList.add("hidden");
-  resultCountEl.textContent = "1";
+  resultCountEl.textContent = existingCount + 1;
 
   // 创建 OCR 结果项
   const item = createOcrResultItem(result);
   resultsList.appendChild(item);
 }
 
+// 显示表格识别结果
+function showTableResult(table) {
+  hideStatus();
+
+  if (!table || !table.rows || table.rows.length === 0) {
+    noResultsEl.innerHTML = `
+      <svg class="empty-icon" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
+        <rect x="3" y="3" width="18" height="18" rx="2"/>
+        <line x1="3" y1="9" x2="21" y2="9"/>
+        <line x1="12" y1="3" x2="12" y2="21"/>
+      </svg>
+      <p class="empty-title">未识别到表格</p>
+      <p class="empty-desc">请尝试只框选表格区域</p>
+    `;
+    noResultsEl.classList.remove("hidden");
+    return;
+  }
+
+  noResultsEl.classList.add("hidden");
+  resultCountEl.textContent = "1";
+  resultsList.appendChild(createTableResultItem(table));
+}
+
+// 创建表格结果项：预览表格，按需导出为 TSV/CSV/Markdown 复制
+function createTableResultItem(table) {
+  const item = document.createElement("div");
+  item.className = "result-item";
+
+  const typeLabel = document.createElement("span");
+  typeLabel.className = "result-type result-type-ocr";
+  typeLabel.textContent = `📊 表格识别 (${table.rows.length} 行 × ${table.rows[0].length} 列)`;
+
+  const content = document.createElement("div");
+  content.className = "result-content result-content-table";
+  const tableEl = document.createElement("table");
+  table.rows.forEach((row, index) => {
+    const tr = document.createElement("tr");
+    row.forEach((cell) => {
+      const td = document.createElement(index === 0 ? "th" : "td");
+      td.textContent = cell;
+      tr.appendChild(td);
+    });
+    tableEl.appendChild(tr);
+  });
+  content.appendChild(tableEl);
+
+  const actions = document.createElement("div");
+  actions.className = "result-actions";
+  [["Tsv", "复制 TSV"], ["Csv", "复制 CSV"], ["Markdown", "复制 Markdown"]].forEach(([format, label]) => {
+    const button = document.createElement("button");
+    button.className = "btn-copy";
+    button.textContent = label;
+    button.onclick = async () => {
+      try {
+        const text = await invoke("export_table", { table, format });
+        await copyToClipboard(text, button);
+      } catch (error) {
+        showError(`导出表格失败: ${error}`);
+      }
+    };
+    actions.appendChild(button);
+  });
+
+  item.appendChild(typeLabel);
+  item.appendChild(content);
+  item.appendChild(actions);
+
+  return item;
+}
+
 // 创建 OCR 结果项
 function createOcrResultItem(result) {
   const item = document.createElement("div");
@@ -297,7 +671,7 @@ function createOcrResultItem(result) {
   // 类型标签
   const typeLabel = document.createElement("span");
   typeLabel.className = "result-type result-type-ocr";
-  typeLabel.textContent = `📄 文字识别 (${result.language})`;
+  typeLabel.textContent = `📄 文字识别 (${result.language})` + getOcrLinesLabel(result) + getSourceLabel(result);
 
   // 内容
   const content = document.createElement("div");
@@ -323,6 +697,33 @@ function createOcrResultItem(result) {
   return item;
 }
 
+// 文本行数和平均置信度（引擎不提供置信度时只显示行数）
+function getOcrLinesLabel(result) {
+  const lines = result.lines || [];
+  if (lines.length === 0) {
+    return "";
+  }
+  let label = ` · ${lines.length} 行`;
+  const scores = lines.map((line) => line.confidence).filter((score) => score !== null && score !== undefined);
+  if (scores.length > 0) {
+    const average = scores.reduce((sum, score) => sum + score, 0) / scores.length;
+    label += ` · 置信度 ${Math.round(average * 100)}%`;
+  }
+  return label;
+}
+
+// 来源标注：文件名和帧序号（仅文件识别时存在）
+function getSourceLabel(result) {
+  let label = "";
+  if (result.source) {
+    label += ` · ${result.source}`;
+  }
+  if (result.frame !== null && result.frame !== undefined) {
+    label += ` · 第 ${result.frame + 1} 帧`;
+  }
+  return label;
+}
+
 // 创建结果项
 function createResultItem(result, index, total) {
   const item = document.createElement("div");
@@ -331,14 +732,31 @@ function createResultItem(result, index, total) {
   // 类型标签（多个二维码时显示序号）
   const typeLabel = document.createElement("span");
   typeLabel.className = "result-type";
-  const prefix = total > 1 ? `二维码${index + 1} - ` : "";
+  const prefix = total > 1 ? `${getFormatLabel(result.format)}${index + 1} - ` : "";
   typeLabel.textContent = prefix + getQrTypeLabel(result.qr_type);
+  if (total <= 1 && result.format && result.format !== "QrCode") {
+    typeLabel.textContent += ` · ${getFormatLabel(result.format)}`;
+  }
+  typeLabel.textContent += getSourceLabel(result);
+  // 不同位置的相同码合并为一条结果
+  if (result.count > 1) {
+    typeLabel.textContent += ` · 共 ${result.count} 处`;
+  }
 
   // 内容
   const content = document.createElement("div");
   content.className = "result-content";
   content.textContent = result.content;
 
+  // 结构化载荷（Wi-Fi、联系人等）
+  const payloadEl = createPayloadFields(result.payload);
+
+  // 结构化追加拼接状态（多个二维码组成的消息）
+  const appendEl = createStructuredAppendStatus(result.structured_append);
+
+  // 链接钓鱼风险提示
+  const riskEl = createRiskWarning(result.risk);
+
   // 操作按钮
   const actions = document.createElement("div");
   actions.className = "result-actions";
@@ -350,14 +768,210 @@ function createResultItem(result, index, total) {
 
   actions.appendChild(copyBtn);
 
+  // 链接按打开规则自动打开、确认后打开或禁止打开
+  if (result.qr_type === "Url" || result.risk) {
+    const openBtn = document.createElement("button");
+    openBtn.className = "btn-copy btn-open";
+    openBtn.textContent = "打开";
+    actions.appendChild(openBtn);
+    setupOpenButton(result.content, openBtn);
+  }
+
+  // 符号详情（版本、纠错等级等）
+  const detailsEl = createSymbolDetails(result.symbol);
+
   // 组装
   item.appendChild(typeLabel);
+  if (riskEl) {
+    item.appendChild(riskEl);
+  }
+  if (appendEl) {
+    item.appendChild(appendEl);
+  }
+  if (payloadEl) {
+    item.appendChild(payloadEl);
+  }
   item.appendChild(content);
+  if (detailsEl) {
+    item.appendChild(detailsEl);
+  }
   item.appendChild(actions);
 
   return item;
 }
 
+// 按链接打开规则设置打开按钮，命中自动打开规则时直接打开
+async function setupOpenButton(url, button) {
+  let decision;
+  try {
+    decision = await invoke("evaluate_open_policy", { url });
+  } catch (error) {
+    console.error('[ERROR] 判定链接打开规则失败:', error);
+    button.remove();
+    return;
+  }
+
+  button.title = `${OPEN_ACTION_LABELS[decision.action]}：${decision.reason}`;
+  if (decision.action === "Block") {
+    button.disabled = true;
+    button.textContent = "已禁止";
+    return;
+  }
+
+  button.onclick = () => openUrl(url, decision);
+  if (decision.action === "Open") {
+    await openUrl(url, decision);
+  }
+}
+
+// 打开链接（需确认时先弹出确认框，最终是否打开由后端按规则判定）
+async function openUrl(url, decision) {
+  let confirmed = false;
+  if (decision.action === "Confirm") {
+    confirmed = await window.__TAURI__.dialog.confirm(`${url}\n\n${decision.reason}`, {
+      title: "确认打开链接",
+      kind: "warning"
+    });
+    if (!confirmed) return;
+  }
+
+  try {
+    await invoke("open_url", { url, confirmed });
+  } catch (error) {
+    console.error('[ERROR] 打开链接失败:', error);
+    showToast(String(error), 'error');
+  }
+}
+
+// 链接风险等级显示名称
+const RISK_LEVEL_LABELS = {
+  Low: "ℹ️ 请留意",
+  Medium: "⚠️ 可疑链接",
+  High: "⛔ 高风险链接"
+};
+
+// 创建链接钓鱼风险提示（无风险时不显示）
+function createRiskWarning(risk) {
+  if (!risk || risk.level === "Safe") return null;
+
+  const warning = document.createElement("div");
+  warning.className = `result-risk result-risk-${risk.level.toLowerCase()}`;
+
+  const title = document.createElement("div");
+  title.className = "result-risk-title";
+  title.textContent = RISK_LEVEL_LABELS[risk.level];
+  if (risk.host) {
+    title.textContent += ` · 实际访问 ${risk.host}`;
+  }
+  warning.appendChild(title);
+
+  const list = document.createElement("ul");
+  risk.reasons.forEach(reason => {
+    const li = document.createElement("li");
+    li.textContent = reason.message;
+    list.appendChild(li);
+  });
+  warning.appendChild(list);
+
+  return warning;
+}
+
+// 创建结构化追加拼接状态提示
+function createStructuredAppendStatus(info) {
+  if (!info) return null;
+
+  const status = document.createElement("div");
+  status.className = "result-append";
+
+  const complete = info.found.length === info.total;
+  if (!complete) {
+    status.classList.add("result-append-warning");
+    const missing = [];
+    for (let i = 0; i < info.total; i++) {
+      if (!info.found.includes(i)) missing.push(i + 1);
+    }
+    status.textContent = `🧩 已找到 ${info.found.length}/${info.total} 部分，缺少第 ${missing.join("、")} 部分`;
+  } else if (info.parity_ok === false) {
+    status.classList.add("result-append-warning");
+    status.textContent = `🧩 已拼接 ${info.total} 个二维码，但奇偶校验不一致，内容可能有误`;
+  } else {
+    status.textContent = `🧩 已拼接 ${info.total} 个二维码`;
+  }
+
+  return status;
+}
+
+// 创建二维码符号详情视图
+function createSymbolDetails(symbol) {
+  if (!symbol) return null;
+
+  const details = document.createElement("details");
+  details.className = "result-details";
+
+  const summary = document.createElement("summary");
+  summary.textContent = "详情";
+  details.appendChild(summary);
+
+  const rows = [
+    ["版本", `${symbol.version} (${symbol.version * 4 + 17}×${symbol.version * 4 + 17})`],
+    ["纠错等级", symbol.ecc_level],
+    ["掩码", symbol.mask],
+    ["ECI", symbol.eci ?? "无"],
+    ["字符集", symbol.charset],
+    ["原始字节 (Base64)", symbol.raw_bytes]
+  ];
+
+  const list = document.createElement("dl");
+  list.className = "result-payload";
+  rows.forEach(([label, value]) => {
+    const dt = document.createElement("dt");
+    dt.textContent = label;
+    const dd = document.createElement("dd");
+    dd.textContent = value;
+    list.appendChild(dt);
+    list.appendChild(dd);
+  });
+  details.appendChild(list);
+
+  return details;
+}
+
+// 将结构化载荷渲染为字段列表
+function createPayloadFields(payload) {
+  if (!payload) return null;
+
+  const fieldLabels = {
+    Wifi: { ssid: "网络名称", auth: "加密方式", password: "密码", hidden: "隐藏网络" },
+    Contact: { name: "姓名", organization: "单位", title: "职位", phones: "电话", emails: "邮箱", urls: "网址", address: "地址", note: "备注" },
+    Geo: { latitude: "纬度", longitude: "经度", altitude: "海拔", query: "地点" },
+    Sms: { number: "号码", body: "内容" },
+    Event: { summary: "标题", start: "开始", end: "结束", location: "地点", description: "描述" },
+    Email: { to: "收件人", subject: "主题", body: "正文" }
+  };
+
+  const labels = fieldLabels[payload.kind];
+  if (!labels) return null;
+
+  const list = document.createElement("dl");
+  list.className = "result-payload";
+
+  Object.entries(labels).forEach(([key, label]) => {
+    let value = payload.data[key];
+    if (Array.isArray(value)) value = value.join("、");
+    if (typeof value === "boolean") value = value ? "是" : "否";
+    if (value === null || value === undefined || value === "") return;
+
+    const dt = document.createElement("dt");
+    dt.textContent = label;
+    const dd = document.createElement("dd");
+    dd.textContent = value;
+    list.appendChild(dt);
+    list.appendChild(dd);
+  });
+
+  return list;
+}
+
 // 获取二维码类型标签
 function getQrTypeLabel(qrType) {
   const labels = {
@@ -365,11 +979,38 @@ function getQrTypeLabel(qrType) {
     "Text": "📝 文本",
     "Email": "📧 邮箱",
     "Phone": "📞 电话",
+    "Wifi": "📶 Wi-Fi",
+    "Contact": "👤 联系人",
+    "Geo": "📍 位置",
+    "Sms": "💬 短信",
+    "Event": "📅 日程",
     "Other": "🔖 其他"
   };
   return labels[qrType] || "🔖 其他";
 }
 
+// 获取码制标签
+function getFormatLabel(format) {
+  const labels = {
+    "QrCode": "二维码",
+    "MicroQrCode": "Micro QR",
+    "RectangularMicroQrCode": "rMQR",
+    "Ean13": "EAN-13",
+    "Ean8": "EAN-8",
+    "UpcA": "UPC-A",
+    "UpcE": "UPC-E",
+    "Code128": "Code 128",
+    "Code39": "Code 39",
+    "Code93": "Code 93",
+    "Codabar": "Codabar",
+    "Itf": "ITF",
+    "DataMatrix": "Data Matrix",
+    "Pdf417": "PDF417",
+    "Aztec": "Aztec"
+  };
+  return labels[format] || "条码";
+}
+
 // 复制到剪贴板
 async function copyToClipboard(text, button) {
   try {
@@ -394,6 +1035,7 @@ function clearResults() {
   // 移除所有结果项
   const items = resultsList.querySelectorAll('.result-item');
   items.forEach(item => item.remove());
+  scanDiagnosticsEl.classList.add("hidden");
 
   // 恢复空状态
   noResultsEl.innerHTML = `
@@ -454,6 +1096,7 @@ function initSettings() {
   shortcutFullscreenInput.value = settings.shortcuts.fullscreen;
   shortcutRegionInput.value = settings.shortcuts.region;
   shortcutOcrInput.value = settings.shortcuts.ocr;
+  shortcutClipboardInput.value = settings.shortcuts.clipboard;
 
   // 更新按钮上显示的快捷键
   updateShortcutDisplay();
@@ -485,8 +1128,226 @@ function saveSettingsToStorage(settings) {
   localStorage.setItem('appSettings', JSON.stringify(settings));
 }
 
+// 显示最近一次扫描的诊断信息（命中策略和耗时）
+async function showScanDiagnostics() {
+  try {
+    const diagnostics = await invoke("get_scan_diagnostics");
+    if (!diagnostics || diagnostics.length === 0) {
+      scanDiagnosticsEl.classList.add("hidden");
+      return;
+    }
+
+    const totalMs = diagnostics.reduce((sum, d) => sum + d.total_ms, 0);
+    const hits = diagnostics
+      .filter(d => d.succeeded !== null)
+      .map(d => {
+        const strategy = d.strategies.find(s => s.index === d.succeeded);
+        return `策略 ${d.succeeded + 1}（${PREPROCESS_LABELS[strategy.method] ?? strategy.method} ×${strategy.scale}）`;
+      });
+    scanDiagnosticsEl.textContent = hits.length > 0
+      ? `命中 ${hits.join("、")}，耗时 ${totalMs}ms`
+      : `所有策略均未识别到结果，耗时 ${totalMs}ms`;
+    scanDiagnosticsEl.title = diagnostics
+      .flatMap(d => d.strategies)
+      .map(s => `策略 ${s.index + 1}: ${s.elapsed_ms}ms，网格 ${s.grids_detected}，解码 ${s.decoded}${s.cancelled ? "（已取消）" : ""}`)
+      .join("\n");
+    scanDiagnosticsEl.classList.remove("hidden");
+  } catch (error) {
+    console.error('[ERROR] 获取扫描诊断信息失败:', error);
+  }
+}
+
+// 渲染识别策略编辑列表
+function renderPipelineEditor() {
+  pipelineListEl.innerHTML = "";
+  pipelineKeepDuplicatesEl.checked = !!editingPipeline.keep_duplicates;
+  const strategies = editingPipeline.strategies;
+
+  strategies.forEach((strategy, index) => {
+    const item = document.createElement("div");
+    item.className = "pipeline-item";
+    item.classList.toggle("disabled", !strategy.enabled);
+
+    const enabled = document.createElement("input");
+    enabled.type = "checkbox";
+    enabled.checked = strategy.enabled;
+    enabled.onchange = () => {
+      strategy.enabled = enabled.checked;
+      item.classList.toggle("disabled", !strategy.enabled);
+    };
+
+    const method = document.createElement("span");
+    method.className = "pipeline-method";
+    method.textContent = `${index + 1}. ${PREPROCESS_LABELS[strategy.method] ?? strategy.method}`;
+
+    const scale = document.createElement("input");
+    scale.type = "number";
+    scale.className = "pipeline-scale";
+    scale.min = "0.1";
+    scale.max = "4";
+    scale.step = "0.25";
+    scale.value = strategy.scale;
+    scale.title = "缩放比例";
+    scale.onchange = () => {
+      strategy.scale = parseFloat(scale.value) || 1;
+    };
+
+    const up = document.createElement("button");
+    up.className = "pipeline-move";
+    up.textContent = "↑";
+    up.disabled = index === 0;
+    up.onclick = () => movePipelineStrategy(index, -1);
+
+    const down = document.createElement("button");
+    down.className = "pipeline-move";
+    down.textContent = "↓";
+    down.disabled = index === strategies.length - 1;
+    down.onclick = () => movePipelineStrategy(index, 1);
+
+    item.appendChild(enabled);
+    item.appendChild(method);
+    item.appendChild(scale);
+    item.appendChild(up);
+    item.appendChild(down);
+    pipelineListEl.appendChild(item);
+  });
+}
+
+// 调整策略顺序
+function movePipelineStrategy(index, offset) {
+  const strategies = editingPipeline.strategies;
+  const target = index + offset;
+  if (target < 0 || target >= strategies.length) return;
+  [strategies[index], strategies[target]] = [strategies[target], strategies[index]];
+  renderPipelineEditor();
+}
+
+// 恢复默认识别策略
+async function resetPipeline() {
+  try {
+    editingPipeline = await invoke("get_default_scan_pipeline");
+    renderPipelineEditor();
+  } catch (error) {
+    console.error('[ERROR] 获取默认识别策略失败:', error);
+  }
+}
+
+// 渲染链接打开规则编辑列表
+function renderOpenPolicyEditor() {
+  openRuleListEl.innerHTML = "";
+  openDefaultActionEl.value = editingOpenPolicy.default_action;
+  const rules = editingOpenPolicy.rules;
+
+  rules.forEach((rule, index) => {
+    const item = document.createElement("div");
+    item.className = "pipeline-item open-rule-item";
+    item.classList.toggle("disabled", !rule.enabled);
+
+    const enabled = document.createElement("input");
+    enabled.type = "checkbox";
+    enabled.checked = rule.enabled;
+    enabled.onchange = () => {
+      rule.enabled = enabled.checked;
+      item.classList.toggle("disabled", !rule.enabled);
+    };
+
+    const type = createSelect(RULE_PATTERN_LABELS, rule.pattern.type);
+    type.onchange = () => {
+      rule.pattern.type = type.value;
+    };
+
+    const value = document.createElement("input");
+    value.type = "text";
+    value.className = "open-rule-value";
+    value.value = rule.pattern.value;
+    value.placeholder = "example.com、*.example.com、weixin 或正则";
+    value.onchange = () => {
+      rule.pattern.value = value.value.trim();
+    };
+
+    const action = createSelect(OPEN_ACTION_LABELS, rule.action);
+    action.onchange = () => {
+      rule.action = action.value;
+    };
+
+    const up = document.createElement("button");
+    up.className = "pipeline-move";
+    up.textContent = "↑";
+    up.disabled = index === 0;
+    up.onclick = () => moveOpenRule(index, -1);
+
+    const down = document.createElement("button");
+    down.className = "pipeline-move";
+    down.textContent = "↓";
+    down.disabled = index === rules.length - 1;
+    down.onclick = () => moveOpenRule(index, 1);
+
+    const remove = document.createElement("button");
+    remove.className = "pipeline-move";
+    remove.textContent = "✕";
+    remove.title = "删除规则";
+    remove.onclick = () => {
+      rules.splice(index, 1);
+      renderOpenPolicyEditor();
+    };
+
+    item.appendChild(enabled);
+    item.appendChild(type);
+    item.appendChild(value);
+    item.appendChild(action);
+    item.appendChild(up);
+    item.appendChild(down);
+    item.appendChild(remove);
+    openRuleListEl.appendChild(item);
+  });
+}
+
+// 创建下拉框，选项为 { 值: 显示名称 }
+function createSelect(labels, selected) {
+  const select = document.createElement("select");
+  select.className = "open-rule-select";
+  Object.entries(labels).forEach(([value, label]) => {
+    const option = document.createElement("option");
+    option.value = value;
+    option.textContent = label;
+    select.appendChild(option);
+  });
+  select.value = selected;
+  return select;
+}
+
+// 添加链接打开规则
+function addOpenRule() {
+  editingOpenPolicy.rules.push({
+    pattern: { type: "Domain", value: "" },
+    action: "Open",
+    enabled: true
+  });
+  renderOpenPolicyEditor();
+  openRuleListEl.lastElementChild?.querySelector(".open-rule-value")?.focus();
+}
+
+// 调整规则顺序（规则按顺序匹配，第一条命中的规则生效）
+function moveOpenRule(index, offset) {
+  const rules = editingOpenPolicy.rules;
+  const target = index + offset;
+  if (target < 0 || target >= rules.length) return;
+  [rules[index], rules[target]] = [rules[target], rules[index]];
+  renderOpenPolicyEditor();
+}
+
+// 恢复默认链接打开规则
+async function resetOpenPolicy() {
+  try {
+    editingOpenPolicy = await invoke("get_default_open_policy");
+    renderOpenPolicyEditor();
+  } catch (error) {
+    console.error('[ERROR] 获取默认链接打开规则失败:', error);
+  }
+}
+
 // 打开设置弹窗
-function openSettings() {
+async function openSettings() {
   const settings = loadSettings();
 
   // 填充当前设置值
@@ -497,6 +1358,34 @@ function openSettings() {
 
   shortcutFullscreenInput.value = settings.shortcuts.fullscreen;
   shortcutRegionInput.value = settings.shortcuts.region;
+  shortcutOcrInput.value = settings.shortcuts.ocr;
+  shortcutClipboardInput.value = settings.shortcuts.clipboard;
+  watchIntervalInput.value = settings.watchIntervalMs / 1000;
+  historyEnabledInput.checked = settings.historyEnabled;
+
+  // OCR 模型包
+  ocrModelsDirInput.value = settings.ocrModelsDir;
+  try {
+    renderOcrModelPacks(await invoke("list_ocr_model_packs"), settings.ocrModelPack);
+  } catch (error) {
+    console.error('[ERROR] 获取 OCR 模型包失败:', error);
+  }
+
+  // 识别策略（未自定义时使用后端默认配置）
+  try {
+    editingPipeline = structuredClone(settings.pipeline ?? await invoke("get_default_scan_pipeline"));
+    renderPipelineEditor();
+  } catch (error) {
+    console.error('[ERROR] 获取识别策略失败:', error);
+  }
+
+  // 链接打开规则（未自定义时使用后端默认配置）
+  try {
+    editingOpenPolicy = structuredClone(settings.openPolicy ?? await invoke("get_default_open_policy"));
+    renderOpenPolicyEditor();
+  } catch (error) {
+    console.error('[ERROR] 获取链接打开规则失败:', error);
+  }
 
   settingsModal.classList.remove("hidden");
 }
@@ -505,6 +1394,42 @@ function openSettings() {
 function closeSettings() {
   settingsModal.classList.add("hidden");
   stopRecordingShortcut();
+
+  // 选择目录时已更新后端的模型目录以列出模型包，取消时恢复为已保存的目录
+  invoke("set_ocr_models_dir", { path: loadSettings().ocrModelsDir || null }).catch((error) => {
+    console.error('[ERROR] 恢复 OCR 模型目录失败:', error);
+  });
+}
+
+// 选择外部 OCR 模型目录
+async function chooseOcrModelsDir() {
+  const dir = await window.__TAURI__.dialog.open({ directory: true });
+  if (dir) {
+    await changeOcrModelsDir(dir);
+  }
+}
+
+// 更换模型目录后重新列出模型包
+async function changeOcrModelsDir(dir) {
+  try {
+    const packs = await invoke("set_ocr_models_dir", { path: dir || null });
+    ocrModelsDirInput.value = dir;
+    renderOcrModelPacks(packs, ocrModelPackEl.value);
+  } catch (error) {
+    showToast(`${error}`, 'error');
+  }
+}
+
+// 填充模型包下拉框，选中的模型包不存在时选择内置模型包
+function renderOcrModelPacks(packs, selectedId) {
+  ocrModelPackEl.innerHTML = "";
+  packs.forEach((pack) => {
+    const option = document.createElement("option");
+    option.value = pack.id;
+    option.textContent = pack.languages.length > 0 ? `${pack.name} (${pack.languages.join(", ")})` : pack.name;
+    ocrModelPackEl.appendChild(option);
+  });
+  ocrModelPackEl.value = packs.some((pack) => pack.id === selectedId) ? selectedId : "builtin-ch";
 }
 
 // 保存设置
@@ -513,12 +1438,22 @@ async function saveSettings() {
   const shortcuts = {
     fullscreen: shortcutFullscreenInput.value,
     region: shortcutRegionInput.value,
-    ocr: shortcutOcrInput.value
+    ocr: shortcutOcrInput.value,
+    clipboard: shortcutClipboardInput.value
   };
 
+  // 监视间隔限制在 0.2~60 秒
+  const watchSeconds = Math.min(60, Math.max(0.2, parseFloat(watchIntervalInput.value) || 1));
+
   const settings = {
     closeBehavior,
-    shortcuts
+    shortcuts,
+    watchIntervalMs: Math.round(watchSeconds * 1000),
+    historyEnabled: historyEnabledInput.checked,
+    ocrModelsDir: ocrModelsDirInput.value,
+    ocrModelPack: ocrModelPackEl.value || "builtin-ch",
+    pipeline: editingPipeline,
+    openPolicy: editingOpenPolicy
   };
 
   try {
@@ -549,12 +1484,325 @@ async function applySettingsToBackend(settings) {
   // 更新退出行为
   await invoke("set_close_behavior", { behavior: settings.closeBehavior });
 
+  // 更新是否保存识别历史
+  await invoke("set_history_enabled", { enabled: settings.historyEnabled });
+
+  // 更新 OCR 模型包（模型目录已不存在时回退到内置模型包）
+  try {
+    await invoke("set_ocr_models_dir", { path: settings.ocrModelsDir || null });
+    await invoke("select_ocr_model_pack", { id: settings.ocrModelPack });
+  } catch (error) {
+    console.error('[ERROR] 应用 OCR 模型包失败，使用内置模型包:', error);
+    await invoke("select_ocr_model_pack", { id: "builtin-ch" });
+  }
+
+  // 更新识别策略
+  if (settings.pipeline) {
+    await invoke("set_scan_pipeline", { pipeline: settings.pipeline });
+  }
+
+  // 更新链接打开规则（规则无效时后端返回错误，设置不会保存）
+  if (settings.openPolicy) {
+    await invoke("set_open_policy", { policy: settings.openPolicy });
+  }
+
   // 更新快捷键
   await invoke("update_shortcuts", {
     fullscreenShortcut: convertShortcutToBackend(settings.shortcuts.fullscreen),
     regionShortcut: convertShortcutToBackend(settings.shortcuts.region),
-    ocrShortcut: convertShortcutToBackend(settings.shortcuts.ocr)
+    ocrShortcut: convertShortcutToBackend(settings.shortcuts.ocr),
+    clipboardShortcut: convertShortcutToBackend(settings.shortcuts.clipboard)
+  });
+}
+
+// ==================== 监视模式 ====================
+
+// 监视所有屏幕
+async function handleWatchScreen() {
+  try {
+    await invoke("start_watch", {
+      options: { target: { type: "AllScreens" }, interval_ms: loadSettings().watchIntervalMs }
+    });
+  } catch (error) {
+    console.error('[ERROR] 开始监视失败:', error);
+    showToast('开始监视失败: ' + error, 'error');
+  }
+}
+
+// 框选区域后监视该区域
+async function handleWatchRegion() {
+  try {
+    await invoke("start_watch_region_selection", { intervalMs: loadSettings().watchIntervalMs });
+  } catch (error) {
+    console.error('[ERROR] 选择监视区域失败:', error);
+    showToast('选择监视区域失败: ' + error, 'error');
+    await invoke("show_window");
+  }
+}
+
+// 停止监视
+async function handleStopWatch() {
+  try {
+    await invoke("stop_watch");
+  } catch (error) {
+    console.error('[ERROR] 停止监视失败:', error);
+  }
+}
+
+// 更新监视状态栏
+function updateWatchBar(status) {
+  isWatching = status.running;
+  watchBarEl.classList.toggle("running", status.running);
+  watchScreenBtn.classList.toggle("hidden", status.running);
+  watchRegionBtn.classList.toggle("hidden", status.running);
+  watchStopBtn.classList.toggle("hidden", !status.running);
+
+  if (!status.running) {
+    watchLabelEl.textContent = "监视模式";
+    return;
+  }
+  const target = status.options.target;
+  const targetLabel = target.type === "Region"
+    ? `区域 ${target.width}×${target.height}`
+    : target.type === "Screen" ? `屏幕 ${target.index + 1}` : "所有屏幕";
+  watchLabelEl.textContent = `正在监视${targetLabel}，每 ${status.options.interval_ms / 1000} 秒检查一次`;
+}
+
+// ==================== 识别历史 ====================
+
+// 打开历史弹窗
+async function openHistory() {
+  historyModal.classList.remove("hidden");
+  historySearchEl.focus();
+  await refreshHistory();
+}
+
+// 关闭历史弹窗
+function closeHistory() {
+  historyModal.classList.add("hidden");
+}
+
+// 根据筛选条件生成查询（日期按本地时区，截止日期包含当天）
+function readHistoryQuery() {
+  const dayStart = (value) => value ? new Date(`${value}T00:00:00`).getTime() : null;
+  const until = dayStart(historyUntilEl.value);
+  return {
+    text: historySearchEl.value.trim() || null,
+    qr_type: historyTypeEl.value || null,
+    source: historySourceEl.value || null,
+    since: dayStart(historySinceEl.value),
+    until: until === null ? null : until + 24 * 60 * 60 * 1000,
+    pinned_only: historyPinnedOnlyEl.checked,
+    limit: null
+  };
+}
+
+// 重新查询并显示历史记录
+async function refreshHistory() {
+  try {
+    const entries = await invoke("query_history", { query: readHistoryQuery() });
+    renderHistory(entries);
+  } catch (error) {
+    console.error('[ERROR] 查询历史记录失败:', error);
+    historyListEl.innerHTML = "";
+    historyListEl.appendChild(createHistoryEmpty(`历史记录不可用: ${error}`));
+  }
+}
+
+// 历史列表为空时的提示
+function createHistoryEmpty(message) {
+  const empty = document.createElement("div");
+  empty.className = "history-empty";
+  empty.textContent = message;
+  return empty;
+}
+
+// 显示历史记录列表
+function renderHistory(entries) {
+  historyListEl.innerHTML = "";
+  if (entries.length === 0) {
+    historyListEl.appendChild(createHistoryEmpty("没有符合条件的记录"));
+    return;
+  }
+  entries.forEach(entry => historyListEl.appendChild(createHistoryItem(entry)));
+}
+
+// 历史来源名称
+function getHistorySourceLabel(source) {
+  const labels = {
+    "FullScreen": "全屏识别",
+    "Region": "区域识别",
+    "Ocr": "文字识别",
+    "Clipboard": "剪贴板",
+    "File": "图片文件",
+    "Watch": "监视模式"
+  };
+  return labels[source] || source;
+}
+
+// 创建历史记录项
+function createHistoryItem(entry) {
+  const item = document.createElement("div");
+  item.className = "history-item" + (entry.pinned ? " pinned" : "");
+
+  if (entry.thumbnail) {
+    const thumbnail = document.createElement("img");
+    thumbnail.className = "history-thumbnail";
+    thumbnail.src = entry.thumbnail;
+    thumbnail.alt = "";
+    item.appendChild(thumbnail);
+  }
+
+  const body = document.createElement("div");
+  body.className = "history-body";
+
+  const meta = document.createElement("div");
+  meta.className = "history-meta";
+  const kind = entry.format ? `${getQrTypeLabel(entry.qr_type)} · ${getFormatLabel(entry.format)}` : "📝 文字";
+  meta.textContent = `${entry.pinned ? "📌 " : ""}${kind} · ${getHistorySourceLabel(entry.source)} · ${new Date(entry.timestamp).toLocaleString()}`;
+
+  const content = document.createElement("div");
+  content.className = "history-text";
+  content.textContent = entry.content;
+  content.title = entry.content;
+
+  body.appendChild(meta);
+  body.appendChild(content);
+  item.appendChild(body);
+
+  const actions = document.createElement("div");
+  actions.className = "history-actions";
+
+  const copyBtn = document.createElement("button");
+  copyBtn.className = "btn-copy";
+  copyBtn.textContent = "复制";
+  copyBtn.onclick = () => copyToClipboard(entry.content, copyBtn);
+
+  const pinBtn = document.createElement("button");
+  pinBtn.className = "btn-copy";
+  pinBtn.textContent = entry.pinned ? "取消置顶" : "置顶";
+  pinBtn.onclick = () => updateHistoryEntry("set_history_pinned", { id: entry.id, pinned: !entry.pinned });
+
+  const deleteBtn = document.createElement("button");
+  deleteBtn.className = "btn-copy";
+  deleteBtn.textContent = "删除";
+  deleteBtn.onclick = () => updateHistoryEntry("delete_history_entry", { id: entry.id });
+
+  actions.appendChild(copyBtn);
+  actions.appendChild(pinBtn);
+  actions.appendChild(deleteBtn);
+  item.appendChild(actions);
+
+  return item;
+}
+
+// 置顶或删除记录后刷新列表
+async function updateHistoryEntry(command, args) {
+  try {
+    await invoke(command, args);
+    await refreshHistory();
+  } catch (error) {
+    console.error('[ERROR] 更新历史记录失败:', error);
+    showToast('操作失败: ' + error, 'error');
+  }
+}
+
+// 导出当前筛选条件下的历史记录
+async function exportHistory() {
+  const format = historyExportFormatEl.value;
+  try {
+    // 保存对话框由后端弹出，取消时返回 null
+    const count = await invoke("export_history", { query: readHistoryQuery(), format });
+    if (count === null) {
+      return;
+    }
+    showToast(`已导出 ${count} 条记录`, 'success');
+  } catch (error) {
+    console.error('[ERROR] 导出历史记录失败:', error);
+    showToast('导出失败: ' + error, 'error');
+  }
+}
+
+// 清空历史记录（保留置顶的记录）
+async function clearHistory() {
+  const confirmed = await window.__TAURI__.dialog.confirm("确定清空历史记录吗？置顶的记录会保留。", {
+    title: "清空历史记录",
+    kind: "warning"
   });
+  if (!confirmed) {
+    return;
+  }
+  try {
+    const removed = await invoke("clear_history", { keepPinned: true });
+    showToast(`已清空 ${removed} 条记录`, 'success');
+    await refreshHistory();
+  } catch (error) {
+    console.error('[ERROR] 清空历史记录失败:', error);
+    showToast('清空失败: ' + error, 'error');
+  }
+}
+
+// ==================== 二维码生成 ====================
+
+// 打开生成弹窗
+function openGenerator() {
+  generatorModal.classList.remove("hidden");
+  generatorTextEl.focus();
+}
+
+// 关闭生成弹窗
+function closeGenerator() {
+  generatorModal.classList.add("hidden");
+}
+
+// 读取生成选项
+function readGeneratorOptions() {
+  const version = document.querySelector("#generator-version").value;
+  return {
+    ecc_level: document.querySelector("#generator-ecc").value,
+    version: version ? parseInt(version, 10) : null,
+    quiet_zone: parseInt(document.querySelector("#generator-quiet-zone").value, 10) || 0,
+    foreground: document.querySelector("#generator-foreground").value,
+    background: document.querySelector("#generator-background").value
+  };
+}
+
+// 生成二维码并显示预览
+async function handleGenerate() {
+  const text = generatorTextEl.value;
+  if (!text) {
+    showToast("请输入要编码的内容", "error");
+    return;
+  }
+
+  try {
+    const generated = await invoke("generate_qr", { text, options: readGeneratorOptions() });
+    lastGeneratedQr = generated;
+
+    // SVG 由后端生成，仅包含路径和颜色，可直接插入预览
+    generatorPreviewEl.innerHTML = generated.svg;
+    generatorInfoEl.textContent = `版本 ${generated.version} · ${generated.modules}×${generated.modules} 模块`;
+    generatorCopySvg.disabled = false;
+    generatorCopyPng.disabled = false;
+  } catch (error) {
+    lastGeneratedQr = null;
+    generatorPreviewEl.innerHTML = "";
+    generatorInfoEl.textContent = "";
+    generatorCopySvg.disabled = true;
+    generatorCopyPng.disabled = true;
+    showToast(`生成失败: ${error}`, "error");
+  }
+}
+
+// 复制 PNG 图片到剪贴板
+async function copyGeneratedPng() {
+  try {
+    const blob = new Blob([new Uint8Array(lastGeneratedQr.png)], { type: "image/png" });
+    await navigator.clipboard.write([new ClipboardItem({ "image/png": blob })]);
+    showToast("图片已复制到剪贴板", "success");
+  } catch (error) {
+    showError(`复制失败: ${error}`);
+  }
 }
 
 // 显示提示消息
@@ -600,6 +1848,11 @@ function updateShortcutDisplay() {
   if (ocrKbd) {
     ocrKbd.textContent = settings.shortcuts.ocr;
   }
+
+  const clipboardKbd = document.querySelector("#scan-clipboard-btn .scan-btn-kbd");
+  if (clipboardKbd) {
+    clipboardKbd.textContent = settings.shortcuts.clipboard;
+  }
 }
 
 // 将前端快捷键格式转换为后端格式
@@ -614,12 +1867,22 @@ function convertShortcutToBackend(shortcut) {
 
 // ==================== 快捷键录制 ====================
 
+// 获取快捷键对应的输入框
+function getShortcutInput(target) {
+  return {
+    fullscreen: shortcutFullscreenInput,
+    region: shortcutRegionInput,
+    ocr: shortcutOcrInput,
+    clipboard: shortcutClipboardInput
+  }[target];
+}
+
 // 开始录制快捷键
 function startRecordingShortcut(target) {
   isRecordingShortcut = true;
   currentRecordingTarget = target;
 
-  const input = target === 'fullscreen' ? shortcutFullscreenInput : (target === 'region' ? shortcutRegionInput : shortcutOcrInput);
+  const input = getShortcutInput(target);
   const btn = document.querySelector(`.shortcut-record-btn[data-target="${target}"]`);
 
   input.classList.add("recording");
@@ -636,7 +1899,7 @@ function stopRecordingShortcut() {
   if (!isRecordingShortcut) return;
 
   const target = currentRecordingTarget;
-  const input = target === 'fullscreen' ? shortcutFullscreenInput : (target === 'region' ? shortcutRegionInput : shortcutOcrInput);
+  const input = getShortcutInput(target);
   const btn = document.querySelector(`.shortcut-record-btn[data-target="${target}"]`);
 
   input.classList.remove("recording");
@@ -649,7 +1912,7 @@ function stopRecordingShortcut() {
   // 如果没有录制到有效快捷键，恢复原值
   if (input.value === "请按下快捷键...") {
     const settings = loadSettings();
-    input.value = target === 'fullscreen' ? settings.shortcuts.fullscreen : (target === 'region' ? settings.shortcuts.region : settings.shortcuts.ocr);
+    input.value = settings.shortcuts[target];
   }
 
   isRecordingShortcut = false;
@@ -689,7 +1952,7 @@ function handleShortcutKeydown(event) {
   }
 
   // 更新输入框
-  const input = currentRecordingTarget === 'fullscreen' ? shortcutFullscreenInput : (currentRecordingTarget === 'region' ? shortcutRegionInput : shortcutOcrInput);
+  const input = getShortcutInput(currentRecordingTarget);
   input.value = shortcut;
 
   // 停止录制